@@ -0,0 +1,341 @@
+// src/backend.rs — 网络后端抽象，菜单逻辑只依赖此 trait
+
+use crate::config::{BackendKind, Config};
+use crate::i18n::t;
+use crate::iwd::Iwd;
+use crate::nm_dbus::NmDbus;
+use crate::nmcli::Nmcli;
+use crate::types::{
+    AccessPoint, ConnectRequest, ConnectResult, ConnectionDetails, RadioState, WifiDevice,
+};
+use crate::wpa::WpaSupplicant;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// 后端操作的网卡：扫描 / 连接用 station，热点用 hotspot（None 由后端自选第一块）
+pub struct Interfaces {
+    station: Mutex<Option<String>>,
+    hotspot: Option<String>,
+}
+
+impl Interfaces {
+    pub fn new(station: Option<String>, hotspot: Option<String>) -> Self {
+        Self {
+            station: Mutex::new(station),
+            hotspot,
+        }
+    }
+
+    /// 当前用于扫描和连接的网卡
+    pub fn station(&self) -> Option<String> {
+        self.station.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// 热点网卡；未单独配置时与 station 相同
+    pub fn hotspot(&self) -> Option<String> {
+        self.hotspot.clone().or_else(|| self.station())
+    }
+
+    /// 菜单中切换网卡
+    pub fn select(&self, ifname: Option<String>) {
+        if let Ok(mut s) = self.station.lock() {
+            *s = ifname;
+        }
+    }
+}
+
+/// 一个可用的 Wi-Fi 管理后端（nmcli、D-Bus 等）
+#[async_trait]
+pub trait NetworkBackend: Send + Sync {
+    /// 后端名称，用于日志和错误提示
+    fn name(&self) -> &'static str;
+
+    // ── 扫描 & 查询 ───────────────────────────────────────────
+
+    /// 触发一次重新扫描（不等待结果）
+    async fn rescan(&self);
+    /// 获取接入点列表，按信号强度降序，当前连接的置顶
+    async fn list_access_points(&self) -> Result<Vec<AccessPoint>>;
+    /// 当前已连接的 SSID（None 表示未连接）
+    async fn current_ssid(&self) -> Option<String>;
+    /// 当前连接的 SSID 和信号强度；只读活动连接，不扫描也不列出所有 AP
+    async fn current_link(&self) -> Option<(String, u8)> {
+        let aps = self.list_access_points().await.ok()?;
+        let ap = aps.into_iter().find(|ap| ap.in_use)?;
+        Some((ap.ssid, ap.signal))
+    }
+    /// 已保存的所有 Wi-Fi 配置名称
+    async fn saved_connections(&self) -> Result<Vec<String>>;
+    /// 已保存配置中标记为隐藏网络的名称
+    async fn saved_hidden(&self) -> Result<Vec<String>>;
+    /// 已保存配置的密码
+    async fn saved_password(&self, name: &str) -> Option<String>;
+    /// 当前 IP 地址
+    async fn get_ip(&self) -> Option<String>;
+    /// 当前连接详情
+    async fn get_details(&self, ssid: &str, ping_host: &str) -> Result<ConnectionDetails>;
+
+    // ── 网卡 ─────────────────────────────────────────────────
+
+    /// 当前选中的网卡
+    fn interfaces(&self) -> &Interfaces;
+    /// 所有 Wi-Fi 网卡
+    async fn devices(&self) -> Result<Vec<WifiDevice>>;
+
+    // ── 连接管理 ─────────────────────────────────────────────
+
+    /// 唤起已保存的配置，可指定 BSSID；结果语义同 connect_new
+    async fn connect_saved(&self, name: &str, bssid: Option<&str>, timeout: u64) -> ConnectResult;
+    /// 连接新网络，返回带语义的结果
+    async fn connect_new(&self, req: &ConnectRequest, timeout: u64) -> ConnectResult;
+    /// 断开连接
+    async fn disconnect(&self, name: &str) -> Result<()>;
+    /// 忘记（删除）已保存的配置
+    async fn forget(&self, name: &str) -> Result<()>;
+
+    // ── 无线电 & 热点 ─────────────────────────────────────────
+
+    async fn radio_state(&self) -> RadioState;
+    async fn set_radio(&self, enable: bool) -> Result<()>;
+    /// 正在运行的热点名称
+    async fn hotspot_active(&self) -> Option<String>;
+    /// 已保存但未开启的热点配置
+    async fn hotspot_profile(&self) -> Option<String>;
+    /// 开启已保存的热点配置
+    async fn hotspot_up(&self, name: &str) -> Result<()>;
+    /// 关闭正在运行的热点
+    async fn hotspot_down(&self, name: &str) -> Result<()>;
+    /// 新建并开启热点
+    async fn create_hotspot(&self, ssid: &str, password: &str) -> Result<()>;
+
+    /// 能否按 BSSID 连接；不能时 connect_* 忽略传入的 BSSID
+    fn supports_bssid(&self) -> bool {
+        true
+    }
+
+    /// 启动 VPN 配置（不支持的后端直接报错）
+    async fn activate_vpn(&self, name: &str) -> Result<()> {
+        Err(anyhow!(t!(
+            VpnUnsupported,
+            backend = self.name(),
+            name = name
+        )))
+    }
+}
+
+/// 共享的后端句柄（后台扫描任务也要持有一份）
+pub type Backend = Arc<dyn NetworkBackend>;
+
+/// 扫描并取回接入点列表；已保存的隐藏网络即使没被扫到也列出来，可以直接唤起
+pub async fn scan_with_hidden(net: &Backend) -> Result<Vec<AccessPoint>> {
+    net.rescan().await;
+    let mut aps = net.list_access_points().await?;
+    for name in net.saved_hidden().await.unwrap_or_default() {
+        if !aps.iter().any(|ap| ap.ssid == name) {
+            aps.push(AccessPoint::saved_hidden(name));
+        }
+    }
+    Ok(aps)
+}
+
+/// 按配置选择后端
+pub fn from_config(cfg: &Config) -> Backend {
+    let ifaces = Interfaces::new(cfg.interface.clone(), cfg.hotspot_interface.clone());
+    match cfg.backend {
+        BackendKind::Nmcli => Arc::new(Nmcli::new(ifaces)),
+        BackendKind::NmDbus => Arc::new(NmDbus::new(ifaces)),
+        BackendKind::Iwd => Arc::new(Iwd::new(ifaces)),
+        BackendKind::WpaSupplicant => Arc::new(WpaSupplicant::new(ifaces)),
+    }
+}
+
+/// 内存中的假后端，供菜单逻辑测试：状态可预设，每次写操作都记在 calls 里
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+
+    pub struct FakeBackend {
+        pub state: Mutex<State>,
+        ifaces: Interfaces,
+    }
+
+    pub struct State {
+        pub aps: Vec<AccessPoint>,
+        pub saved: Vec<String>,
+        pub current: Option<String>,
+        pub radio: RadioState,
+        /// 依次作为 connect_* 的结果，用完后一律成功
+        pub results: Vec<ConnectResult>,
+        pub calls: Vec<String>,
+    }
+
+    impl FakeBackend {
+        pub fn new(aps: Vec<AccessPoint>, saved: &[&str]) -> Arc<Self> {
+            Arc::new(Self {
+                state: Mutex::new(State {
+                    aps,
+                    saved: saved.iter().map(|s| s.to_string()).collect(),
+                    current: None,
+                    radio: RadioState::Enabled,
+                    results: vec![],
+                    calls: vec![],
+                }),
+                ifaces: Interfaces::new(None, None),
+            })
+        }
+
+        pub fn calls(&self) -> Vec<String> {
+            self.state.lock().unwrap().calls.clone()
+        }
+
+        /// 记录一次调用，取出预设的连接结果；成功时视为已连接并保存
+        fn connect(&self, call: String, ssid: &str) -> ConnectResult {
+            let mut s = self.state.lock().unwrap();
+            s.calls.push(call);
+            let result = if s.results.is_empty() {
+                ConnectResult::Success {
+                    ip: "10.0.0.2/24".into(),
+                }
+            } else {
+                s.results.remove(0)
+            };
+            if matches!(result, ConnectResult::Success { .. }) {
+                s.current = Some(ssid.to_string());
+                if !s.saved.iter().any(|n| n == ssid) {
+                    s.saved.push(ssid.to_string());
+                }
+            }
+            result
+        }
+    }
+
+    #[async_trait]
+    impl NetworkBackend for FakeBackend {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        async fn rescan(&self) {}
+
+        async fn list_access_points(&self) -> Result<Vec<AccessPoint>> {
+            Ok(self.state.lock().unwrap().aps.clone())
+        }
+
+        async fn current_ssid(&self) -> Option<String> {
+            self.state.lock().unwrap().current.clone()
+        }
+
+        async fn saved_connections(&self) -> Result<Vec<String>> {
+            Ok(self.state.lock().unwrap().saved.clone())
+        }
+
+        async fn saved_hidden(&self) -> Result<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn saved_password(&self, _name: &str) -> Option<String> {
+            None
+        }
+
+        async fn get_ip(&self) -> Option<String> {
+            None
+        }
+
+        async fn get_details(&self, _ssid: &str, _ping_host: &str) -> Result<ConnectionDetails> {
+            Err(anyhow!("fake"))
+        }
+
+        fn interfaces(&self) -> &Interfaces {
+            &self.ifaces
+        }
+
+        async fn devices(&self) -> Result<Vec<WifiDevice>> {
+            Ok(vec![])
+        }
+
+        async fn connect_saved(
+            &self,
+            name: &str,
+            bssid: Option<&str>,
+            _timeout: u64,
+        ) -> ConnectResult {
+            let call = format!("connect_saved {name} {}", bssid.unwrap_or("any"));
+            self.connect(call, name)
+        }
+
+        async fn connect_new(&self, req: &ConnectRequest, _timeout: u64) -> ConnectResult {
+            let call = format!(
+                "connect_new {} {} hidden={}",
+                req.ssid,
+                req.password.as_deref().unwrap_or("-"),
+                req.hidden
+            );
+            self.connect(call, &req.ssid)
+        }
+
+        async fn disconnect(&self, name: &str) -> Result<()> {
+            let mut s = self.state.lock().unwrap();
+            s.calls.push(format!("disconnect {name}"));
+            s.current = None;
+            Ok(())
+        }
+
+        async fn forget(&self, name: &str) -> Result<()> {
+            let mut s = self.state.lock().unwrap();
+            s.calls.push(format!("forget {name}"));
+            s.saved.retain(|n| n != name);
+            Ok(())
+        }
+
+        async fn radio_state(&self) -> RadioState {
+            self.state.lock().unwrap().radio.clone()
+        }
+
+        async fn set_radio(&self, enable: bool) -> Result<()> {
+            let mut s = self.state.lock().unwrap();
+            s.calls.push(format!("set_radio {enable}"));
+            s.radio = if enable {
+                RadioState::Enabled
+            } else {
+                RadioState::Disabled
+            };
+            Ok(())
+        }
+
+        async fn hotspot_active(&self) -> Option<String> {
+            None
+        }
+
+        async fn hotspot_profile(&self) -> Option<String> {
+            None
+        }
+
+        async fn hotspot_up(&self, name: &str) -> Result<()> {
+            self.state
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("hotspot_up {name}"));
+            Ok(())
+        }
+
+        async fn hotspot_down(&self, name: &str) -> Result<()> {
+            self.state
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("hotspot_down {name}"));
+            Ok(())
+        }
+
+        async fn create_hotspot(&self, ssid: &str, _password: &str) -> Result<()> {
+            self.state
+                .lock()
+                .unwrap()
+                .calls
+                .push(format!("create_hotspot {ssid}"));
+            Ok(())
+        }
+    }
+}
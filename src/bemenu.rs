@@ -0,0 +1,56 @@
+// src/bemenu.rs — bemenu 前端（X11 / Wayland 通用）
+
+use crate::config::Config;
+use crate::menu::{self, Menu, SelectOpts, Selection};
+use async_trait::async_trait;
+
+pub struct Bemenu {
+    cfg: Config,
+}
+
+impl Bemenu {
+    pub fn new(cfg: Config) -> Self {
+        Self { cfg }
+    }
+
+    async fn dmenu(&self, items: &[String], prompt: &str, extra: Vec<String>) -> Option<String> {
+        let mut args: Vec<String> = vec![
+            "-p".into(),
+            prompt.into(),
+            "--fn".into(),
+            self.cfg.font.clone(),
+        ];
+        // bemenu 只能放在顶部、底部或居中
+        match self.cfg.position {
+            0 => args.push("--center".into()),
+            5..=7 => args.push("--bottom".into()),
+            _ => {}
+        }
+        args.extend(extra);
+        menu::run("bemenu", &args, items).await
+    }
+}
+
+#[async_trait]
+impl Menu for Bemenu {
+    /// 不支持消息区、高亮行和禁止自由输入
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut extra: Vec<String> = vec![];
+        if let Some(n) = opts.lines.filter(|_| !items.is_empty()) {
+            extra.extend(["--list".into(), n.to_string()]);
+        }
+        let prompt = menu::prompt_with(prompt, opts.message);
+        let text = self.dmenu(items, &prompt, extra).await?;
+        Some(menu::match_row(items, text))
+    }
+
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let extra = vec!["--password".into(), "indicator".into()];
+        self.dmenu(&[], prompt, extra).await
+    }
+}
@@ -0,0 +1,280 @@
+// src/commands.rs — 非交互子命令：connect / disconnect / forget / radio / qr
+//
+// 供快捷键和部署脚本调用：结果打印到 stdout / stderr，以退出码区分失败原因。
+
+use crate::backend::Backend;
+use crate::cache;
+use crate::config::Config;
+use crate::i18n::t;
+use crate::qr;
+use crate::types::{AccessPoint, ConnectRequest, ConnectResult, RadioState, Security};
+use clap::ValueEnum;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+/// 子命令的退出码；2 留给 clap 的参数错误
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exit {
+    Ok = 0,
+    /// 其它失败
+    Failed = 1,
+    /// 附近没有该网络 / 没有该配置或密码 / 未连接
+    NotFound = 3,
+    /// 密码错误或需要密码但未提供
+    WrongPassword = 4,
+    Timeout = 5,
+}
+
+/// radio 子命令的动作
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum RadioAction {
+    On,
+    Off,
+    Toggle,
+}
+
+/// connect 的参数
+pub struct ConnectArgs {
+    pub ssid: String,
+    pub password_stdin: bool,
+    pub hidden: bool,
+    pub bssid: Option<String>,
+    pub timeout: Option<u64>,
+}
+
+/// qr 的参数
+pub struct QrArgs {
+    pub ssid: Option<String>,
+    pub format: qr::Format,
+    pub output: Option<PathBuf>,
+    pub size: u32,
+}
+
+/// 连接网络：不带新密码时优先唤起已保存的配置，否则按扫描结果新建连接
+pub async fn connect(args: ConnectArgs, cfg: &Config, net: &Backend) -> Exit {
+    let password = if args.password_stdin {
+        match read_password() {
+            Some(p) => Some(p),
+            None => return fail(Exit::WrongPassword, t!(NoPassword)),
+        }
+    } else {
+        None
+    };
+    let timeout = args.timeout.unwrap_or(cfg.connect_timeout);
+    let ssid = args.ssid;
+    let bssid = match args.bssid {
+        Some(_) if !net.supports_bssid() => {
+            eprintln!("{}", t!(BssidIgnored, backend = net.name()));
+            None
+        }
+        b => b,
+    };
+
+    let saved = net.saved_connections().await.unwrap_or_default();
+    if password.is_none() && saved.contains(&ssid) {
+        let result = net.connect_saved(&ssid, bssid.as_deref(), timeout).await;
+        return finish(result, &ssid, cfg, net).await;
+    }
+
+    // 隐藏网络扫不到，直接交给后端探测
+    let security = if args.hidden {
+        None
+    } else {
+        let find = |aps: Vec<AccessPoint>| {
+            aps.into_iter()
+                .find(|ap| ap.ssid == ssid)
+                .map(|ap| ap.security)
+        };
+        // 缓存里没有时再扫一次，可能只是缓存旧了
+        match find(crate::fetch_aps(cfg, net, false).await) {
+            Some(sec) => Some(sec),
+            None => match find(crate::fetch_aps(cfg, net, true).await) {
+                Some(sec) => Some(sec),
+                None => return fail(Exit::NotFound, &t!(NotNearby, ssid = ssid)),
+            },
+        }
+    };
+    if security.as_ref().is_some_and(|s| s.is_enterprise()) {
+        return fail(Exit::Failed, &t!(EnterpriseNeedsMenu, ssid = ssid));
+    }
+    if password.is_none() && security.as_ref().is_some_and(|s| s.needs_password()) {
+        return fail(Exit::WrongPassword, t!(NoPassword));
+    }
+
+    let req = ConnectRequest {
+        ssid: ssid.clone(),
+        password,
+        bssid,
+        hidden: args.hidden,
+        enterprise: None,
+    };
+    let result = net.connect_new(&req, timeout).await;
+    finish(result, &ssid, cfg, net).await
+}
+
+/// 连接结果对应的退出码；已保存和新建的连接共用
+async fn finish(result: ConnectResult, ssid: &str, cfg: &Config, net: &Backend) -> Exit {
+    match result {
+        ConnectResult::Success { ip } => connected(ssid, &ip, cfg, net).await,
+        ConnectResult::WrongPassword => fail(Exit::WrongPassword, t!(WrongPassword)),
+        ConnectResult::Timeout => fail(Exit::Timeout, &t!(TimeoutBody, ssid = ssid)),
+        ConnectResult::NotFound => fail(Exit::NotFound, &t!(NotNearby, ssid = ssid)),
+        ConnectResult::EapFailed(msg) | ConnectResult::Failed(msg) => {
+            fail(Exit::Failed, &format!("{}: {msg}", t!(ConnectFailed)))
+        }
+    }
+}
+
+/// 断开指定配置，不指定时断开当前连接
+pub async fn disconnect(name: Option<String>, net: &Backend) -> Exit {
+    let name = match name {
+        Some(n) => n,
+        None => match net.current_ssid().await {
+            Some(s) => s,
+            None => return fail(Exit::NotFound, t!(NotConnected)),
+        },
+    };
+    match net.disconnect(&name).await {
+        Ok(_) => {
+            println!("{}: {name}", t!(Disconnected));
+            Exit::Ok
+        }
+        Err(e) => fail(Exit::Failed, &format!("{}: {e}", t!(DisconnectFailed))),
+    }
+}
+
+/// 删除已保存的配置
+pub async fn forget(name: String, net: &Backend) -> Exit {
+    let saved = net.saved_connections().await.unwrap_or_default();
+    if !saved.contains(&name) {
+        return fail(Exit::NotFound, &t!(SavedNotFound, name = name));
+    }
+    match net.forget(&name).await {
+        Ok(_) => {
+            println!("{}: {}", t!(Forgotten), t!(ForgottenBody, name = name));
+            Exit::Ok
+        }
+        Err(e) => fail(Exit::Failed, &format!("{}: {e}", t!(ForgetFailed))),
+    }
+}
+
+/// 开关无线电；不带动作时只打印当前状态
+pub async fn radio(action: Option<RadioAction>, net: &Backend) -> Exit {
+    let enabled = net.radio_state().await == RadioState::Enabled;
+    let enable = match action {
+        None => {
+            println!("{}", if enabled { t!(RadioOn) } else { t!(RadioOff) });
+            return Exit::Ok;
+        }
+        Some(RadioAction::On) => true,
+        Some(RadioAction::Off) => false,
+        Some(RadioAction::Toggle) => !enabled,
+    };
+    match net.set_radio(enable).await {
+        Ok(_) => {
+            println!("{}", if enable { t!(RadioOn) } else { t!(RadioOff) });
+            Exit::Ok
+        }
+        Err(e) => fail(Exit::Failed, &e.to_string()),
+    }
+}
+
+/// 输出已保存网络的分享二维码，不指定时用当前连接
+pub async fn qr(args: QrArgs, net: &Backend) -> Exit {
+    let ssid = match args.ssid {
+        Some(s) => s,
+        None => match net.current_ssid().await {
+            Some(s) => s,
+            None => return fail(Exit::NotFound, t!(NotConnected)),
+        },
+    };
+    // 加密方式只能从扫描结果得知，过期的缓存也够用；不在附近时按有无密码推断
+    let cache_path = Config::cache_path(net.interfaces().station().as_deref());
+    let cached = cache::read(&cache_path, u64::MAX)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|ap| ap.ssid == ssid)
+        .map(|ap| ap.security);
+
+    let saved = net.saved_connections().await.unwrap_or_default();
+    let open = cached.as_ref().is_some_and(|s| !s.needs_password());
+    if !saved.contains(&ssid) && !open {
+        return fail(Exit::NotFound, &t!(NotSaved, ssid = ssid));
+    }
+    let pass = net.saved_password(&ssid).await.unwrap_or_default();
+    let security = cached.unwrap_or_else(|| {
+        if pass.is_empty() {
+            Security::default()
+        } else {
+            Security::wpa2_psk()
+        }
+    });
+    if pass.is_empty() && security.needs_password() {
+        return fail(Exit::NotFound, &t!(NoSavedPassword, ssid = ssid));
+    }
+
+    let data = match qr::export(&ssid, &pass, &security, args.format, args.size) {
+        Ok(d) => d,
+        Err(e) => return fail(Exit::Failed, &format!("{}: {e}", t!(QrFailed))),
+    };
+    let written = match &args.output {
+        Some(path) => std::fs::write(path, &data),
+        None => std::io::stdout().lock().write_all(&data),
+    };
+    match written {
+        Ok(_) => Exit::Ok,
+        Err(e) => fail(Exit::Failed, &format!("{}: {e}", t!(QrSaveFailed))),
+    }
+}
+
+async fn connected(ssid: &str, ip: &str, cfg: &Config, net: &Backend) -> Exit {
+    println!("{} {ssid}  IP: {ip}", t!(Connected));
+    crate::try_auto_vpn(ssid, cfg, net).await;
+    Exit::Ok
+}
+
+fn fail(code: Exit, msg: &str) -> Exit {
+    eprintln!("{msg}");
+    code
+}
+
+/// 从 stdin 读第一行作为密码，只去掉行尾换行
+fn read_password() -> Option<String> {
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line).ok()?;
+    let pass = line.trim_end_matches(['\n', '\r']);
+    (!pass.is_empty()).then(|| pass.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::fake::FakeBackend;
+
+    fn args(ssid: &str, hidden: bool) -> ConnectArgs {
+        ConnectArgs {
+            ssid: ssid.into(),
+            password_stdin: false,
+            hidden,
+            bssid: None,
+            timeout: Some(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn backend_not_found_exits_with_3() {
+        let fake = FakeBackend::new(vec![], &["Home"]);
+        fake.state.lock().unwrap().results = vec![ConnectResult::NotFound, ConnectResult::NotFound];
+        let net: Backend = fake.clone();
+        let cfg = Config::default();
+        assert_eq!(
+            connect(args("Home", false), &cfg, &net).await,
+            Exit::NotFound
+        );
+        assert_eq!(connect(args("Lab", true), &cfg, &net).await, Exit::NotFound);
+        assert_eq!(
+            fake.calls(),
+            ["connect_saved Home any", "connect_new Lab - hidden=true"]
+        );
+    }
+}
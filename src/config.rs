@@ -1,19 +1,49 @@
 // src/config.rs — 配置加载，支持文件覆盖
 
-use anyhow::Result;
+use crate::i18n::{t, Lang};
+use crate::types::{signal_level, NetworkAction, Security};
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
-    /// rofi 字体
+    /// 网络后端
+    pub backend: BackendKind,
+    /// 默认使用的无线网卡（如 "wlan0"），不填由后端自选第一块
+    pub interface: Option<String>,
+    /// 热点使用的网卡，不填则与 interface 相同
+    pub hotspot_interface: Option<String>,
+    /// 菜单前端
+    pub menu: MenuKind,
+    /// 界面语言（en / zh_CN），不填时跟随 LC_ALL / LC_MESSAGES / LANG
+    pub language: Option<Lang>,
+    /// 菜单字体（Pango 格式，如 "DejaVu Sans Mono 8"）
     pub font: String,
-    /// rofi 窗口位置 (0–8, 同 rofi -location)
+    /// rofi 主题（.rasi 文件路径或主题名）
+    pub theme: Option<String>,
+    /// 追加的 rofi 主题片段（-theme-str）
+    pub theme_str: Option<String>,
+    /// 追加给 rofi 的其它参数
+    pub extra_args: Vec<String>,
+    /// 按菜单覆盖字体、主题与参数（仅 rofi）
+    pub menus: MenuStyles,
+    /// 菜单窗口位置 (0–8, 同 rofi -location)
     pub position: u8,
     pub x_offset: i32,
     pub y_offset: i32,
     /// 菜单最大显示行数
     pub max_lines: usize,
+    /// 主菜单中每个网络一行的模板，占位符见 config.toml.example
+    pub row_format: String,
+    /// 行模板使用的符号
+    pub glyphs: Glyphs,
+    /// 用 Pango 标记给主菜单着色（仅 rofi 及其 script 模式）
+    pub markup: bool,
+    /// 着色使用的颜色
+    pub colors: Colors,
     /// nmcli 连接超时（秒）
     pub connect_timeout: u64,
     /// 密码错误最大重试次数
@@ -26,26 +56,271 @@ pub struct Config {
     pub ping_count: u8,
     /// VPN 联动: [("VPN profile 名", "触发 SSID"), ...]
     pub auto_vpn: Vec<(String, String)>,
+    /// 主菜单中作用于高亮网络的快捷键
+    pub keybindings: Keybindings,
 }
 
+/// 与早期固定布局一致的默认行模板
+pub const DEFAULT_ROW_FORMAT: &str =
+    "{in_use}{lock}{ssid:<20}  {bars}  {signal:>3}%  {security}  {extra}";
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            backend: BackendKind::Nmcli,
+            interface: None,
+            hotspot_interface: None,
+            menu: MenuKind::Auto,
+            language: None,
             font: "DejaVu Sans Mono 8".into(),
+            theme: None,
+            theme_str: None,
+            extra_args: vec![],
+            menus: MenuStyles::default(),
             position: 0,
             x_offset: 0,
             y_offset: 0,
             max_lines: 8,
+            row_format: DEFAULT_ROW_FORMAT.into(),
+            glyphs: Glyphs::default(),
+            markup: true,
+            colors: Colors::default(),
             connect_timeout: 15,
             max_retry: 3,
             cache_ttl: 30,
             ping_host: "1.1.1.1".into(),
             ping_count: 2,
             auto_vpn: vec![],
+            keybindings: Keybindings::default(),
+        }
+    }
+}
+
+/// 单个菜单的 rofi 外观覆盖；未填的项沿用全局设置，theme_str 与 extra_args 追加在全局设置之后
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MenuStyle {
+    pub font: Option<String>,
+    pub theme: Option<String>,
+    pub theme_str: Option<String>,
+    pub extra_args: Vec<String>,
+}
+
+/// 各菜单的外观覆盖
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MenuStyles {
+    /// 主菜单及其它列表子菜单
+    pub main: MenuStyle,
+    /// 密码输入
+    pub password: MenuStyle,
+    /// 是 / 否确认
+    pub confirm: MenuStyle,
+    /// 详情等只读信息
+    pub info: MenuStyle,
+    /// 二维码
+    pub qr: MenuStyle,
+}
+
+impl Default for MenuStyles {
+    fn default() -> Self {
+        Self {
+            main: MenuStyle::default(),
+            password: MenuStyle::default(),
+            confirm: MenuStyle::default(),
+            info: MenuStyle::default(),
+            // 二维码由块字符拼成，必须用等宽字体
+            qr: MenuStyle {
+                font: Some("Monospace 9".into()),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// 内置符号集
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlyphSet {
+    #[default]
+    Emoji,
+    /// 纯 ASCII，适合没有 emoji / 方块字符的字体
+    Ascii,
+}
+
+/// 行模板中的符号：先取符号集，再按单项覆盖
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Glyphs {
+    pub set: GlyphSet,
+    /// 当前连接 / 未连接
+    pub in_use: Option<String>,
+    pub not_in_use: Option<String>,
+    /// 加密 / 弱加密（WEP、TKIP）/ 开放
+    pub secure: Option<String>,
+    pub weak: Option<String>,
+    pub open: Option<String>,
+    /// 已保存配置
+    pub saved: Option<String>,
+    /// 信号格，由弱到强共 5 级
+    pub bars: Option<Vec<String>>,
+}
+
+impl Glyphs {
+    fn pick<'a>(
+        over: &'a Option<String>,
+        emoji: &'a str,
+        ascii: &'a str,
+        set: GlyphSet,
+    ) -> &'a str {
+        match (over, set) {
+            (Some(s), _) => s,
+            (None, GlyphSet::Emoji) => emoji,
+            (None, GlyphSet::Ascii) => ascii,
+        }
+    }
+
+    pub fn in_use(&self, in_use: bool) -> &str {
+        if in_use {
+            Self::pick(&self.in_use, "● ", "* ", self.set)
+        } else {
+            Self::pick(&self.not_in_use, "  ", "  ", self.set)
+        }
+    }
+
+    pub fn lock(&self, security: &Security) -> &str {
+        if security.is_open() {
+            Self::pick(&self.open, "   ", "  ", self.set)
+        } else if security.is_weak() {
+            Self::pick(&self.weak, "🔓 ", "! ", self.set)
+        } else {
+            Self::pick(&self.secure, "🔒 ", "# ", self.set)
+        }
+    }
+
+    pub fn saved(&self, saved: bool) -> &str {
+        if saved {
+            Self::pick(&self.saved, "💾", "S", self.set)
+        } else {
+            ""
+        }
+    }
+
+    pub fn bars(&self, signal: u8) -> String {
+        let level = signal_level(signal);
+        if let Some(b) = self.bars.as_ref().and_then(|b| b.get(level)) {
+            return b.clone();
+        }
+        let set = match self.set {
+            GlyphSet::Emoji => ["____", "▂___", "▂▄__", "▂▄▆_", "▂▄▆█"],
+            GlyphSet::Ascii => ["....", "#...", "##..", "###.", "####"],
+        };
+        set[level].to_string()
+    }
+}
+
+/// 主菜单着色（Pango 颜色：#rrggbb 或颜色名；留空不着色）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Colors {
+    /// 信号格与信号百分比，由弱到强共 5 级
+    pub signal: Vec<String>,
+    /// 开放 / WEP / TKIP 网络的加密标记与加密列
+    pub risky: String,
+    /// 已保存网络的 SSID（同时加粗）
+    pub saved: String,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        Self {
+            signal: ["#bf616a", "#d08770", "#ebcb8b", "#a3be8c", "#a3be8c"]
+                .map(String::from)
+                .to_vec(),
+            risky: "#bf616a".into(),
+            saved: "#88c0d0".into(),
+        }
+    }
+}
+
+impl Colors {
+    /// 信号强度对应的颜色；级数不足 5 个时取最后一个
+    pub fn signal(&self, signal: u8) -> &str {
+        let level = signal_level(signal);
+        self.signal
+            .get(level)
+            .or(self.signal.last())
+            .map_or("", String::as_str)
+    }
+}
+
+/// 快捷键（rofi 按键语法，如 "Alt+i"；留空禁用）。
+/// rofi 默认把 Alt+b / Alt+d / Alt+f 用于按词编辑，不要与之重复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keybindings {
+    /// 查看网络详情
+    pub details: String,
+    /// 删除已保存的配置
+    pub forget: String,
+    /// 显示分享二维码
+    pub qrcode: String,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            details: "Alt+i".into(),
+            forget: "Alt+x".into(),
+            qrcode: "Alt+q".into(),
         }
     }
 }
 
+impl Keybindings {
+    /// 已启用的按键与动作，顺序即 -kb-custom-N 的编号
+    pub fn bindings(&self) -> Vec<(String, NetworkAction)> {
+        [
+            (&self.details, NetworkAction::Details),
+            (&self.forget, NetworkAction::Forget),
+            (&self.qrcode, NetworkAction::QrCode),
+        ]
+        .into_iter()
+        .filter(|(key, _)| !key.trim().is_empty())
+        .map(|(key, action)| (key.trim().to_string(), action))
+        .collect()
+    }
+}
+
+/// 可选的网络后端
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackendKind {
+    /// nmcli 命令行（NetworkManager）
+    Nmcli,
+    /// 直接走 D-Bus 访问 NetworkManager，不启动子进程
+    NmDbus,
+    /// iwd（net.connman.iwd），适用于没有 NetworkManager 的机器
+    Iwd,
+    /// 直接访问 wpa_supplicant 控制接口，适用于只跑 wpa_supplicant 的最小系统
+    WpaSupplicant,
+}
+
+/// 可选的菜单前端
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MenuKind {
+    /// Wayland 下依次尝试 fuzzel / wofi / tofi / bemenu / rofi，X11 下依次尝试 rofi / dmenu / bemenu
+    Auto,
+    Rofi,
+    Wofi,
+    Fuzzel,
+    Bemenu,
+    Tofi,
+    /// suckless dmenu（X11）
+    Dmenu,
+}
+
 impl Config {
     /// 按优先级查找并加载配置文件
     pub fn load() -> Result<Self> {
@@ -60,9 +335,12 @@ impl Config {
         Ok(Config::default())
     }
 
-    /// 返回运行时缓存文件路径
-    pub fn cache_path() -> PathBuf {
-        runtime_dir().join("rofi-wifi-cache.json")
+    /// 返回运行时缓存文件路径；指定网卡时每块网卡各用一份
+    pub fn cache_path(ifname: Option<&str>) -> PathBuf {
+        match ifname {
+            Some(name) => runtime_dir().join(format!("rofi-wifi-cache-{name}.json")),
+            None => runtime_dir().join("rofi-wifi-cache.json"),
+        }
     }
 
     /// 返回守护进程 PID 文件路径
@@ -70,6 +348,16 @@ impl Config {
         runtime_dir().join("rofi-wifi-daemon.pid")
     }
 
+    /// 返回 rofi script 模式会话套接字路径；套接字上传的是明文密码，只放在当前用户私有的目录里
+    pub fn script_socket_path() -> Result<PathBuf> {
+        Ok(private_runtime_dir()?.join("rofi-wifi-script.sock"))
+    }
+
+    /// 返回“用看图软件打开”时临时写出的二维码图片路径
+    pub fn qr_image_path() -> PathBuf {
+        runtime_dir().join("rofi-wifi-qr.png")
+    }
+
     /// 返回扫描互斥锁文件路径（防止守护进程与手动刷新并发扫描）
     pub fn lock_path() -> PathBuf {
         runtime_dir().join("rofi-wifi-scan.lock")
@@ -82,6 +370,25 @@ fn runtime_dir() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("/tmp"))
 }
 
+/// XDG_RUNTIME_DIR 未设置时用 /tmp 下按 uid 区分的 0700 目录，
+/// 目录已存在时须属于当前用户、不是符号链接且其他人无权访问
+fn private_runtime_dir() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("XDG_RUNTIME_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let uid = unsafe { libc::getuid() };
+    let dir = std::env::temp_dir().join(format!("rofi-wifi-{uid}"));
+    match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => return Err(e.into()),
+        _ => {}
+    }
+    let meta = std::fs::symlink_metadata(&dir)?;
+    if !meta.is_dir() || meta.uid() != uid || meta.mode() & 0o077 != 0 {
+        return Err(anyhow!(t!(UnsafeRuntimeDir, path = dir.display())));
+    }
+    Ok(dir)
+}
+
 fn config_candidates() -> Vec<PathBuf> {
     let mut v = vec![];
     // 同目录下的 config.toml
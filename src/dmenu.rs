@@ -0,0 +1,59 @@
+// src/dmenu.rs — suckless dmenu 前端（X11）
+
+use crate::config::Config;
+use crate::menu::{self, Menu, SelectOpts, Selection};
+use async_trait::async_trait;
+
+pub struct Dmenu {
+    cfg: Config,
+}
+
+impl Dmenu {
+    pub fn new(cfg: Config) -> Self {
+        Self { cfg }
+    }
+
+    async fn dmenu(&self, items: &[String], prompt: &str, extra: Vec<String>) -> Option<String> {
+        // Xft 字体格式："DejaVu Sans Mono:size=8"
+        let font = match menu::split_font(&self.cfg.font) {
+            (family, Some(size)) => format!("{family}:size={size}"),
+            (family, None) => family.to_string(),
+        };
+        let mut args: Vec<String> = vec!["-p".into(), prompt.into(), "-fn".into(), font];
+        if matches!(self.cfg.position, 5..=7) {
+            args.push("-b".into());
+        }
+        args.extend(extra);
+        menu::run("dmenu", &args, items).await
+    }
+}
+
+#[async_trait]
+impl Menu for Dmenu {
+    /// 不支持消息区、高亮行和禁止自由输入
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut extra: Vec<String> = vec![];
+        if let Some(n) = opts.lines.filter(|_| !items.is_empty()) {
+            extra.extend(["-l".into(), n.to_string()]);
+        }
+        let prompt = menu::prompt_with(prompt, opts.message);
+        let text = self.dmenu(items, &prompt, extra).await?;
+        Some(menu::match_row(items, text))
+    }
+
+    /// 原版 dmenu 没有密码模式：把输入文字颜色设成与背景相同
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let extra = vec![
+            "-nb".into(),
+            "#222222".into(),
+            "-nf".into(),
+            "#222222".into(),
+        ];
+        self.dmenu(&[], prompt, extra).await
+    }
+}
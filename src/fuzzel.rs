@@ -0,0 +1,66 @@
+// src/fuzzel.rs — fuzzel 前端（Wayland）
+
+use crate::config::Config;
+use crate::menu::{self, Menu, SelectOpts, Selection};
+use async_trait::async_trait;
+
+pub struct Fuzzel {
+    cfg: Config,
+}
+
+impl Fuzzel {
+    pub fn new(cfg: Config) -> Self {
+        Self { cfg }
+    }
+
+    async fn dmenu(&self, items: &[String], prompt: &str, extra: Vec<String>) -> Option<String> {
+        // fuzzel 用 fontconfig 格式："DejaVu Sans Mono:size=8"
+        let font = match menu::split_font(&self.cfg.font) {
+            (family, Some(size)) => format!("{family}:size={size}"),
+            (family, None) => family.to_string(),
+        };
+        let mut args: Vec<String> = vec![
+            "--dmenu".into(),
+            format!("--prompt={prompt}"),
+            format!("--font={font}"),
+            format!("--anchor={}", menu::anchor(self.cfg.position)),
+            format!("--x-margin={}", self.cfg.x_offset.unsigned_abs()),
+            format!("--y-margin={}", self.cfg.y_offset.unsigned_abs()),
+        ];
+        args.extend(extra);
+        menu::run("fuzzel", &args, items).await
+    }
+}
+
+#[async_trait]
+impl Menu for Fuzzel {
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut extra: Vec<String> = vec![];
+        if let Some(n) = opts.lines {
+            extra.push(format!("--lines={n}"));
+        }
+        if let Some(w) = opts.width {
+            // fuzzel 的宽度单位就是字符
+            extra.push(format!("--width={w}"));
+        }
+        if let Some(a) = opts.active {
+            extra.push(format!("--select-index={a}"));
+        }
+        if opts.no_custom {
+            extra.push("--only-match".into());
+        }
+        let prompt = menu::prompt_with(prompt, opts.message);
+        let text = self.dmenu(items, &prompt, extra).await?;
+        Some(menu::match_row(items, text))
+    }
+
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let extra = vec!["--password".into(), "--lines=0".into()];
+        self.dmenu(&[], prompt, extra).await
+    }
+}
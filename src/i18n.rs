@@ -0,0 +1,347 @@
+// src/i18n.rs — 界面文字目录：en / zh_CN
+//
+// 每条文字是 Msg 的一个变体，键名在编译期检查；占位符写作 {name}，由 t! 宏替换。
+// 新增语言时给 catalog! 每行加一列，并在 Lang / detect 中登记。
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Write};
+use std::sync::OnceLock;
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Lang {
+    #[serde(rename = "en", alias = "en_US")]
+    En,
+    #[serde(rename = "zh_CN", alias = "zh")]
+    ZhCn,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// 设置界面语言，None 时按环境变量判断；须在第一次取文字之前调用
+pub fn init(lang: Option<Lang>) {
+    let _ = LANG.set(lang.unwrap_or_else(detect));
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(detect)
+}
+
+/// 与 gettext 相同的优先级：LC_ALL > LC_MESSAGES > LANG；都没有时用英文
+fn detect() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .find(|v| !v.is_empty())
+        .map_or(Lang::En, |v| {
+            if v.starts_with("zh") {
+                Lang::ZhCn
+            } else {
+                Lang::En
+            }
+        })
+}
+
+/// t!(Key) → &'static str；t!(Key, name = value, ..) → 替换占位符后的 String
+macro_rules! t {
+    ($key:ident) => {
+        $crate::i18n::Msg::$key.text()
+    };
+    ($key:ident, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::Msg::$key.format(&[$((stringify!($name), &$value as &dyn std::fmt::Display)),+])
+    };
+}
+pub(crate) use t;
+
+impl Msg {
+    /// 从左到右把 {name} 替换成对应的值；代入的值不再扫描，SSID 里的 "{ip}" 之类原样保留
+    pub fn format(self, args: &[(&str, &dyn Display)]) -> String {
+        let mut rest = self.text();
+        let mut out = String::with_capacity(rest.len());
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let arg = rest.find('}').and_then(|end| {
+                let name = &rest[1..end];
+                args.iter().find(|(n, _)| *n == name).map(|(_, v)| (end, v))
+            });
+            match arg {
+                Some((end, value)) => {
+                    let _ = write!(out, "{value}");
+                    rest = &rest[end + 1..];
+                }
+                // 不认识的占位符原样输出
+                None => {
+                    out.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+macro_rules! catalog {
+    ($($key:ident => $en:literal, $zh:literal;)*) => {
+        /// 所有界面文字的键
+        #[derive(Debug, Clone, Copy)]
+        pub enum Msg {
+            $($key,)*
+        }
+
+        impl Msg {
+            /// 当前语言下的文字
+            pub fn text(self) -> &'static str {
+                match lang() {
+                    Lang::En => match self { $(Msg::$key => $en,)* },
+                    Lang::ZhCn => match self { $(Msg::$key => $zh,)* },
+                }
+            }
+        }
+    };
+}
+
+catalog! {
+    // ── 命令行 ──────────────────────────────────────────────
+    CliAbout            => "Wi-Fi manager for rofi and other menu launchers", "rofi Wi-Fi 管理器";
+    CmdDaemon           => "Start the background daemon (refreshes the cache periodically)", "启动后台守护进程（定时刷新缓存）";
+    CmdDaemonStop       => "Stop the daemon", "停止守护进程";
+    CmdScan             => "Scan now and update the cache", "立即执行一次扫描并更新缓存";
+    CliHelp             => "Print help", "显示帮助";
+    CliVersion          => "Print version", "显示版本";
+    CmdTui              => "Full-screen terminal UI (TTY / SSH without a desktop)", "终端全屏界面（TTY / SSH 等无图形环境）";
+    CmdList             => "Print the network list (a table by default)", "输出网络列表（默认表格）";
+    ListJson            => "Print JSON (stable schema, see README)", "输出 JSON（格式稳定，见 README）";
+    ListCsv             => "Print CSV with a header row", "输出带表头的 CSV";
+    ListTable           => "Print an aligned table", "输出对齐的表格";
+    ListFresh           => "Scan now instead of using the cache", "立即扫描而不使用缓存";
+    ListSecurity        => "Only networks supporting these auth methods", "只列出支持这些认证方式的网络";
+    ListMinSignal       => "Only networks with at least this signal (%)", "只列出信号不低于此值（%）的网络";
+    ListBand            => "Only networks seen on these bands (GHz)", "只列出出现在这些频段（GHz）的网络";
+    CmdConnect          => "Connect to a network (saved profiles are activated directly)", "连接网络（已保存的配置直接唤起）";
+    CmdDisconnect       => "Disconnect", "断开连接";
+    CmdForget           => "Delete a saved profile", "删除已保存的配置";
+    CmdRadio            => "Turn the Wi-Fi radio on or off (prints the state without an argument)", "开关 Wi-Fi 无线电（不带参数时输出当前状态）";
+    ArgSsid             => "Network SSID", "网络 SSID";
+    ArgPasswordStdin    => "Read the password from the first line of stdin", "从 stdin 第一行读取密码";
+    ArgHidden           => "The network does not broadcast its SSID", "该网络不广播 SSID";
+    ArgBssid            => "Connect to this access point only", "只连接该接入点";
+    ArgTimeout          => "Connection timeout (default: connect_timeout)", "连接超时（默认 connect_timeout）";
+    ArgDisconnectName   => "Profile to disconnect (default: the current connection)", "要断开的配置（默认当前连接）";
+    ArgProfile          => "Saved profile name", "已保存的配置名称";
+    ArgRadio            => "Action", "动作";
+    CmdQr               => "Print or export the QR code of a saved network", "输出或导出已保存网络的分享二维码";
+    ArgQrSsid           => "Saved network (default: the current connection)", "已保存的网络（默认当前连接）";
+    ArgQrFormat         => "Output format", "输出格式";
+    ArgOutput           => "Write to this file instead of stdout", "写入该文件而不是 stdout";
+    ArgSize             => "Minimum image size in pixels (png / svg)", "图片的最小边长，单位像素（png / svg）";
+    CmdMenu             => "Open a submenu directly; Esc exits", "直接打开某个子菜单，Esc 退出";
+    CmdMenuManual       => "Connect manually (SSID or SSID,password)", "手动连接（SSID 或 SSID,密码）";
+    CmdMenuDisconnect   => "Disconnect after confirming", "确认后断开当前连接";
+    CmdMenuForget       => "Pick a saved profile to delete", "选择要删除的已保存配置";
+    CmdMenuHotspot      => "Turn the hotspot on or off", "开启或关闭热点";
+    CmdMenuDetails      => "Details of the current connection", "当前连接的详情";
+    CmdMenuQr           => "QR code of the current network", "当前网络的分享二维码";
+    CmdStatus           => "Print the Wi-Fi state for status bars", "给状态栏输出当前 Wi-Fi 状态";
+    ArgStatusFormat     => "Output format", "输出格式";
+    ArgWatch            => "Keep running and print a new line whenever the state changes", "持续运行，状态变化时输出新的一行";
+    ArgInterval         => "Polling interval in --watch mode", "--watch 模式的采样间隔";
+    StatusOff           => "Wi-Fi off", "Wi-Fi 已关闭";
+    StatusDisconnected  => "Disconnected", "未连接";
+    StatusTooltip       => "SSID   : {ssid}\nSignal : {signal}%\nIP     : {ip}", "SSID   : {ssid}\n信号   : {signal}%\nIP     : {ip}";
+    EnterpriseNeedsMenu => "{ssid} uses 802.1X, set it up from the menu first", "{ssid} 是 802.1X 网络，请先在菜单中完成配置";
+
+    // ── 守护进程 & 扫描 ─────────────────────────────────────
+    DaemonRunning       => "Daemon already running (PID: {pid})", "守护进程已在运行 (PID: {pid})";
+    DaemonStarted       => "Daemon started (PID: {pid}), refreshing the cache every {secs}s", "守护进程已启动 (PID: {pid})，每 {secs}s 刷新缓存";
+    DaemonNotRunning    => "Daemon is not running", "守护进程未运行";
+    DaemonStopped       => "Daemon stopped (PID: {pid})", "守护进程已停止 (PID: {pid})";
+    PidUnreadable       => "Cannot read PID", "无法读取 PID";
+    ScanDone            => "Scan finished, cache updated", "扫描完成，缓存已更新";
+    ScanFailed          => "Scan failed", "扫描失败";
+    LockFileFailed      => "Cannot create lock file", "无法创建锁文件";
+    Scanning            => "Scanning", "扫描中";
+    ScanningBody        => "Searching for nearby Wi-Fi…", "正在搜索附近 Wi-Fi…";
+
+    // ── 主菜单 ──────────────────────────────────────────────
+    MenuPrompt          => "📶 Wi-Fi: ", "📶 Wi-Fi: ";
+    MenuToggleOff       => "⚡ toggle off", "⚡ toggle off";
+    MenuToggleOn        => "⚡ toggle on", "⚡ toggle on";
+    MenuRefreshExpired  => "🔄 refresh  (cache expired)", "🔄 refresh  (缓存已过期)";
+    MenuRefreshLeft     => "🔄 refresh  (cache {secs}s left)", "🔄 refresh  (缓存剩余 {secs}s)";
+    MenuManual          => "✏️  manual", "✏️  manual";
+    MenuDisconnect      => "❌ disconnect", "❌ disconnect";
+    MenuForget          => "🗑️  forget", "🗑️  forget";
+    MenuHotspot         => "📡 hotspot", "📡 hotspot";
+    MenuInterface       => "🛜 interface  ({name})", "🛜 interface  ({name})";
+    MenuDetails         => "📊 details", "📊 details";
+    MenuQrCode          => "📷 qrcode", "📷 qrcode";
+    OpenNetworkWarning  => "⚠ The list contains open (unencrypted) or WEP networks, connect with care", "⚠ 列表中含有开放（无加密）或 WEP 网络，请谨慎连接";
+    HiddenSaved         => "(hidden · saved)", "(隐藏 · 已保存)";
+
+    // ── 通用 ────────────────────────────────────────────────
+    Yes                 => "Yes", "是";
+    No                  => "No", "否";
+    TitleError          => "Error", "错误";
+    TitleInfo           => "Info", "提示";
+    Unknown             => "unknown", "未知";
+    UnknownError        => "unknown error", "未知错误";
+    EscToClose          => "Press Esc to close", "按 Esc 关闭";
+    CloseRow            => "── Esc or Enter to close ──", "── 按 Esc 或 Enter 关闭 ──";
+    PasswordPrompt      => "🔒 Password: ", "🔒 密码: ";
+    PasswordPromptHint  => "🔒 Password ({hint}): ", "🔒 密码 ({hint}): ";
+    TitleMenu           => "Menu", "菜单";
+    CannotStart         => "Cannot start {name}", "无法启动 {name}";
+    NotConnected        => "Not connected to any Wi-Fi", "未连接任何 Wi-Fi";
+
+    // ── 无线电 / 手动 / 断开 / 忘记 / 网卡 ──────────────────
+    RadioOn             => "Enabled", "已开启";
+    RadioOff            => "Disabled", "已关闭";
+    ManualPrompt        => "Connect manually (SSID or SSID,password)", "手动连接 (SSID 或 SSID,密码)";
+    SsidEmpty           => "SSID cannot be empty", "SSID 不能为空";
+    ConfirmDisconnect   => "Disconnect {ssid}?", "断开 {ssid}？";
+    Disconnected        => "Disconnected", "已断开";
+    DisconnectFailed    => "Disconnect failed", "断开失败";
+    NoSavedNetworks     => "No saved Wi-Fi profiles", "没有已保存的 Wi-Fi 配置";
+    ForgetPrompt        => "🗑 Forget which network?", "🗑 忘记哪个网络？";
+    ConfirmForget       => "Permanently delete “{name}”?", "永久删除「{name}」？";
+    Forgotten           => "Deleted", "已删除";
+    ForgottenBody       => "Profile of {name}", "{name} 的连接配置";
+    ForgetFailed        => "Delete failed", "删除失败";
+    InterfacePrompt     => "🛜 Interface", "🛜 网卡";
+    TitleInterface      => "Interface", "网卡";
+    InterfaceSwitched   => "Switched to {name}", "已切换到 {name}";
+
+    // ── 详情 / 二维码 ───────────────────────────────────────
+    Fetching            => "Loading", "获取中";
+    FetchingDetails     => "Reading connection info…", "正在读取连接信息…";
+    FetchFailed         => "Failed to load", "获取失败";
+    PingTimeout         => "timed out", "超时";
+    DetailsBody         => "SSID     : {ssid}\nIP       : {ip}\nGateway  : {gateway}\nDNS      : {dns}\nSecurity : {security}\nSignal   : {signal}%\nLatency  : {ping}",
+                           "SSID     : {ssid}\nIP       : {ip}\n网关     : {gateway}\nDNS      : {dns}\n安全     : {security}\n信号     : {signal}%\n延迟     : {ping}";
+    ApInfoBody          => "SSID     : {ssid}\nSecurity : {security}\nSignal   : {signal}%  {bars}\nSaved    : {saved}",
+                           "SSID     : {ssid}\n安全     : {security}\n信号     : {signal}%  {bars}\n已保存   : {saved}";
+    NoSavedPassword     => "No saved password for {ssid}", "没有 {ssid} 的已保存密码";
+    NotSaved            => "{ssid} has no saved profile", "{ssid} 没有已保存的配置";
+    KeyDetails          => "details", "详情";
+    KeyForget           => "forget", "忘记";
+    KeyQrCode           => "share", "分享";
+    QrFailed            => "Failed to generate QR code", "生成失败";
+    QrSaveImage         => "💾 save as image", "💾 保存为图片";
+    QrOpenImage         => "🖼 open in image viewer", "🖼 用看图软件打开";
+    QrSaved             => "QR code saved", "二维码已保存";
+    QrSaveFailed        => "Failed to save QR code", "保存二维码失败";
+    QrOpenFailed        => "Failed to open QR code", "打开二维码失败";
+
+    // ── 连接 ────────────────────────────────────────────────
+    HiddenSsidPrompt    => "Hidden network SSID", "隐藏网络 SSID";
+    ConfirmOpen         => "⚠ {ssid} is an open network and traffic is not encrypted. Connect anyway?", "⚠ {ssid} 是开放网络，流量不加密，确认连接？";
+    BssAuto             => "🎯 auto  (let the system pick a BSSID)", "🎯 auto  (由系统选择 BSSID)";
+    Connecting          => "Connecting…", "连接中…";
+    ConnectingAttempt   => "{ssid} ({attempt}/{max})", "{ssid}（{attempt}/{max}）";
+    ConnectingEap       => "{ssid} (802.1X)", "{ssid}（802.1X）";
+    ConnectFailed       => "Connection failed", "连接失败";
+    WrongPassword       => "Wrong password", "密码错误";
+    RetryBody           => "Attempt {n} was wrong, please try again ({attempt}/{max})", "第 {n} 次输入有误，请重试 ({attempt}/{max})";
+    RetryHint           => "attempt {n}", "第 {n} 次";
+    Cancelled           => "Cancelled", "已取消";
+    GaveUp              => "Gave up connecting to {ssid}", "放弃连接 {ssid}";
+    RetriesExhausted    => "Retried {n} times, the password is still wrong", "已重试 {n} 次，密码始终错误";
+    TitleTimeout        => "Connection timed out", "连接超时";
+    TimeoutBody         => "{ssid} timed out, check the signal strength", "{ssid} 连接超时，请检查信号强度";
+    Connected           => "Connected ✓", "已连接 ✓";
+    Online              => "✓ Online", "✓ 网络畅通";
+    OnlinePing          => "✓ Online ({ms}ms)", "✓ 网络畅通 ({ms}ms)";
+    NoInternet          => "⚠ Connected but no internet access", "⚠ 已连接但无法访问互联网";
+    VpnStarting         => "Starting {vpn}…", "正在启动 {vpn}…";
+    VpnConnected        => "VPN connected", "VPN 已连接";
+    VpnFailed           => "VPN failed", "VPN 失败";
+
+    // ── 802.1X 向导 ─────────────────────────────────────────
+    EapMethodPrompt     => "🏢 {ssid} EAP method", "🏢 {ssid} EAP 方法";
+    Phase2Prompt        => "Inner authentication (phase 2)", "内层认证 (phase 2)";
+    IdentityPrompt      => "Identity", "身份 (identity)";
+    IdentityEmpty       => "Identity cannot be empty", "身份不能为空";
+    AnonIdentityPrompt  => "Anonymous identity (optional)", "匿名身份（可留空）";
+    CaCertPrompt        => "CA certificate path (empty = don't verify the server)", "CA 证书路径（留空不校验服务器）";
+    DomainMatchPrompt   => "Server domain match (optional)", "服务器域名匹配（可留空）";
+    ClientCertPrompt    => "Client certificate path", "客户端证书路径";
+    PrivateKeyPrompt    => "Private key path", "私钥路径";
+    PrivateKeyHint      => "private key", "私钥";
+    TlsNeedsCert        => "EAP-TLS needs a client certificate and a private key", "EAP-TLS 需要客户端证书和私钥";
+    FileUnavailable     => "File unavailable", "文件不可用";
+    AuthFailed          => "Authentication failed", "认证失败";
+    BadCredentials      => "Wrong identity or password", "身份或密码错误";
+    EapFailed           => "802.1X authentication failed", "802.1X 认证失败";
+    EapFailedBody       => "{msg}\nCheck the EAP method, CA certificate and domain match", "{msg}\n请检查 EAP 方法、CA 证书与域名匹配";
+    EapTimeout          => "{ssid} 802.1X authentication timed out", "{ssid} 802.1X 认证超时";
+
+    // ── 热点 ────────────────────────────────────────────────
+    ConfirmHotspotOff   => "Turn off the hotspot?", "关闭热点？";
+    HotspotOff          => "Hotspot off", "热点已关闭";
+    HotspotOn           => "Hotspot on", "热点已开启";
+    HotspotNamePrompt   => "📡 Hotspot name: ", "📡 热点名称: ";
+    HotspotPassPrompt   => "Hotspot password (at least 8 characters)", "热点密码（至少8位）";
+    PasswordTooShort    => "The password needs at least 8 characters", "密码至少需要 8 位";
+    HotspotFailed       => "Hotspot failed", "热点失败";
+    HotspotCreateFailed => "Failed to create hotspot", "创建热点失败";
+
+    // ── script 模式 / 终端界面 ──────────────────────────────
+    BackRow             => "« Back", "« 返回";
+    ScriptInputVisible  => "⚠ Input is visible in script mode", "⚠ script 模式下输入内容可见";
+    UnsafeRuntimeDir    => "{path} is not a private directory of the current user", "{path} 不是当前用户的私有目录";
+    SessionUnreachable  => "Cannot reach the menu session {path}", "无法连接菜单会话 {path}";
+    TuiHintInput        => "Enter confirm  Esc back", "Enter 确认  Esc 返回";
+    TuiHintList         => "↑↓ select  type to filter  Enter confirm  Esc back", "↑↓ 选择  输入过滤  Enter 确认  Esc 返回";
+    TuiHintText         => "Esc / Enter close", "Esc / Enter 关闭";
+
+    // ── 后端错误 ────────────────────────────────────────────
+    VpnUnsupported      => "The {backend} backend does not support VPN: {name}", "{backend} 后端不支持 VPN: {name}";
+    BssidIgnored        => "The {backend} backend cannot connect to a specific BSSID, ignoring --bssid", "{backend} 后端不支持指定 BSSID 连接，已忽略 --bssid";
+    NoPassword          => "No password provided", "未提供密码";
+    NotNearby           => "{ssid} is not nearby", "附近没有 {ssid}";
+    BssidNotNearby      => "BSSID {bssid} is not nearby", "附近没有 BSSID {bssid}";
+    InvalidBssid        => "Invalid BSSID: {bssid}", "无效的 BSSID: {bssid}";
+    SavedNotFound       => "Saved network “{name}” not found", "找不到已保存的网络「{name}」";
+    ProfileNotFound     => "Connection profile “{name}” not found", "找不到连接配置「{name}」";
+    NotActive           => "“{name}” is not active", "「{name}」未激活";
+    NoIpv4              => "The device has no IPv4 configuration", "设备没有 IPv4 配置";
+    NoWifiDevice        => "No Wi-Fi device found", "未找到 Wi-Fi 设备";
+    NoWifiDeviceNamed   => "Wi-Fi device {name} not found", "未找到 Wi-Fi 设备 {name}";
+    EapRejected         => "802.1X authentication rejected (reason {code})", "802.1X 认证未通过 (reason {code})";
+    NmActivationFailed  => "NetworkManager activation failed (reason {code})", "NetworkManager 激活失败 (reason {code})";
+    IwdNoDevice         => "No Wi-Fi device managed by iwd", "未找到 iwd 管理的 Wi-Fi 设备";
+    IwdNoDeviceNamed    => "iwd does not manage Wi-Fi device {name}", "iwd 未管理 Wi-Fi 设备 {name}";
+    IwdAgentFailed      => "Cannot register the iwd agent: {error}", "无法注册 iwd Agent: {error}";
+    WriteNeedsRoot      => "Failed to write {path} (root required): {error}", "写入 {path} 失败（需要 root 权限）: {error}";
+    CannotConnect       => "Cannot connect to {path}: {error}", "无法连接 {path}: {error}";
+    CannotRead          => "Cannot read {path}: {error}", "无法读取 {path}: {error}";
+    WpaNoResponse       => "wpa_supplicant did not respond: {cmd}", "wpa_supplicant 无响应: {cmd}";
+    WpaNoInterface      => "wpa_supplicant does not manage interface {name}", "wpa_supplicant 未管理接口 {name}";
+    WpaNoCtrl           => "wpa_supplicant has no usable control interface", "wpa_supplicant 没有可用的控制接口";
+    WpaNoRadio          => "The wpa_supplicant backend cannot toggle the radio", "wpa_supplicant 后端不支持切换无线电";
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn values_are_not_substituted_again() {
+        let s = t!(
+            ConnectingAttempt,
+            ssid = "{attempt} {max}",
+            attempt = 2,
+            max = 3
+        );
+        assert!(s.contains("{attempt} {max}"), "{s}");
+        assert!(s.contains('2') && s.contains('3'), "{s}");
+        assert!(!s.contains("{ssid}"), "{s}");
+    }
+
+    #[test]
+    fn unknown_placeholders_are_kept() {
+        let s = t!(ConnectingAttempt, attempt = 1, max = "}{");
+        assert!(s.contains("{ssid}"), "{s}");
+        assert!(s.contains("}{"), "{s}");
+    }
+}
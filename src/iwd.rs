@@ -0,0 +1,881 @@
+// src/iwd.rs — 通过 net.connman.iwd D-Bus API 操作 iwd（无 NetworkManager 的机器）
+
+use crate::backend::{Interfaces, NetworkBackend};
+use crate::i18n::t;
+use crate::netinfo;
+use crate::ping;
+use crate::types::{
+    dbm_to_percent, group_by_ssid, AccessPoint, Bss, ConnectRequest, ConnectResult,
+    ConnectionDetails, EapMethod, EnterpriseConfig, Phase2Auth, RadioState, ScanEntry, Security,
+    WifiDevice,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue};
+use zbus::{Connection, Proxy};
+
+const IWD: &str = "net.connman.iwd";
+const IWD_PATH: &str = "/net/connman/iwd";
+/// 本进程注册的密码代理对象路径
+const AGENT_PATH: &str = "/rofi_rwifi/agent";
+
+const IFACE_OBJECT_MANAGER: &str = "org.freedesktop.DBus.ObjectManager";
+const IFACE_AGENT_MANAGER: &str = "net.connman.iwd.AgentManager";
+const IFACE_ADAPTER: &str = "net.connman.iwd.Adapter";
+const IFACE_DEVICE: &str = "net.connman.iwd.Device";
+const IFACE_STATION: &str = "net.connman.iwd.Station";
+const IFACE_NETWORK: &str = "net.connman.iwd.Network";
+const IFACE_KNOWN: &str = "net.connman.iwd.KnownNetwork";
+const IFACE_AP: &str = "net.connman.iwd.AccessPoint";
+const IFACE_BSS: &str = "net.connman.iwd.BasicServiceSet";
+
+/// GetManagedObjects 返回的 a{oa{sa{sv}}}
+type Objects = HashMap<OwnedObjectPath, HashMap<String, HashMap<String, OwnedValue>>>;
+
+/// 通过系统总线调用 iwd
+pub struct Iwd {
+    conn: OnceCell<Connection>,
+    ifaces: Interfaces,
+}
+
+/// 连接新网络时临时注册的 Agent，iwd 需要密码时回调它
+struct PassphraseAgent {
+    passphrase: Option<String>,
+}
+
+#[zbus::interface(name = "net.connman.iwd.Agent")]
+impl PassphraseAgent {
+    fn release(&self) {}
+
+    fn request_passphrase(&self, _network: OwnedObjectPath) -> zbus::fdo::Result<String> {
+        self.passphrase
+            .clone()
+            .ok_or_else(|| zbus::fdo::Error::Failed(t!(NoPassword).into()))
+    }
+
+    fn cancel(&self, _reason: String) {}
+}
+
+impl Iwd {
+    pub fn new(ifaces: Interfaces) -> Self {
+        Self {
+            conn: OnceCell::new(),
+            ifaces,
+        }
+    }
+
+    async fn conn(&self) -> Result<&Connection> {
+        let conn = self
+            .conn
+            .get_or_try_init(|| async { Connection::system().await })
+            .await?;
+        Ok(conn)
+    }
+
+    async fn proxy(&self, path: &str, iface: &'static str) -> Result<Proxy<'static>> {
+        let proxy = Proxy::new(self.conn().await?, IWD, path.to_string(), iface).await?;
+        Ok(proxy)
+    }
+
+    async fn objects(&self) -> Result<Objects> {
+        let om = self.proxy("/", IFACE_OBJECT_MANAGER).await?;
+        Ok(om.call("GetManagedObjects", &()).await?)
+    }
+
+    /// 所有 Wi-Fi 设备：(对象路径, 接口名)
+    async fn all_devices(&self) -> Result<Vec<(OwnedObjectPath, String)>> {
+        let mut devices: Vec<_> = self
+            .objects()
+            .await?
+            .into_iter()
+            .filter_map(|(path, ifaces)| {
+                let name = prop::<String>(ifaces.get(IFACE_DEVICE)?, "Name")?;
+                Some((path, name))
+            })
+            .collect();
+        // GetManagedObjects 无序，按接口名排序保证“第一个”稳定
+        devices.sort_by(|a, b| a.1.cmp(&b.1));
+        Ok(devices)
+    }
+
+    /// 按接口名找设备；None 取第一个
+    async fn device_named(&self, ifname: Option<String>) -> Result<(OwnedObjectPath, String)> {
+        let mut devices = self.all_devices().await?.into_iter();
+        match ifname {
+            Some(name) => devices
+                .find(|(_, n)| *n == name)
+                .ok_or_else(|| anyhow!(t!(IwdNoDeviceNamed, name = name))),
+            None => devices.next().ok_or_else(|| anyhow!(t!(IwdNoDevice))),
+        }
+    }
+
+    /// 当前选中的 Wi-Fi 设备：(对象路径, 接口名)
+    async fn device(&self) -> Result<(OwnedObjectPath, String)> {
+        self.device_named(self.ifaces.station()).await
+    }
+
+    /// 热点使用的 Wi-Fi 设备
+    async fn hotspot_device(&self) -> Result<(OwnedObjectPath, String)> {
+        self.device_named(self.ifaces.hotspot()).await
+    }
+
+    /// 按 SSID 查找当前设备扫描到的 Network 对象
+    /// 当前网卡扫描到的同名 Network 对象；没扫到为 None
+    async fn find_network(&self, ssid: &str) -> Result<Option<OwnedObjectPath>> {
+        let (device, _) = self.device().await?;
+        Ok(self
+            .objects()
+            .await?
+            .into_iter()
+            .find(|(_, ifaces)| {
+                ifaces.get(IFACE_NETWORK).is_some_and(|n| {
+                    prop::<String>(n, "Name").as_deref() == Some(ssid)
+                        && prop::<OwnedObjectPath>(n, "Device").as_ref() == Some(&device)
+                })
+            })
+            .map(|(p, _)| p))
+    }
+
+    async fn station(&self) -> Result<Proxy<'static>> {
+        let (device, _) = self.device().await?;
+        self.proxy(device.as_str(), IFACE_STATION).await
+    }
+
+    /// 所有 KnownNetwork：(路径, 名称)
+    async fn known_networks(&self) -> Result<Vec<(OwnedObjectPath, String)>> {
+        Ok(self
+            .objects()
+            .await?
+            .into_iter()
+            .filter_map(|(path, ifaces)| {
+                let name = prop::<String>(ifaces.get(IFACE_KNOWN)?, "Name")?;
+                Some((path, name))
+            })
+            .collect())
+    }
+
+    /// 当前连接的网络：(Network 路径, SSID, Type)
+    async fn connected_network(&self) -> Option<(OwnedObjectPath, String, String)> {
+        let (device, _) = self.device().await.ok()?;
+        let objects = self.objects().await.ok()?;
+        let station = objects.get(&device)?.get(IFACE_STATION)?;
+        let path: OwnedObjectPath = prop(station, "ConnectedNetwork")?;
+        let network = objects.get(&path)?.get(IFACE_NETWORK)?;
+        Some((
+            path.clone(),
+            prop(network, "Name")?,
+            prop(network, "Type").unwrap_or_default(),
+        ))
+    }
+
+    async fn adapter(&self) -> Result<Proxy<'static>> {
+        let (device, _) = self.device().await?;
+        let path: OwnedObjectPath = self
+            .proxy(device.as_str(), IFACE_DEVICE)
+            .await?
+            .get_property("Adapter")
+            .await?;
+        self.proxy(path.as_str(), IFACE_ADAPTER).await
+    }
+
+    /// 切换设备工作模式（station / ap）
+    async fn set_mode(&self, device: &OwnedObjectPath, mode: &str) -> Result<()> {
+        let dev = self.proxy(device.as_str(), IFACE_DEVICE).await?;
+        let current: String = dev.get_property("Mode").await?;
+        if current != mode {
+            dev.set_property("Mode", mode).await?;
+            // 等待 AccessPoint / Station 接口出现
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        Ok(())
+    }
+
+    /// 已保存网络的类型（open / psk / 8021x）
+    async fn known_type(&self, name: &str) -> Option<String> {
+        self.objects().await.ok()?.values().find_map(|ifaces| {
+            let known = ifaces.get(IFACE_KNOWN)?;
+            (prop::<String>(known, "Name")? == name).then(|| prop(known, "Type"))?
+        })
+    }
+
+    /// 把 Connect / ConnectHiddenNetwork 的返回翻译成 ConnectResult
+    async fn connect_result(
+        &self,
+        res: Result<zbus::Result<()>, tokio::time::error::Elapsed>,
+        enterprise: bool,
+        has_secret: bool,
+    ) -> ConnectResult {
+        match res {
+            Ok(Ok(())) => {
+                let ip = self.get_ip().await.unwrap_or_else(|| t!(Unknown).into());
+                ConnectResult::Success { ip }
+            }
+            Err(_) => {
+                // 超时后主动中止，避免 iwd 继续尝试
+                if let Ok((device, _)) = self.device().await {
+                    if let Ok(station) = self.proxy(device.as_str(), IFACE_STATION).await {
+                        let _ = station.call::<_, _, ()>("Disconnect", &()).await;
+                    }
+                }
+                ConnectResult::Timeout
+            }
+            Ok(Err(zbus::Error::MethodError(name, desc, _))) => match name.as_str() {
+                "net.connman.iwd.Failed" if enterprise => {
+                    ConnectResult::EapFailed(desc.unwrap_or_else(|| name.to_string()))
+                }
+                // 握手失败：有密码时基本就是密码错误
+                "net.connman.iwd.Failed" if has_secret => ConnectResult::WrongPassword,
+                "net.connman.iwd.Timeout" => ConnectResult::Timeout,
+                // 隐藏网络探测不到
+                "net.connman.iwd.NotFound" => ConnectResult::NotFound,
+                _ => ConnectResult::Failed(desc.unwrap_or_else(|| name.to_string())),
+            },
+            Ok(Err(e)) => ConnectResult::Failed(e.to_string()),
+        }
+    }
+
+    async fn register_agent(&self, passphrase: Option<&str>) -> Result<()> {
+        let conn = self.conn().await?;
+        let agent = PassphraseAgent {
+            passphrase: passphrase.map(str::to_string),
+        };
+        conn.object_server().at(AGENT_PATH, agent).await?;
+        let manager = self.proxy(IWD_PATH, IFACE_AGENT_MANAGER).await?;
+        manager
+            .call::<_, _, ()>("RegisterAgent", &(ObjectPath::try_from(AGENT_PATH)?,))
+            .await?;
+        Ok(())
+    }
+
+    async fn unregister_agent(&self) {
+        if let Ok(manager) = self.proxy(IWD_PATH, IFACE_AGENT_MANAGER).await {
+            if let Ok(path) = ObjectPath::try_from(AGENT_PATH) {
+                let _ = manager.call::<_, _, ()>("UnregisterAgent", &(path,)).await;
+            }
+        }
+        if let Ok(conn) = self.conn().await {
+            let _ = conn
+                .object_server()
+                .remove::<PassphraseAgent, _>(AGENT_PATH)
+                .await;
+        }
+    }
+}
+
+#[async_trait]
+impl NetworkBackend for Iwd {
+    fn name(&self) -> &'static str {
+        "iwd"
+    }
+
+    /// iwd 的 D-Bus 接口没有按 BSSID 连接的方法，只能由它自己选 AP
+    fn supports_bssid(&self) -> bool {
+        false
+    }
+
+    // ── 查询 ─────────────────────────────────────────────────
+
+    async fn rescan(&self) {
+        if let Ok((device, _)) = self.device().await {
+            if let Ok(station) = self.proxy(device.as_str(), IFACE_STATION).await {
+                let _ = station.call::<_, _, ()>("Scan", &()).await;
+            }
+        }
+    }
+
+    async fn list_access_points(&self) -> Result<Vec<AccessPoint>> {
+        let (device, _) = self.device().await?;
+        let station = self.proxy(device.as_str(), IFACE_STATION).await?;
+        // a(on)：Network 路径 + 信号强度（单位 0.01 dBm）
+        let ordered: Vec<(OwnedObjectPath, i16)> = station.call("GetOrderedNetworks", &()).await?;
+        let objects = self.objects().await?;
+        let connected_ap: Option<OwnedObjectPath> = objects
+            .get(&device)
+            .and_then(|o| o.get(IFACE_STATION))
+            .and_then(|s| prop(s, "ConnectedAccessPoint"));
+        let mut connected_ssid = None;
+
+        // iwd 不暴露逐 BSS 的频率与信号，只能按 ExtendedServiceSet 列出 BSSID
+        let mut entries = vec![];
+        for (path, strength) in &ordered {
+            let Some(network) = objects.get(path).and_then(|o| o.get(IFACE_NETWORK)) else {
+                continue;
+            };
+            let Some(ssid) = prop::<String>(network, "Name") else {
+                continue;
+            };
+            let kind: String = prop(network, "Type").unwrap_or_default();
+            let signal = dbm_to_percent(i32::from(*strength) / 100);
+            let connected = prop(network, "Connected").unwrap_or(false);
+            if connected {
+                connected_ssid = Some(ssid.clone());
+            }
+
+            let ess: Vec<OwnedObjectPath> = prop(network, "ExtendedServiceSet").unwrap_or_default();
+            let mut bss: Vec<(String, bool)> = ess
+                .iter()
+                .filter_map(|p| {
+                    let address = prop(objects.get(p)?.get(IFACE_BSS)?, "Address")?;
+                    let in_use = connected && bss_in_use(p, connected_ap.as_ref(), ess.len());
+                    Some((address, in_use))
+                })
+                .collect();
+            if bss.is_empty() {
+                bss.push((String::new(), connected));
+            }
+            for (bssid, in_use) in bss {
+                entries.push(ScanEntry {
+                    ssid: ssid.clone(),
+                    security: security_from_kind(&kind),
+                    bss: Bss::new(bssid, 0, signal, in_use),
+                });
+            }
+        }
+
+        // 隐藏网络只有 BSS 信息：a(sns)，地址 + 信号 + 类型
+        let hidden: Vec<(String, i16, String)> = station
+            .call("GetHiddenAccessPoints", &())
+            .await
+            .unwrap_or_default();
+        for (address, strength, kind) in hidden {
+            let signal = dbm_to_percent(i32::from(strength) / 100);
+            entries.push(ScanEntry {
+                ssid: String::new(),
+                security: security_from_kind(&kind),
+                bss: Bss::new(address, 0, signal, false),
+            });
+        }
+
+        // 分不出连的是哪个 BSS 时，至少把网络本身标为当前连接并置顶
+        let mut aps = group_by_ssid(entries);
+        if let Some(ssid) = connected_ssid {
+            for ap in aps.iter_mut().filter(|ap| ap.ssid == ssid) {
+                ap.in_use = true;
+            }
+            aps.sort_by_key(|ap| !ap.in_use);
+        }
+        Ok(aps)
+    }
+
+    async fn current_ssid(&self) -> Option<String> {
+        self.connected_network().await.map(|(_, ssid, _)| ssid)
+    }
+
+    /// GetOrderedNetworks 只读上次扫描的结果
+    async fn current_link(&self) -> Option<(String, u8)> {
+        let (path, ssid, _) = self.connected_network().await?;
+        let (device, _) = self.device().await.ok()?;
+        let station = self.proxy(device.as_str(), IFACE_STATION).await.ok()?;
+        let ordered: Vec<(OwnedObjectPath, i16)> =
+            station.call("GetOrderedNetworks", &()).await.ok()?;
+        let signal = ordered
+            .iter()
+            .find(|(p, _)| p == &path)
+            .map_or(0, |(_, s)| dbm_to_percent(i32::from(*s) / 100));
+        Some((ssid, signal))
+    }
+
+    /// iwd 的 KnownNetwork 即已保存的网络
+    async fn saved_connections(&self) -> Result<Vec<String>> {
+        Ok(self
+            .known_networks()
+            .await?
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect())
+    }
+
+    async fn saved_hidden(&self) -> Result<Vec<String>> {
+        Ok(self
+            .objects()
+            .await?
+            .values()
+            .filter_map(|ifaces| {
+                let known = ifaces.get(IFACE_KNOWN)?;
+                prop::<bool>(known, "Hidden")
+                    .unwrap_or(false)
+                    .then(|| prop::<String>(known, "Name"))?
+            })
+            .collect())
+    }
+
+    /// 读取 /var/lib/iwd/<SSID>.psk（通常需要 root 权限）
+    async fn saved_password(&self, name: &str) -> Option<String> {
+        let path = format!("/var/lib/iwd/{}.psk", storage_name(name));
+        std::fs::read_to_string(path)
+            .ok()?
+            .lines()
+            .find_map(|l| l.strip_prefix("Passphrase="))
+            .map(str::to_string)
+    }
+
+    async fn get_ip(&self) -> Option<String> {
+        // 稍等一下让 DHCP 完成
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let (_, ifname) = self.device().await.ok()?;
+        netinfo::ipv4_address(&ifname)
+    }
+
+    async fn get_details(&self, ssid: &str, ping_host: &str) -> Result<ConnectionDetails> {
+        let (device, ifname) = self.device().await?;
+        let ping_ms = ping::ping_once(ping_host).await;
+
+        let (security, signal) = match self.connected_network().await {
+            Some((path, _, kind)) => {
+                let station = self.proxy(device.as_str(), IFACE_STATION).await?;
+                let ordered: Vec<(OwnedObjectPath, i16)> =
+                    station.call("GetOrderedNetworks", &()).await?;
+                let signal = ordered
+                    .iter()
+                    .find(|(p, _)| p == &path)
+                    .map(|(_, s)| dbm_to_percent(i32::from(*s) / 100).to_string())
+                    .unwrap_or_else(|| "--".into());
+                (security_from_kind(&kind).to_string(), signal)
+            }
+            None => ("--".into(), "--".into()),
+        };
+
+        let dns = netinfo::dns_servers().join(", ");
+        Ok(ConnectionDetails {
+            ssid: ssid.to_string(),
+            ip: netinfo::ipv4_address(&ifname).unwrap_or_else(|| "N/A".into()),
+            gateway: netinfo::default_gateway(&ifname).unwrap_or_else(|| "N/A".into()),
+            dns: if dns.is_empty() { "N/A".into() } else { dns },
+            security,
+            signal,
+            ping_ms,
+        })
+    }
+
+    fn interfaces(&self) -> &Interfaces {
+        &self.ifaces
+    }
+
+    async fn devices(&self) -> Result<Vec<WifiDevice>> {
+        let objects = self.objects().await?;
+        let network_name = |path: Option<OwnedObjectPath>| {
+            let network = objects.get(&path?)?.get(IFACE_NETWORK)?;
+            prop::<String>(network, "Name")
+        };
+        Ok(self
+            .all_devices()
+            .await?
+            .into_iter()
+            .map(|(path, name)| {
+                let ifaces = objects.get(&path);
+                // AP 模式下没有 Station 接口，改报热点名称
+                let (state, connection) = match ifaces.and_then(|i| i.get(IFACE_STATION)) {
+                    Some(st) => (
+                        prop(st, "State").unwrap_or_default(),
+                        network_name(prop(st, "ConnectedNetwork")),
+                    ),
+                    None => (
+                        "ap".to_string(),
+                        ifaces
+                            .and_then(|i| i.get(IFACE_AP))
+                            .and_then(|ap| prop(ap, "Name")),
+                    ),
+                };
+                WifiDevice {
+                    name,
+                    state,
+                    connection,
+                }
+            })
+            .collect())
+    }
+
+    // ── 连接管理 ─────────────────────────────────────────────
+
+    async fn connect_saved(&self, name: &str, _bssid: Option<&str>, timeout: u64) -> ConnectResult {
+        // 已保存的隐藏网络不会出现在扫描结果里，改由 Station 按 SSID 探测
+        let network = match self.find_network(name).await {
+            Ok(n) => n,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        let hidden = network.is_none()
+            && self
+                .saved_hidden()
+                .await
+                .unwrap_or_default()
+                .iter()
+                .any(|n| n == name);
+        let proxy = match network {
+            Some(n) => self.proxy(n.as_str(), IFACE_NETWORK).await,
+            None if hidden => self.station().await,
+            None => return ConnectResult::NotFound,
+        };
+        let proxy = match proxy {
+            Ok(p) => p,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        let kind = self.known_type(name).await.unwrap_or_default();
+        let call = async {
+            if hidden {
+                proxy
+                    .call::<_, _, ()>("ConnectHiddenNetwork", &(name,))
+                    .await
+            } else {
+                proxy.call::<_, _, ()>("Connect", &()).await
+            }
+        };
+        let res = tokio::time::timeout(Duration::from_secs(timeout), call).await;
+        self.connect_result(res, kind == "8021x", kind == "psk")
+            .await
+    }
+
+    async fn connect_new(&self, req: &ConnectRequest, timeout: u64) -> ConnectResult {
+        // 隐藏网络没有 Network 对象，由 Station.ConnectHiddenNetwork 探测
+        let proxy = if req.hidden {
+            self.station().await
+        } else {
+            match self.find_network(&req.ssid).await {
+                Ok(Some(n)) => self.proxy(n.as_str(), IFACE_NETWORK).await,
+                Ok(None) => return ConnectResult::NotFound,
+                Err(e) => Err(e),
+            }
+        };
+        let proxy = match proxy {
+            Ok(p) => p,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+
+        // 802.1X 参数只能通过 /var/lib/iwd/<SSID>.8021x 预置
+        let provision = match &req.enterprise {
+            Some(eap) => {
+                let path = format!("/var/lib/iwd/{}.8021x", storage_name(&req.ssid));
+                if let Err(e) = std::fs::write(&path, provisioning_file(eap, req.hidden)) {
+                    return ConnectResult::Failed(t!(WriteNeedsRoot, path = path, error = e));
+                }
+                // 等 iwd 监测到文件变化并载入
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                Some(path)
+            }
+            None => None,
+        };
+
+        if let Err(e) = self.register_agent(req.password.as_deref()).await {
+            return ConnectResult::Failed(t!(IwdAgentFailed, error = e));
+        }
+        let call = async {
+            if req.hidden {
+                proxy
+                    .call::<_, _, ()>("ConnectHiddenNetwork", &(req.ssid.as_str(),))
+                    .await
+            } else {
+                proxy.call::<_, _, ()>("Connect", &()).await
+            }
+        };
+        let res = tokio::time::timeout(Duration::from_secs(timeout), call).await;
+        self.unregister_agent().await;
+
+        let result = self
+            .connect_result(res, req.enterprise.is_some(), req.password.is_some())
+            .await;
+
+        // 失败时删掉预置文件，避免 iwd 之后反复自动重连
+        if let (Some(path), false) = (&provision, matches!(result, ConnectResult::Success { .. })) {
+            let _ = std::fs::remove_file(path);
+        }
+        result
+    }
+
+    async fn disconnect(&self, _name: &str) -> Result<()> {
+        let (device, _) = self.device().await?;
+        let station = self.proxy(device.as_str(), IFACE_STATION).await?;
+        station
+            .call::<_, _, ()>("Disconnect", &())
+            .await
+            .map_err(|_| anyhow!(t!(DisconnectFailed)))
+    }
+
+    async fn forget(&self, name: &str) -> Result<()> {
+        let (path, _) = self
+            .known_networks()
+            .await?
+            .into_iter()
+            .find(|(_, n)| n == name)
+            .ok_or_else(|| anyhow!(t!(SavedNotFound, name = name)))?;
+        let known = self.proxy(path.as_str(), IFACE_KNOWN).await?;
+        known
+            .call::<_, _, ()>("Forget", &())
+            .await
+            .map_err(|_| anyhow!(t!(ForgetFailed)))
+    }
+
+    // ── 无线电 & 热点 ─────────────────────────────────────────
+
+    async fn radio_state(&self) -> RadioState {
+        let powered = match self.adapter().await {
+            Ok(a) => a.get_property::<bool>("Powered").await.unwrap_or(false),
+            Err(_) => false,
+        };
+        if powered {
+            RadioState::Enabled
+        } else {
+            RadioState::Disabled
+        }
+    }
+
+    async fn set_radio(&self, enable: bool) -> Result<()> {
+        self.adapter()
+            .await?
+            .set_property("Powered", enable)
+            .await?;
+        Ok(())
+    }
+
+    async fn hotspot_active(&self) -> Option<String> {
+        let objects = self.objects().await.ok()?;
+        objects.values().find_map(|ifaces| {
+            let ap = ifaces.get(IFACE_AP)?;
+            if prop::<bool>(ap, "Started")? {
+                prop::<String>(ap, "Name")
+            } else {
+                None
+            }
+        })
+    }
+
+    /// iwd 的 AP 配置（/var/lib/iwd/ap/*.ap）不经 D-Bus 暴露，只能尝试直接读目录
+    async fn hotspot_profile(&self) -> Option<String> {
+        std::fs::read_dir("/var/lib/iwd/ap")
+            .ok()?
+            .filter_map(|e| e.ok())
+            .find_map(|e| {
+                e.file_name()
+                    .to_str()?
+                    .strip_suffix(".ap")
+                    .map(str::to_string)
+            })
+    }
+
+    async fn hotspot_up(&self, name: &str) -> Result<()> {
+        let (device, _) = self.hotspot_device().await?;
+        self.set_mode(&device, "ap").await?;
+        let ap = self.proxy(device.as_str(), IFACE_AP).await?;
+        ap.call::<_, _, ()>("StartProfile", &(name,)).await?;
+        Ok(())
+    }
+
+    async fn hotspot_down(&self, _name: &str) -> Result<()> {
+        let (device, _) = self.hotspot_device().await?;
+        let ap = self.proxy(device.as_str(), IFACE_AP).await?;
+        let _ = ap.call::<_, _, ()>("Stop", &()).await;
+        self.set_mode(&device, "station").await
+    }
+
+    async fn create_hotspot(&self, ssid: &str, password: &str) -> Result<()> {
+        let (device, _) = self.hotspot_device().await?;
+        self.set_mode(&device, "ap").await?;
+        let ap = self.proxy(device.as_str(), IFACE_AP).await?;
+        ap.call::<_, _, ()>("Start", &(ssid, password))
+            .await
+            .map_err(|e| anyhow!("{}: {e}", t!(HotspotCreateFailed)))
+    }
+}
+
+// ── 辅助函数 ─────────────────────────────────────────────────
+
+fn prop<T>(map: &HashMap<String, OwnedValue>, key: &str) -> Option<T>
+where
+    T: TryFrom<OwnedValue>,
+{
+    map.get(key)
+        .and_then(|v| v.try_clone().ok())
+        .and_then(|v| T::try_from(v).ok())
+}
+
+/// iwd 的 Network.Type 映射为加密描述（iwd 不暴露 WPA 代际与加密套件，按 WPA2 记）
+fn security_from_kind(kind: &str) -> Security {
+    match kind {
+        "open" => Security::default(),
+        "wep" => Security::wep(),
+        "psk" => Security::wpa2_psk(),
+        "8021x" => Security::wpa2_eap(),
+        _ => Security::unknown(),
+    }
+}
+
+/// 已连接网络中的某个 BSS 是否正在使用：以 Station.ConnectedAccessPoint 为准；
+/// 较旧的 iwd 没有这个属性，只有单个 BSS 时才能确定
+fn bss_in_use(
+    bss: &OwnedObjectPath,
+    connected_ap: Option<&OwnedObjectPath>,
+    ess_len: usize,
+) -> bool {
+    match connected_ap {
+        Some(ap) => ap == bss,
+        None => ess_len == 1,
+    }
+}
+
+/// iwd 的 .8021x 预置文件内容，见 iwd.network(5)
+fn provisioning_file(eap: &EnterpriseConfig, hidden: bool) -> String {
+    let method = eap.eap.to_string();
+    let mut lines = vec!["[Security]".to_string(), format!("EAP-Method={method}")];
+    let mut push = |key: &str, value: Option<&str>| {
+        if let Some(v) = value {
+            lines.push(format!("EAP-{key}={v}"));
+        }
+    };
+
+    match eap.eap {
+        EapMethod::Tls => {
+            push("Identity", Some(&eap.identity));
+            push("TLS-CACert", eap.ca_cert.as_deref());
+            push("TLS-ServerDomainMask", eap.domain_match.as_deref());
+            push("TLS-ClientCert", eap.client_cert.as_deref());
+            push("TLS-ClientKey", eap.private_key.as_deref());
+            push(
+                "TLS-ClientKeyPassphrase",
+                eap.private_key_password.as_deref(),
+            );
+        }
+        EapMethod::Peap | EapMethod::Ttls => {
+            // 外层用匿名身份（若有），真实身份放在 phase 2
+            let outer = eap.anonymous_identity.as_deref().unwrap_or(&eap.identity);
+            let phase2 = eap.phase2.map(|p| iwd_phase2(eap.eap, p));
+            push("Identity", Some(outer));
+            push(&format!("{method}-CACert"), eap.ca_cert.as_deref());
+            push(
+                &format!("{method}-ServerDomainMask"),
+                eap.domain_match.as_deref(),
+            );
+            push(&format!("{method}-Phase2-Method"), phase2.as_deref());
+            push(&format!("{method}-Phase2-Identity"), Some(&eap.identity));
+            push(
+                &format!("{method}-Phase2-Password"),
+                eap.password.as_deref(),
+            );
+        }
+    }
+
+    if hidden {
+        lines.push(String::new());
+        lines.push("[Settings]".into());
+        lines.push("Hidden=true".into());
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// iwd 的 phase 2 方法名：TTLS 的非 EAP 内层认证写作 Tunneled-XXX
+fn iwd_phase2(method: EapMethod, auth: Phase2Auth) -> String {
+    match (method, auth) {
+        (EapMethod::Ttls, Phase2Auth::Pap) => "Tunneled-PAP".into(),
+        (EapMethod::Ttls, Phase2Auth::Chap) => "Tunneled-CHAP".into(),
+        (EapMethod::Ttls, Phase2Auth::Mschap) => "Tunneled-MSCHAP".into(),
+        (EapMethod::Ttls, Phase2Auth::Mschapv2) => "Tunneled-MSCHAPv2".into(),
+        _ => auth.to_string(),
+    }
+}
+
+/// iwd 存储文件名：纯字母数字 / 空格 / - / _ 原样，否则 "=" + 十六进制
+fn storage_name(ssid: &str) -> String {
+    if ssid
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        ssid.to_string()
+    } else {
+        let hex: String = ssid.bytes().map(|b| format!("{b:02x}")).collect();
+        format!("={hex}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eap(method: EapMethod, phase2: Option<Phase2Auth>) -> EnterpriseConfig {
+        EnterpriseConfig {
+            eap: method,
+            phase2,
+            identity: "alice".into(),
+            anonymous_identity: None,
+            ca_cert: None,
+            domain_match: None,
+            password: Some("pw".into()),
+            client_cert: None,
+            private_key: None,
+            private_key_password: None,
+        }
+    }
+
+    #[test]
+    fn only_the_associated_bss_is_in_use() {
+        let path = |s: &str| OwnedObjectPath::try_from(s).unwrap();
+        let (a, b) = (
+            path("/net/connman/iwd/0/1/a"),
+            path("/net/connman/iwd/0/1/b"),
+        );
+        assert!(bss_in_use(&a, Some(&a), 2));
+        assert!(!bss_in_use(&b, Some(&a), 2));
+        // 没有 ConnectedAccessPoint：多个 BSS 时都不标记
+        assert!(bss_in_use(&a, None, 1));
+        assert!(!bss_in_use(&a, None, 2));
+    }
+
+    #[test]
+    fn security_follows_network_type() {
+        assert!(security_from_kind("open").is_open());
+        assert!(security_from_kind("wep").is_wep());
+        assert_eq!(security_from_kind("psk"), Security::wpa2_psk());
+        assert!(security_from_kind("8021x").is_enterprise());
+        assert_eq!(security_from_kind("hotspot"), Security::unknown());
+    }
+
+    #[test]
+    fn peap_provisioning_file() {
+        let cfg = EnterpriseConfig {
+            anonymous_identity: Some("anonymous".into()),
+            ca_cert: Some("/etc/ssl/ca.pem".into()),
+            ..eap(EapMethod::Peap, Some(Phase2Auth::Mschapv2))
+        };
+        assert_eq!(
+            provisioning_file(&cfg, false),
+            "[Security]\n\
+             EAP-Method=PEAP\n\
+             EAP-Identity=anonymous\n\
+             EAP-PEAP-CACert=/etc/ssl/ca.pem\n\
+             EAP-PEAP-Phase2-Method=MSCHAPV2\n\
+             EAP-PEAP-Phase2-Identity=alice\n\
+             EAP-PEAP-Phase2-Password=pw\n"
+        );
+    }
+
+    #[test]
+    fn ttls_and_tls_provisioning_files() {
+        // TTLS 的非 EAP 内层认证要写成 Tunneled-XXX；隐藏网络追加 [Settings]
+        let ttls = provisioning_file(&eap(EapMethod::Ttls, Some(Phase2Auth::Pap)), true);
+        assert!(ttls.contains("EAP-Identity=alice\n"));
+        assert!(ttls.contains("EAP-TTLS-Phase2-Method=Tunneled-PAP\n"));
+        assert!(ttls.ends_with("\n[Settings]\nHidden=true\n"));
+
+        let tls = EnterpriseConfig {
+            password: None,
+            client_cert: Some("/c.pem".into()),
+            private_key: Some("/k.pem".into()),
+            ..eap(EapMethod::Tls, None)
+        };
+        assert_eq!(
+            provisioning_file(&tls, false),
+            "[Security]\n\
+             EAP-Method=TLS\n\
+             EAP-Identity=alice\n\
+             EAP-TLS-ClientCert=/c.pem\n\
+             EAP-TLS-ClientKey=/k.pem\n"
+        );
+    }
+
+    #[test]
+    fn storage_name_encodes_special_ssids() {
+        assert_eq!(storage_name("My Home-5G_1"), "My Home-5G_1");
+        assert_eq!(storage_name("a.b"), "=612e62");
+        assert_eq!(storage_name("中"), "=e4b8ad");
+    }
+}
@@ -0,0 +1,446 @@
+// src/list.rs — `list` 子命令：以 JSON / CSV / 表格输出网络列表
+//
+// JSON 格式对脚本是稳定接口（见 README），改动字段须同时提升 SCHEMA_VERSION。
+
+use crate::template;
+use crate::types::{AccessPoint, Band, Bss, KeyMgmt};
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// JSON 输出的格式版本
+const SCHEMA_VERSION: u32 = 1;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+    Table,
+}
+
+/// 认证方式，既用于 --security 过滤，也是 JSON 中 auth 字段的取值
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Auth {
+    // 开放网络（无加密）
+    Open,
+    // Enhanced Open
+    Owe,
+    Wep,
+    // WPA/WPA2 个人版
+    Psk,
+    // WPA3 个人版
+    Sae,
+    // 802.1X 企业网络
+    Enterprise,
+}
+
+/// --band 的取值
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum BandFilter {
+    #[value(name = "2.4")]
+    G2_4,
+    #[value(name = "5")]
+    G5,
+    #[value(name = "6")]
+    G6,
+}
+
+/// 过滤条件：各项之间为“且”，同一项的多个取值为“或”
+#[derive(Debug, Default)]
+pub struct Filter {
+    pub security: Vec<Auth>,
+    pub min_signal: Option<u8>,
+    pub band: Vec<BandFilter>,
+}
+
+impl Filter {
+    fn matches(&self, ap: &AccessPoint) -> bool {
+        let auth = auth(ap);
+        (self.security.is_empty() || self.security.iter().any(|a| auth.contains(a)))
+            && self.min_signal.is_none_or(|min| ap.signal >= min)
+            && (self.band.is_empty()
+                || ap
+                    .bss
+                    .iter()
+                    .any(|b| self.band.iter().any(|f| band_matches(*f, b.band))))
+    }
+}
+
+fn band_matches(filter: BandFilter, band: Band) -> bool {
+    matches!(
+        (filter, band),
+        (BandFilter::G2_4, Band::G2_4) | (BandFilter::G5, Band::G5) | (BandFilter::G6, Band::G6)
+    )
+}
+
+/// 网络支持的认证方式；过渡模式（如 WPA2/WPA3）同时列出多种，安全类型未知时为空
+fn auth(ap: &AccessPoint) -> Vec<Auth> {
+    let sec = &ap.security;
+    if sec.is_open() {
+        return vec![Auth::Open];
+    }
+    if sec.is_wep() {
+        return vec![Auth::Wep];
+    }
+    sec.key_mgmt
+        .iter()
+        .filter_map(|k| match k {
+            KeyMgmt::Psk => Some(Auth::Psk),
+            KeyMgmt::Sae => Some(Auth::Sae),
+            KeyMgmt::Eap => Some(Auth::Enterprise),
+            KeyMgmt::Owe => Some(Auth::Owe),
+            KeyMgmt::Other(_) => None,
+        })
+        .collect()
+}
+
+/// 频段的短名称，与 --band 的取值一致
+fn band_name(band: Band) -> Option<&'static str> {
+    match band {
+        Band::G2_4 => Some("2.4"),
+        Band::G5 => Some("5"),
+        Band::G6 => Some("6"),
+        Band::Unknown => None,
+    }
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+    version: u32,
+    networks: Vec<Network<'a>>,
+}
+
+/// JSON 中的一个网络（同一 SSID 的所有 BSSID 归为一组）
+#[derive(Serialize)]
+struct Network<'a> {
+    /// 扫描到的隐藏 AP 为空字符串
+    ssid: &'a str,
+    hidden: bool,
+    /// 显示用的加密描述，如 "WPA2/WPA3"
+    security: String,
+    auth: Vec<Auth>,
+    /// 0–100，组内最强的 BSSID
+    signal: u8,
+    in_use: bool,
+    saved: bool,
+    bss: Vec<BssEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct BssEntry<'a> {
+    bssid: &'a str,
+    /// MHz，未知时为 null
+    frequency: Option<u32>,
+    channel: Option<u32>,
+    band: Option<&'static str>,
+    signal: u8,
+    /// Mbit/s，未知时为 null
+    max_rate: Option<u32>,
+    in_use: bool,
+}
+
+impl<'a> Network<'a> {
+    fn new(ap: &'a AccessPoint, saved: bool) -> Self {
+        Self {
+            ssid: &ap.ssid,
+            hidden: ap.hidden,
+            security: ap.security.to_string(),
+            auth: auth(ap),
+            signal: ap.signal,
+            in_use: ap.in_use,
+            saved,
+            bss: ap.bss.iter().map(BssEntry::new).collect(),
+        }
+    }
+}
+
+impl<'a> BssEntry<'a> {
+    fn new(b: &'a Bss) -> Self {
+        let known = |v: u32| (v > 0).then_some(v);
+        Self {
+            bssid: &b.bssid,
+            frequency: known(b.frequency),
+            channel: known(b.channel),
+            band: band_name(b.band),
+            signal: b.signal,
+            max_rate: known(b.max_rate),
+            in_use: b.in_use,
+        }
+    }
+}
+
+/// 按格式输出过滤后的网络列表
+pub fn print(aps: &[AccessPoint], saved: &[String], filter: &Filter, format: Format) {
+    print!("{}", render(aps, saved, filter, format));
+}
+
+fn render(aps: &[AccessPoint], saved: &[String], filter: &Filter, format: Format) -> String {
+    let networks: Vec<Network> = aps
+        .iter()
+        .filter(|ap| filter.matches(ap))
+        .map(|ap| Network::new(ap, !ap.ssid.is_empty() && saved.contains(&ap.ssid)))
+        .collect();
+    match format {
+        Format::Json => json(networks),
+        Format::Csv => csv(&networks),
+        Format::Table => table(&networks),
+    }
+}
+
+fn json(networks: Vec<Network>) -> String {
+    let out = Output {
+        version: SCHEMA_VERSION,
+        networks,
+    };
+    serde_json::to_string_pretty(&out).unwrap_or_default() + "\n"
+}
+
+/// 每个网络一行，频段 / 信道 / BSSID 取信号最强的射频
+fn csv(networks: &[Network]) -> String {
+    let mut out = String::from("ssid,security,signal,band,channel,bssid,in_use,saved\n");
+    for n in networks {
+        let best = n.bss.iter().max_by_key(|b| b.signal);
+        let fields = [
+            n.ssid.to_string(),
+            n.security.clone(),
+            n.signal.to_string(),
+            best.and_then(|b| b.band).unwrap_or_default().to_string(),
+            best.and_then(|b| b.channel)
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            best.map(|b| b.bssid.to_string()).unwrap_or_default(),
+            n.in_use.to_string(),
+            n.saved.to_string(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out += &fields.join(",");
+        out.push('\n');
+    }
+    out
+}
+
+/// 含逗号、引号或换行的字段加引号，内部引号写两次
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// 对齐的表格，当前连接的网络以 * 标记
+fn table(networks: &[Network]) -> String {
+    const SSID_MAX: usize = 32;
+    let name = |n: &Network| {
+        if n.ssid.is_empty() {
+            "<hidden>".to_string()
+        } else {
+            n.ssid.to_string()
+        }
+    };
+    let ssid_width = networks
+        .iter()
+        .map(|n| template::width(&name(n)).min(SSID_MAX))
+        .max()
+        .unwrap_or(0)
+        .max("SSID".len());
+    let security_width = networks
+        .iter()
+        .map(|n| n.security.len())
+        .max()
+        .unwrap_or(0)
+        .max("SECURITY".len());
+
+    let cell = |s: &str, w: usize| {
+        let s = template::truncate(s, w);
+        let gap = w.saturating_sub(template::width(&s));
+        format!("{s}{}", " ".repeat(gap))
+    };
+    let mut out = format!(
+        "  {}  {}  SIGNAL  BAND  CH   SAVED\n",
+        cell("SSID", ssid_width),
+        cell("SECURITY", security_width)
+    );
+    for n in networks {
+        let best = n.bss.iter().max_by_key(|b| b.signal);
+        let line = format!(
+            "{} {}  {}  {:>5}%  {:<4}  {:<3}  {}",
+            if n.in_use { "*" } else { " " },
+            cell(&name(n), ssid_width),
+            cell(&n.security, security_width),
+            n.signal,
+            best.and_then(|b| b.band).unwrap_or("-"),
+            best.and_then(|b| b.channel)
+                .map_or("-".into(), |c| c.to_string()),
+            if n.saved { "yes" } else { "no" },
+        );
+        out += line.trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{group_by_ssid, nm_sec::*, ScanEntry, Security};
+
+    /// Home：WPA2/WPA3 过渡模式，5 GHz + 2.4 GHz 两个射频，已连接
+    /// Cafe：开放网络；Old：WPA1 TKIP；另有一个扫描到的隐藏 AP
+    fn sample() -> Vec<AccessPoint> {
+        let entry = |ssid: &str, security: Security, bssid: &str, mhz, signal, in_use| {
+            let mut bss = Bss::new(bssid.into(), mhz, signal, in_use);
+            bss.max_rate = if mhz > 5000 { 866 } else { 0 };
+            ScanEntry {
+                ssid: ssid.into(),
+                security,
+                bss,
+            }
+        };
+        let home = Security::from_nm_flags(true, 0, KEY_MGMT_PSK | KEY_MGMT_SAE | PAIR_CCMP);
+        let old = Security::from_nm_flags(true, KEY_MGMT_PSK | PAIR_TKIP, 0);
+        group_by_ssid(vec![
+            entry("Home", home.clone(), "AA:00:00:00:00:01", 5180, 82, true),
+            entry("Home", home, "AA:00:00:00:00:02", 2437, 60, false),
+            entry(
+                "Cafe",
+                Security::default(),
+                "AA:00:00:00:00:03",
+                2412,
+                45,
+                false,
+            ),
+            entry("Old", old, "AA:00:00:00:00:04", 2462, 20, false),
+            entry(
+                "",
+                Security::wpa2_psk(),
+                "AA:00:00:00:00:05",
+                5955,
+                30,
+                false,
+            ),
+        ])
+    }
+
+    fn names(aps: &[AccessPoint], filter: Filter) -> Vec<String> {
+        aps.iter()
+            .filter(|ap| filter.matches(ap))
+            .map(|ap| ap.ssid.clone())
+            .collect()
+    }
+
+    #[test]
+    fn json_snapshot() {
+        let aps = sample();
+        let filter = Filter {
+            security: vec![Auth::Sae],
+            ..Filter::default()
+        };
+        let out = render(&aps, &["Home".into()], &filter, Format::Json);
+        let expected = r#"{
+  "version": 1,
+  "networks": [
+    {
+      "ssid": "Home",
+      "hidden": false,
+      "security": "WPA2/WPA3",
+      "auth": [
+        "psk",
+        "sae"
+      ],
+      "signal": 82,
+      "in_use": true,
+      "saved": true,
+      "bss": [
+        {
+          "bssid": "AA:00:00:00:00:01",
+          "frequency": 5180,
+          "channel": 36,
+          "band": "5",
+          "signal": 82,
+          "max_rate": 866,
+          "in_use": true
+        },
+        {
+          "bssid": "AA:00:00:00:00:02",
+          "frequency": 2437,
+          "channel": 6,
+          "band": "2.4",
+          "signal": 60,
+          "max_rate": null,
+          "in_use": false
+        }
+      ]
+    }
+  ]
+}
+"#;
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn csv_rows_and_quoting() {
+        let out = render(&sample(), &["Home".into()], &Filter::default(), Format::Csv);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "ssid,security,signal,band,channel,bssid,in_use,saved",
+                "Home,WPA2/WPA3,82,5,36,AA:00:00:00:00:01,true,true",
+                "Cafe,Open,45,2.4,1,AA:00:00:00:00:03,false,false",
+                ",WPA2,30,6,1,AA:00:00:00:00:05,false,false",
+                "Old,WPA1 TKIP,20,2.4,11,AA:00:00:00:00:04,false,false",
+            ]
+        );
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn filter_by_security_band_and_signal() {
+        let aps = sample();
+        let by_security = |security| Filter {
+            security,
+            ..Filter::default()
+        };
+        // 过渡模式同时匹配 psk 和 sae
+        assert_eq!(
+            names(&aps, by_security(vec![Auth::Psk])),
+            ["Home", "", "Old"]
+        );
+        assert_eq!(names(&aps, by_security(vec![Auth::Sae])), ["Home"]);
+        assert_eq!(
+            names(&aps, by_security(vec![Auth::Open, Auth::Enterprise])),
+            ["Cafe"]
+        );
+
+        let by_band = |band| Filter {
+            band,
+            ..Filter::default()
+        };
+        // 任一射频在该频段即匹配
+        assert_eq!(names(&aps, by_band(vec![BandFilter::G5])), ["Home"]);
+        assert_eq!(
+            names(&aps, by_band(vec![BandFilter::G2_4])),
+            ["Home", "Cafe", "Old"]
+        );
+        assert_eq!(names(&aps, by_band(vec![BandFilter::G6])), [""]);
+
+        let strong = Filter {
+            min_signal: Some(45),
+            ..Filter::default()
+        };
+        assert_eq!(names(&aps, strong), ["Home", "Cafe"]);
+
+        // 不同选项之间为“且”
+        let both = Filter {
+            security: vec![Auth::Psk],
+            band: vec![BandFilter::G2_4],
+            min_signal: Some(30),
+        };
+        assert_eq!(names(&aps, both), ["Home"]);
+    }
+}
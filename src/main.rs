@@ -1,38 +1,196 @@
 // src/main.rs — 主入口 & 菜单逻辑
+mod backend;
+mod bemenu;
 mod cache;
+mod commands;
 mod config;
 mod daemon;
+mod dmenu;
+mod fuzzel;
+mod i18n;
+mod iwd;
+mod list;
+mod markup;
+mod menu;
+mod netinfo;
+mod nm_dbus;
 mod nmcli;
 mod notify;
+mod ping;
 mod qr;
 mod rofi;
+mod script;
+mod status;
+mod template;
+mod tofi;
+mod tui;
 mod types;
+mod wofi;
+mod wpa;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use backend::Backend;
+use clap::{ArgAction, Parser, Subcommand};
 use config::Config;
+use i18n::t;
+use menu::{Frontend, Role, SelectOpts, Selection};
 use std::os::unix::io::AsRawFd;
-use types::{AccessPoint, ConnectResult, MenuAction, RadioState, Security};
+use std::os::unix::process::CommandExt;
+use std::time::Duration;
+use types::{
+    AccessPoint, ConnectRequest, ConnectResult, EapMethod, EnterpriseConfig, MenuAction,
+    NetworkAction, RadioState, Security,
+};
 
 // ════════════════════════════════════════════════════════════════
 // CLI 参数
 // ════════════════════════════════════════════════════════════════
+//
+// 各模块 ValueEnum 的变体只写普通注释：文档注释会成为 --help 里未翻译的取值说明。
 
 #[derive(Parser)]
-#[command(name = "rofi-wifi", about = "rofi Wi-Fi 管理器", version)]
+#[command(
+    name = "rofi-wifi",
+    about = t!(CliAbout),
+    version,
+    disable_help_flag = true,
+    disable_version_flag = true
+)]
 struct Cli {
     #[command(subcommand)]
     cmd: Option<Cmd>,
+    /// 自带的 -h / -V 说明文字无法翻译，改为自己声明
+    #[arg(short, long, global = true, action = ArgAction::Help, help = t!(CliHelp))]
+    help: Option<bool>,
+    #[arg(short = 'V', long, action = ArgAction::Version, help = t!(CliVersion))]
+    version: Option<bool>,
 }
 
 #[derive(Subcommand)]
 enum Cmd {
     /// 启动后台守护进程（定时刷新缓存）
+    #[command(about = t!(CmdDaemon))]
     Daemon,
     /// 停止守护进程
+    #[command(about = t!(CmdDaemonStop))]
     DaemonStop,
     /// 立即执行一次扫描并更新缓存
+    #[command(about = t!(CmdScan))]
     Scan,
+    /// 输出网络列表（默认表格）
+    #[command(about = t!(CmdList))]
+    List {
+        #[arg(long, conflicts_with_all = ["csv", "table"], help = t!(ListJson))]
+        json: bool,
+        #[arg(long, conflicts_with = "table", help = t!(ListCsv))]
+        csv: bool,
+        #[arg(long, help = t!(ListTable))]
+        table: bool,
+        #[arg(long, help = t!(ListFresh))]
+        fresh: bool,
+        #[arg(long, value_enum, value_delimiter = ',', help = t!(ListSecurity))]
+        security: Vec<list::Auth>,
+        #[arg(long, value_name = "0-100", value_parser = clap::value_parser!(u8).range(0..=100), help = t!(ListMinSignal))]
+        min_signal: Option<u8>,
+        #[arg(long, value_enum, value_delimiter = ',', help = t!(ListBand))]
+        band: Vec<list::BandFilter>,
+    },
+    /// 连接网络（已保存的配置直接唤起）
+    #[command(about = t!(CmdConnect))]
+    Connect {
+        #[arg(help = t!(ArgSsid))]
+        ssid: String,
+        #[arg(long, help = t!(ArgPasswordStdin))]
+        password_stdin: bool,
+        #[arg(long, help = t!(ArgHidden))]
+        hidden: bool,
+        #[arg(long, help = t!(ArgBssid))]
+        bssid: Option<String>,
+        #[arg(long, value_name = "SECS", help = t!(ArgTimeout))]
+        timeout: Option<u64>,
+    },
+    /// 断开连接
+    #[command(about = t!(CmdDisconnect))]
+    Disconnect {
+        #[arg(help = t!(ArgDisconnectName))]
+        name: Option<String>,
+    },
+    /// 删除已保存的配置
+    #[command(about = t!(CmdForget))]
+    Forget {
+        #[arg(help = t!(ArgProfile))]
+        name: String,
+    },
+    /// 开关 Wi-Fi 无线电
+    #[command(about = t!(CmdRadio))]
+    Radio {
+        #[arg(value_enum, help = t!(ArgRadio))]
+        action: Option<commands::RadioAction>,
+    },
+    /// 输出或导出分享二维码
+    #[command(about = t!(CmdQr))]
+    Qr {
+        #[arg(help = t!(ArgQrSsid))]
+        ssid: Option<String>,
+        #[arg(long, value_enum, default_value_t, help = t!(ArgQrFormat))]
+        format: qr::Format,
+        #[arg(short, long, value_name = "FILE", help = t!(ArgOutput))]
+        output: Option<std::path::PathBuf>,
+        #[arg(long, value_name = "PX", default_value_t = 512, help = t!(ArgSize))]
+        size: u32,
+    },
+    /// 直接打开某个子菜单，Esc 退出程序
+    #[command(about = t!(CmdMenu))]
+    Menu {
+        #[command(subcommand)]
+        target: MenuTarget,
+    },
+    /// 给状态栏输出当前状态
+    #[command(about = t!(CmdStatus))]
+    Status {
+        #[arg(long, value_enum, default_value_t, help = t!(ArgStatusFormat))]
+        format: status::Format,
+        #[arg(long, help = t!(ArgWatch))]
+        watch: bool,
+        #[arg(long, value_name = "SECS", default_value_t = 5, help = t!(ArgInterval))]
+        interval: u64,
+    },
+    /// rofi script 模式的后台会话（由脚本调用自动启动）
+    #[command(hide = true)]
+    ScriptSession,
+    /// 终端全屏界面（TTY / SSH 等无图形环境）
+    #[command(about = t!(CmdTui))]
+    Tui,
+}
+
+/// `menu` 可直接打开的子菜单，对应主菜单中的同名入口
+#[derive(Subcommand, Clone, Copy)]
+enum MenuTarget {
+    #[command(about = t!(CmdMenuManual))]
+    Manual,
+    #[command(about = t!(CmdMenuDisconnect))]
+    Disconnect,
+    #[command(about = t!(CmdMenuForget))]
+    Forget,
+    #[command(about = t!(CmdMenuHotspot))]
+    Hotspot,
+    #[command(about = t!(CmdMenuDetails))]
+    Details,
+    #[command(about = t!(CmdMenuQr))]
+    Qr,
+}
+
+impl MenuTarget {
+    fn action(self) -> MenuAction {
+        match self {
+            MenuTarget::Manual => MenuAction::Manual,
+            MenuTarget::Disconnect => MenuAction::Disconnect,
+            MenuTarget::Forget => MenuAction::Forget,
+            MenuTarget::Hotspot => MenuAction::Hotspot,
+            MenuTarget::Details => MenuAction::Details,
+            MenuTarget::Qr => MenuAction::QrCode,
+        }
+    }
 }
 
 // ════════════════════════════════════════════════════════════════
@@ -56,33 +214,132 @@ enum Nav {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    // rofi -modi wifi:rofi-rwifi：参数是选中的行，不能交给 clap 解析
+    if script::is_active() {
+        return script::client().await;
+    }
+
+    // 先读配置确定界面语言，clap 的帮助文字也要用到
     let cfg = Config::load().unwrap_or_default();
+    i18n::init(cfg.language);
+    let cli = Cli::parse();
+    let net = backend::from_config(&cfg);
+    let ui = menu::from_config(&cfg);
 
     match cli.cmd {
-        Some(Cmd::Daemon) => daemon::start(&cfg).await?,
+        Some(Cmd::Daemon) => daemon::start(&cfg, &net).await?,
         Some(Cmd::DaemonStop) => daemon::stop()?,
         Some(Cmd::Scan) => {
-            do_scan().await;
-            println!("扫描完成，缓存已更新");
-        }
-        // 主菜单循环：Refresh 强制重扫，Back 直接重显，Quit 退出
-        None => {
-            let mut force = false;
-            loop {
-                match run_menu(&cfg, force).await? {
-                    Nav::Quit => break,
-                    Nav::Back => {
-                        force = false;
-                    }
-                    Nav::Refresh => {
-                        force = true;
-                    }
-                }
+            do_scan(&net).await;
+            println!("{}", t!(ScanDone));
+        }
+        Some(Cmd::List {
+            json,
+            csv,
+            table: _,
+            fresh,
+            security,
+            min_signal,
+            band,
+        }) => {
+            let format = match (json, csv) {
+                (true, _) => list::Format::Json,
+                (_, true) => list::Format::Csv,
+                _ => list::Format::Table,
+            };
+            let filter = list::Filter {
+                security,
+                min_signal,
+                band,
+            };
+            let (aps, saved) = tokio::join!(fetch_aps(&cfg, &net, fresh), net.saved_connections());
+            list::print(&aps, &saved.unwrap_or_default(), &filter, format);
+        }
+        Some(Cmd::Connect {
+            ssid,
+            password_stdin,
+            hidden,
+            bssid,
+            timeout,
+        }) => {
+            let args = commands::ConnectArgs {
+                ssid,
+                password_stdin,
+                hidden,
+                bssid,
+                timeout,
+            };
+            std::process::exit(commands::connect(args, &cfg, &net).await as i32);
+        }
+        Some(Cmd::Disconnect { name }) => {
+            std::process::exit(commands::disconnect(name, &net).await as i32)
+        }
+        Some(Cmd::Forget { name }) => std::process::exit(commands::forget(name, &net).await as i32),
+        Some(Cmd::Qr {
+            ssid,
+            format,
+            output,
+            size,
+        }) => {
+            let args = commands::QrArgs {
+                ssid,
+                format,
+                output,
+                size,
+            };
+            std::process::exit(commands::qr(args, &net).await as i32);
+        }
+        Some(Cmd::Menu { target }) => open_submenu(target.action(), &cfg, &net, &ui).await?,
+        Some(Cmd::Status {
+            format,
+            watch,
+            interval,
+        }) => status::run(&cfg, &net, format, watch, interval).await,
+        Some(Cmd::Radio { action }) => {
+            std::process::exit(commands::radio(action, &net).await as i32)
+        }
+        Some(Cmd::ScriptSession) => {
+            let (cfg, net) = (&cfg, &net);
+            script::serve(|ui| async move { menu_loop(cfg, net, &ui).await }).await?;
+        }
+        Some(Cmd::Tui) => {
+            let (cfg, net) = (&cfg, &net);
+            tui::run(|ui| async move { menu_loop(cfg, net, &ui).await }).await?;
+        }
+        None => menu_loop(&cfg, &net, &ui).await?,
+    }
+
+    Ok(())
+}
+
+/// 主菜单循环：Refresh 强制重扫，Back 直接重显，Quit 退出
+async fn menu_loop(cfg: &Config, net: &Backend, ui: &Frontend) -> Result<()> {
+    let mut force = false;
+    loop {
+        match run_menu(cfg, net, ui, force).await? {
+            Nav::Quit => return Ok(()),
+            Nav::Back => {
+                force = false;
+            }
+            Nav::Refresh => {
+                force = true;
             }
         }
     }
+}
 
+/// 直接打开某个子菜单：只执行这一个动作，完成或按 Esc 后退出程序
+async fn open_submenu(
+    action: MenuAction,
+    cfg: &Config,
+    net: &Backend,
+    ui: &Frontend,
+) -> Result<()> {
+    // 列表只用于按 SSID 查加密方式等，过期的缓存也够用，不为此扫描
+    let cache_path = Config::cache_path(net.interfaces().station().as_deref());
+    let aps = cache::read(&cache_path, u64::MAX).unwrap_or_default();
+    let curr_ssid = net.current_ssid().await;
+    handle_action(action, cfg, net, ui, &curr_ssid, &aps).await?;
     Ok(())
 }
 
@@ -90,8 +347,8 @@ async fn main() -> Result<()> {
 // 扫描 & 缓存
 // ════════════════════════════════════════════════════════════════
 
-async fn do_scan() {
-    let cache_path = Config::cache_path();
+async fn do_scan(net: &Backend) {
+    let cache_path = Config::cache_path(net.interfaces().station().as_deref());
     let lock_path = Config::lock_path();
 
     let lock_file = match std::fs::OpenOptions::new()
@@ -102,7 +359,7 @@ async fn do_scan() {
     {
         Ok(f) => f,
         Err(e) => {
-            eprintln!("无法创建锁文件: {e}");
+            eprintln!("{}: {e}", t!(LockFileFailed));
             return;
         }
     };
@@ -113,32 +370,44 @@ async fn do_scan() {
         return;
     }
 
-    nmcli::rescan().await;
-    match nmcli::list_access_points().await {
+    match backend::scan_with_hidden(net).await {
         Ok(aps) => {
             let _ = cache::write(&cache_path, &aps);
         }
-        Err(e) => eprintln!("扫描失败: {e}"),
+        Err(e) => eprintln!("{}: {e}", t!(ScanFailed)),
     }
 
     unsafe { libc::flock(fd, libc::LOCK_UN) };
 }
 
 /// 获取 AP 列表：缓存有效则秒返回 + 后台刷新，否则前台等待
-async fn get_aps(cfg: &Config, force_refresh: bool) -> Vec<AccessPoint> {
-    let cache_path = Config::cache_path();
+async fn get_aps(cfg: &Config, net: &Backend, force_refresh: bool) -> Vec<AccessPoint> {
+    let cache_path = Config::cache_path(net.interfaces().station().as_deref());
 
     if force_refresh {
         cache::invalidate(&cache_path);
     }
 
     if let Some(aps) = cache::read(&cache_path, cfg.cache_ttl) {
-        tokio::spawn(async { do_scan().await });
+        let net = net.clone();
+        tokio::spawn(async move { do_scan(&net).await });
         return aps;
     }
 
-    notify::low("扫描中", "正在搜索附近 Wi-Fi…");
-    do_scan().await;
+    notify::low(t!(Scanning), t!(ScanningBody));
+    do_scan(net).await;
+    cache::read(&cache_path, cfg.cache_ttl * 10).unwrap_or_default()
+}
+
+/// 命令行取 AP 列表：缓存有效直接用，否则（或 fresh 时）前台扫描，不发通知
+async fn fetch_aps(cfg: &Config, net: &Backend, fresh: bool) -> Vec<AccessPoint> {
+    let cache_path = Config::cache_path(net.interfaces().station().as_deref());
+    if !fresh {
+        if let Some(aps) = cache::read(&cache_path, cfg.cache_ttl) {
+            return aps;
+        }
+    }
+    do_scan(net).await;
     cache::read(&cache_path, cfg.cache_ttl * 10).unwrap_or_default()
 }
 
@@ -146,49 +415,76 @@ async fn get_aps(cfg: &Config, force_refresh: bool) -> Vec<AccessPoint> {
 // 主菜单（返回 Nav 而非 ()）
 // ════════════════════════════════════════════════════════════════
 
-async fn run_menu(cfg: &Config, force_refresh: bool) -> Result<Nav> {
-    let (aps, radio, curr_ssid) = tokio::join!(
-        get_aps(cfg, force_refresh),
-        nmcli::radio_state(),
-        nmcli::current_ssid(),
+async fn run_menu(cfg: &Config, net: &Backend, ui: &Frontend, force_refresh: bool) -> Result<Nav> {
+    let (aps, radio, curr_ssid, devices, saved) = tokio::join!(
+        get_aps(cfg, net, force_refresh),
+        net.radio_state(),
+        net.current_ssid(),
+        net.devices(),
+        net.saved_connections(),
     );
+    let saved = saved.unwrap_or_default();
+    let station = net.interfaces().station();
 
     let toggle_label = match radio {
-        RadioState::Enabled => "⚡ toggle off",
-        RadioState::Disabled => "⚡ toggle on",
+        RadioState::Enabled => t!(MenuToggleOff),
+        RadioState::Disabled => t!(MenuToggleOn),
     };
 
     let refresh_label = {
-        let remaining = cache::remaining_ttl(&Config::cache_path(), cfg.cache_ttl);
+        let remaining =
+            cache::remaining_ttl(&Config::cache_path(station.as_deref()), cfg.cache_ttl);
         if remaining.is_zero() {
-            "🔄 refresh  (缓存已过期)".to_string()
+            t!(MenuRefreshExpired).to_string()
         } else {
-            format!("🔄 refresh  (缓存剩余 {}s)", remaining.as_secs())
+            t!(MenuRefreshLeft, secs = remaining.as_secs())
         }
     };
 
-    let mut menu_items: Vec<String> = vec![
-        toggle_label.into(),
-        refresh_label,
-        "✏️  manual".into(),
-        "❌ disconnect".into(),
-        "🗑️  forget".into(),
-        "📡 hotspot".into(),
+    // 每行文字与动作一一对应，选择结果按下标映射，不依赖文字内容
+    let mut rows: Vec<(String, MenuAction)> = vec![
+        (toggle_label.into(), MenuAction::ToggleRadio),
+        (refresh_label, MenuAction::Refresh),
+        (t!(MenuManual).into(), MenuAction::Manual),
+        (t!(MenuDisconnect).into(), MenuAction::Disconnect),
+        (t!(MenuForget).into(), MenuAction::Forget),
+        (t!(MenuHotspot).into(), MenuAction::Hotspot),
     ];
 
-    let has_connection = curr_ssid.is_some();
-    let header_count = if has_connection {
-        menu_items.push("📊 details".into());
-        menu_items.push("📷 qrcode".into());
-        8usize
-    } else {
-        6usize
-    };
+    // 只有一块网卡时不显示切换入口
+    if devices.map_or(0, |d| d.len()) > 1 {
+        let current = station.clone().unwrap_or_else(|| "auto".into());
+        rows.push((t!(MenuInterface, name = current), MenuAction::Interface));
+    }
+
+    if curr_ssid.is_some() {
+        rows.push((t!(MenuDetails).into(), MenuAction::Details));
+        rows.push((t!(MenuQrCode).into(), MenuAction::QrCode));
+    }
+    let header_count = rows.len();
 
-    let ap_start = menu_items.len();
+    let ap_start = rows.len();
     for ap in &aps {
-        menu_items.push(ap.display_line());
+        let line = ap.display_line(&cfg.row_format, &cfg.glyphs, saved.contains(&ap.ssid));
+        rows.push((line, MenuAction::Connect(ap.clone())));
+    }
+    // 宽度按纯文字计算，之后再换成标记
+    let width = rows.iter().map(|(s, _)| template::width(s)).max();
+    let markup = cfg.markup && ui.supports_markup();
+    if markup {
+        for (line, action) in &mut rows {
+            *line = match action {
+                MenuAction::Connect(ap) => ap.display_markup(
+                    &cfg.row_format,
+                    &cfg.glyphs,
+                    saved.contains(&ap.ssid),
+                    &cfg.colors,
+                ),
+                _ => markup::escape(line),
+            };
+        }
     }
+    let (menu_items, actions): (Vec<String>, Vec<MenuAction>) = rows.into_iter().unzip();
 
     let highlight = curr_ssid.as_ref().and_then(|ssid| {
         aps.iter()
@@ -196,61 +492,89 @@ async fn run_menu(cfg: &Config, force_refresh: bool) -> Result<Nav> {
             .map(|i| ap_start + i)
     });
 
-    let warning = if aps.iter().any(|ap| ap.security == Security::Open) {
-        Some("⚠ 列表中含有开放（无加密）网络，请谨慎连接")
+    // 开放 / WEP 网络标红
+    let urgent: Vec<usize> = aps
+        .iter()
+        .enumerate()
+        .filter(|(_, ap)| ap.security.is_open() || ap.security.is_wep())
+        .map(|(i, _)| ap_start + i)
+        .collect();
+    let warning = if !urgent.is_empty() {
+        Some(t!(OpenNetworkWarning))
     } else {
         None
     };
 
+    // 作用于高亮网络的快捷键，提示写在消息区
+    let bindings = if ui.supports_keys() {
+        cfg.keybindings.bindings()
+    } else {
+        vec![]
+    };
+    let (keys, key_actions): (Vec<String>, Vec<NetworkAction>) = bindings.into_iter().unzip();
+    let hint = (!keys.is_empty()).then(|| {
+        let parts: Vec<String> = keys
+            .iter()
+            .zip(&key_actions)
+            .map(|(key, action)| format!("{key} {}", action.label()))
+            .collect();
+        parts.join("  ·  ")
+    });
+    let message: Vec<&str> = [warning, hint.as_deref()].into_iter().flatten().collect();
+    let message = message.join("\n");
+
     let max_lines = if radio == RadioState::Disabled {
         1
     } else {
         (aps.len() + header_count).min(cfg.max_lines)
     };
 
-    let choice = rofi::main_menu(
-        &menu_items,
-        "📶 Wi-Fi: ",
-        cfg,
-        highlight,
-        warning,
-        max_lines,
-    )
-    .await;
-
-    // 主菜单按 Esc → 退出程序
+    let opts = SelectOpts {
+        lines: Some(max_lines),
+        message: (!message.is_empty()).then_some(message.as_str()),
+        active: highlight,
+        width: width.map(|w| w + 4),
+        urgent: &urgent,
+        no_custom: false,
+        root: true,
+        refresh: Some(Duration::from_secs(cfg.cache_ttl.clamp(1, 10))),
+        keys: &keys,
+        markup,
+        role: Role::Main,
+    };
+    let choice = ui.select(&menu_items, t!(MenuPrompt), opts).await;
+
+    // 主菜单按 Esc → 退出程序；TUI 定时超时 → 重读缓存（后台扫描会持续更新它）
     let choice = match choice {
+        Some(Selection::Expired) => return Ok(Nav::Back),
         Some(c) => c,
         None => return Ok(Nav::Quit),
     };
 
-    let action = parse_action(&choice, &aps, &curr_ssid);
-    handle_action(action, cfg, &curr_ssid, &aps).await
+    let action = parse_action(choice, actions, &key_actions, &aps);
+    handle_action(action, cfg, net, ui, &curr_ssid, &aps).await
 }
 
-fn parse_action(choice: &str, aps: &[AccessPoint], curr_ssid: &Option<String>) -> MenuAction {
-    match choice.trim() {
-        s if s.starts_with("⚡") => MenuAction::ToggleRadio,
-        s if s.starts_with("🔄") => MenuAction::Refresh,
-        s if s.starts_with("✏️") => MenuAction::Manual,
-        "❌ disconnect" => MenuAction::Disconnect,
-        s if s.starts_with("🗑️") => MenuAction::Forget,
-        "📡 hotspot" => MenuAction::Hotspot,
-        "📊 details" => MenuAction::Details,
-        "📷 qrcode" => MenuAction::QrCode,
-        _ => {
-            if let Some(ap) = aps.iter().find(|ap| choice.contains(&ap.ssid)) {
-                MenuAction::Connect(ap.clone())
-            } else if let Some(ssid) = curr_ssid {
-                if let Some(ap) = aps.iter().find(|ap| &ap.ssid == ssid) {
-                    MenuAction::Connect(ap.clone())
-                } else {
-                    MenuAction::Refresh
-                }
-            } else {
-                MenuAction::Refresh
+/// 选中行按下标取动作；快捷键只作用于网络行；自由输入只按 SSID 整体匹配
+fn parse_action(
+    choice: Selection,
+    mut actions: Vec<MenuAction>,
+    key_actions: &[NetworkAction],
+    aps: &[AccessPoint],
+) -> MenuAction {
+    match choice {
+        Selection::Row(i) if i < actions.len() => actions.swap_remove(i),
+        Selection::Key(k, i) => match (key_actions.get(k), actions.get(i)) {
+            (Some(&action), Some(MenuAction::Connect(ap))) => {
+                MenuAction::OnNetwork(action, ap.clone())
             }
-        }
+            _ => MenuAction::Refresh,
+        },
+        Selection::Row(_) | Selection::Expired => MenuAction::Refresh,
+        Selection::Custom(text) => aps
+            .iter()
+            .find(|ap| !ap.ssid.is_empty() && ap.ssid == text.trim())
+            .map_or(MenuAction::Refresh, |ap| MenuAction::Connect(ap.clone())),
     }
 }
 
@@ -261,15 +585,17 @@ fn parse_action(choice: &str, aps: &[AccessPoint], curr_ssid: &Option<String>) -
 async fn handle_action(
     action: MenuAction,
     cfg: &Config,
+    net: &Backend,
+    ui: &Frontend,
     curr_ssid: &Option<String>,
     aps: &[AccessPoint],
 ) -> Result<Nav> {
     match action {
         // ── Wi-Fi 开关 ──────────────────────────────────────────
         MenuAction::ToggleRadio => {
-            let enable = nmcli::radio_state().await == RadioState::Disabled;
-            nmcli::set_radio(enable).await?;
-            notify::normal("Wi-Fi", if enable { "已开启" } else { "已关闭" });
+            let enable = net.radio_state().await == RadioState::Disabled;
+            net.set_radio(enable).await?;
+            notify::normal("Wi-Fi", if enable { t!(RadioOn) } else { t!(RadioOff) });
             if enable {
                 // 开启后等 1s 让扫描结果出来，再交由 loop 强制刷新
                 tokio::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -285,7 +611,7 @@ async fn handle_action(
         // ── 手动输入 ────────────────────────────────────────────
         MenuAction::Manual => {
             // Esc → 回主菜单
-            let input = match rofi::input_prompt("手动连接 (SSID 或 SSID,密码)", cfg).await {
+            let input = match ui.input_prompt(t!(ManualPrompt)).await {
                 Some(s) if !s.is_empty() => s,
                 _ => return Ok(Nav::Back),
             };
@@ -297,10 +623,18 @@ async fn handle_action(
                 (input.trim().to_string(), None)
             };
             if ssid.is_empty() {
-                notify::critical("错误", "SSID 不能为空");
+                notify::critical(t!(TitleError), t!(SsidEmpty));
                 return Ok(Nav::Back);
             }
-            do_connect_new(&ssid, pass.as_deref(), cfg).await;
+            // 不在扫描列表里的 SSID 按隐藏网络处理
+            let hidden = !aps.iter().any(|ap| ap.ssid == ssid && !ap.hidden);
+            let req = ConnectRequest {
+                ssid,
+                password: pass,
+                hidden,
+                ..Default::default()
+            };
+            do_connect_new(req, cfg, net, ui).await;
         }
 
         // ── 断开 ────────────────────────────────────────────────
@@ -308,45 +642,50 @@ async fn handle_action(
             let ssid = match curr_ssid {
                 Some(s) => s.clone(),
                 None => {
-                    notify::low("提示", "当前没有已连接的 Wi-Fi");
+                    notify::low(t!(TitleInfo), t!(NotConnected));
                     return Ok(Nav::Back);
                 }
             };
             // 确认框按 Esc → 回主菜单
-            if rofi::confirm(&format!("断开 {ssid}？"), cfg).await {
-                match nmcli::disconnect(&ssid).await {
-                    Ok(_) => notify::normal("已断开", &ssid),
-                    Err(e) => notify::critical("断开失败", &e.to_string()),
+            if ui.confirm(&t!(ConfirmDisconnect, ssid = ssid)).await {
+                match net.disconnect(&ssid).await {
+                    Ok(_) => notify::normal(t!(Disconnected), &ssid),
+                    Err(e) => notify::critical(t!(DisconnectFailed), &e.to_string()),
                 }
             }
         }
 
         // ── 忘记网络 ────────────────────────────────────────────
         MenuAction::Forget => {
-            let saved = nmcli::saved_connections().await.unwrap_or_default();
+            let saved = net.saved_connections().await.unwrap_or_default();
             if saved.is_empty() {
-                notify::low("提示", "没有已保存的 Wi-Fi 配置");
+                notify::low(t!(TitleInfo), t!(NoSavedNetworks));
                 return Ok(Nav::Back);
             }
             // 网络列表按 Esc → 回主菜单
-            let name = match rofi::dmenu(&saved, "🗑 忘记哪个网络？", cfg, &["-lines", "6"]).await
+            let name = match ui
+                .select(&saved, t!(ForgetPrompt), SelectOpts::lines(6))
+                .await
             {
-                Some(n) => n,
+                Some(n) => n.text(&saved),
                 None => return Ok(Nav::Back),
             };
-            // 确认框按 Esc → 回主菜单
-            if rofi::confirm(&format!("永久删除「{name}」？"), cfg).await {
-                match nmcli::delete_connection(&name).await {
-                    Ok(_) => notify::normal("已删除", &format!("{name} 的连接配置")),
-                    Err(e) => notify::critical("删除失败", &e.to_string()),
-                }
-            }
+            forget_network(&name, net, ui).await;
         }
 
         // ── 热点 ────────────────────────────────────────────────
         MenuAction::Hotspot => {
             // 内部 Esc 均回主菜单
-            handle_hotspot(cfg).await;
+            handle_hotspot(net, ui).await;
+        }
+
+        // ── 切换网卡：之后的扫描 / 连接都作用于新网卡 ─────────────
+        MenuAction::Interface => {
+            if let Some(ifname) = pick_interface(cfg, net, ui).await? {
+                net.interfaces().select(Some(ifname.clone()));
+                notify::low(t!(TitleInterface), &t!(InterfaceSwitched, name = ifname));
+                return Ok(Nav::Refresh);
+            }
         }
 
         // ── 连接详情 ────────────────────────────────────────────
@@ -354,26 +693,11 @@ async fn handle_action(
             let ssid = match curr_ssid {
                 Some(s) => s.clone(),
                 None => {
-                    notify::low("提示", "未连接任何 Wi-Fi");
+                    notify::low(t!(TitleInfo), t!(NotConnected));
                     return Ok(Nav::Back);
                 }
             };
-            notify::low("获取中", "正在读取连接信息…");
-            match nmcli::get_details(&ssid, &cfg.ping_host).await {
-                Ok(d) => {
-                    let ping_str = match d.ping_ms {
-                        Some(ms) => format!("{:.1} ms", ms),
-                        None => "超时".into(),
-                    };
-                    let content = format!(
-                        "SSID     : {}\nIP       : {}\n网关     : {}\nDNS      : {}\n安全     : {}\n信号     : {}%\n延迟     : {}",
-                        d.ssid, d.ip, d.gateway, d.dns, d.security, d.signal, ping_str
-                    );
-                    // 详情页按 Esc → 回主菜单
-                    rofi::show_info(&format!("📊 {}", d.ssid), &content, cfg).await;
-                }
-                Err(e) => notify::critical("获取失败", &e.to_string()),
-            }
+            show_details(&ssid, cfg, net, ui).await;
         }
 
         // ── 二维码 ──────────────────────────────────────────────
@@ -381,51 +705,124 @@ async fn handle_action(
             let ssid = match curr_ssid {
                 Some(s) => s.clone(),
                 None => {
-                    notify::low("提示", "未连接任何 Wi-Fi");
+                    notify::low(t!(TitleInfo), t!(NotConnected));
                     return Ok(Nav::Back);
                 }
             };
-            let pass = nmcli::saved_password(&ssid).await.unwrap_or_default();
             let security = aps
                 .iter()
                 .find(|ap| ap.ssid == ssid)
                 .map(|ap| ap.security.clone())
-                .unwrap_or(Security::Wpa2);
-            match qr::wifi_qr(&ssid, &pass, &security) {
-                // 二维码页按 Esc → 回主菜单
-                Ok(qr_text) => rofi::show_qr(&ssid, &qr_text, cfg).await,
-                Err(e) => notify::critical("生成失败", &e.to_string()),
-            }
+                .unwrap_or_else(Security::wpa2_psk);
+            show_qr(&ssid, &security, net, ui).await;
         }
 
+        // ── 快捷键：对高亮的网络执行动作 ──────────────────────────
+        MenuAction::OnNetwork(action, ap) => match action {
+            // 已连接的网络看完整详情，其余只能展示扫描到的信息
+            NetworkAction::Details if curr_ssid.as_deref() == Some(ap.ssid.as_str()) => {
+                show_details(&ap.ssid, cfg, net, ui).await;
+            }
+            NetworkAction::Details => {
+                let saved = net.saved_connections().await.unwrap_or_default();
+                let content = ap_info(&ap, saved.contains(&ap.ssid));
+                ui.show_info(&format!("📊 {}", ap.display_name()), &content)
+                    .await;
+            }
+            NetworkAction::Forget => {
+                let saved = net.saved_connections().await.unwrap_or_default();
+                if ap.ssid.is_empty() || !saved.contains(&ap.ssid) {
+                    notify::low(t!(TitleInfo), &t!(NotSaved, ssid = ap.display_name()));
+                    return Ok(Nav::Back);
+                }
+                forget_network(&ap.ssid, net, ui).await;
+            }
+            NetworkAction::QrCode => show_qr(&ap.ssid, &ap.security, net, ui).await,
+        },
+
         // ── 连接具体 AP ─────────────────────────────────────────
-        MenuAction::Connect(ap) => {
-            if ap.security == Security::Open {
-                let msg = format!("⚠ {} 是开放网络，流量不加密，确认连接？", ap.ssid);
+        MenuAction::Connect(mut ap) => {
+            // 扫描到的隐藏 AP 不知道 SSID，先让用户输入
+            if ap.ssid.is_empty() {
+                match ui.input_prompt(t!(HiddenSsidPrompt)).await {
+                    Some(s) if !s.trim().is_empty() => ap.ssid = s.trim().to_string(),
+                    _ => return Ok(Nav::Back),
+                }
+            }
+
+            if ap.security.is_open() {
+                let msg = t!(ConfirmOpen, ssid = ap.ssid);
                 // 警告框按 Esc → 回主菜单
-                if !rofi::confirm(&msg, cfg).await {
+                if !ui.confirm(&msg).await {
                     return Ok(Nav::Back);
                 }
             }
 
-            let saved = nmcli::saved_connections().await.unwrap_or_default();
+            // 同名多个 BSS 且后端能指定 BSSID 时展开，让用户选自动或指定某个 AP
+            let bssid = if ap.bss.len() > 1 && net.supports_bssid() {
+                match pick_bss(&ap, cfg, ui).await {
+                    Some(b) => b,
+                    None => return Ok(Nav::Back),
+                }
+            } else {
+                None
+            };
+
+            let saved = net.saved_connections().await.unwrap_or_default();
             if saved.iter().any(|n| n == &ap.ssid) {
-                notify::normal("连接中…", &ap.ssid);
-                match nmcli::connect_saved(&ap.ssid, cfg).await {
-                    Ok(_) => handle_post_connect(&ap.ssid, cfg).await,
-                    Err(e) => notify::critical("连接失败", &e.to_string()),
+                notify::normal(t!(Connecting), &ap.ssid);
+                match net
+                    .connect_saved(&ap.ssid, bssid.as_deref(), cfg.connect_timeout)
+                    .await
+                {
+                    ConnectResult::Success { ip } => {
+                        handle_post_connect_with_ip(&ap.ssid, &ip, cfg, net).await
+                    }
+                    ConnectResult::WrongPassword => {
+                        notify::critical(t!(ConnectFailed), t!(WrongPassword))
+                    }
+                    ConnectResult::Timeout => {
+                        notify::critical(t!(TitleTimeout), &t!(TimeoutBody, ssid = ap.ssid))
+                    }
+                    ConnectResult::NotFound => {
+                        notify::critical(t!(ConnectFailed), &t!(NotNearby, ssid = ap.ssid))
+                    }
+                    ConnectResult::EapFailed(msg) | ConnectResult::Failed(msg) => {
+                        notify::critical(t!(ConnectFailed), &msg)
+                    }
                 }
+            } else if ap.security.is_enterprise() {
+                // 企业网络走 802.1X 向导，任一步按 Esc → 回主菜单
+                let eap = match prompt_enterprise(&ap.ssid, ui).await {
+                    Some(e) => e,
+                    None => return Ok(Nav::Back),
+                };
+                let req = ConnectRequest {
+                    ssid: ap.ssid.clone(),
+                    bssid,
+                    hidden: ap.hidden,
+                    enterprise: Some(eap),
+                    ..Default::default()
+                };
+                do_connect_enterprise(req, cfg, net).await;
             } else {
                 let pass = if ap.security.needs_password() {
                     // 密码框按 Esc → 回主菜单
-                    match rofi::password_prompt("", cfg).await {
+                    match ui.password_prompt("").await {
                         Some(p) if !p.is_empty() => Some(p),
                         _ => return Ok(Nav::Back),
                     }
                 } else {
                     None
                 };
-                do_connect_new(&ap.ssid, pass.as_deref(), cfg).await;
+                let req = ConnectRequest {
+                    ssid: ap.ssid.clone(),
+                    password: pass,
+                    bssid,
+                    hidden: ap.hidden,
+                    enterprise: None,
+                };
+                do_connect_new(req, cfg, net, ui).await;
             }
         }
     }
@@ -433,93 +830,345 @@ async fn handle_action(
     Ok(Nav::Back)
 }
 
+// ════════════════════════════════════════════════════════════════
+// 详情 / 二维码 / 删除
+// ════════════════════════════════════════════════════════════════
+
+/// 已连接网络的详情页；按 Esc 回主菜单
+async fn show_details(ssid: &str, cfg: &Config, net: &Backend, ui: &Frontend) {
+    notify::low(t!(Fetching), t!(FetchingDetails));
+    match net.get_details(ssid, &cfg.ping_host).await {
+        Ok(d) => {
+            let ping_str = match d.ping_ms {
+                Some(ms) => format!("{:.1} ms", ms),
+                None => t!(PingTimeout).into(),
+            };
+            let content = t!(
+                DetailsBody,
+                ssid = d.ssid,
+                ip = d.ip,
+                gateway = d.gateway,
+                dns = d.dns,
+                security = d.security,
+                signal = d.signal,
+                ping = ping_str,
+            );
+            ui.show_info(&format!("📊 {}", d.ssid), &content).await;
+        }
+        Err(e) => notify::critical(t!(FetchFailed), &e.to_string()),
+    }
+}
+
+/// 未连接网络的扫描信息，附上每个 BSS
+fn ap_info(ap: &AccessPoint, saved: bool) -> String {
+    let mut content = t!(
+        ApInfoBody,
+        ssid = ap.display_name(),
+        security = ap.security,
+        signal = ap.signal,
+        bars = ap.bars,
+        saved = if saved { t!(Yes) } else { t!(No) },
+    );
+    if !ap.bss.is_empty() {
+        content.push('\n');
+        for b in &ap.bss {
+            content.push('\n');
+            content.push_str(b.display_line().trim_end());
+        }
+    }
+    content
+}
+
+/// 分享二维码；需要密码却取不到已保存密码时只提示
+async fn show_qr(ssid: &str, security: &Security, net: &Backend, ui: &Frontend) {
+    let pass = net.saved_password(ssid).await.unwrap_or_default();
+    if pass.is_empty() && security.needs_password() {
+        notify::low(t!(TitleInfo), &t!(NoSavedPassword, ssid = ssid));
+        return;
+    }
+    let qr_text = match qr::wifi_qr(ssid, &pass, security) {
+        Ok(text) => text,
+        Err(e) => return notify::critical(t!(QrFailed), &e.to_string()),
+    };
+    // 二维码页按 Esc → 回主菜单
+    let actions = [t!(QrSaveImage).to_string(), t!(QrOpenImage).to_string()];
+    let Some(action) = ui.show_qr(ssid, &qr_text, &actions).await else {
+        return;
+    };
+    let png = match qr::export(ssid, &pass, security, qr::Format::Png, 512) {
+        Ok(png) => png,
+        Err(e) => return notify::critical(t!(QrFailed), &e.to_string()),
+    };
+    let (result, title) = if action == 0 {
+        (save_qr_image(ssid, &png), t!(QrSaveFailed))
+    } else {
+        (open_qr_image(&png), t!(QrOpenFailed))
+    };
+    if let Err(e) = result {
+        notify::critical(title, &e.to_string());
+    }
+}
+
+/// 保存到图片目录（没有时为主目录），文件名为 wifi-<SSID>.png
+fn save_qr_image(ssid: &str, png: &[u8]) -> Result<()> {
+    let dir = dirs::picture_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(|| ".".into());
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("wifi-{}.png", ssid.replace(['/', '\0'], "_")));
+    std::fs::write(&path, png)?;
+    notify::normal(t!(QrSaved), &path.display().to_string());
+    Ok(())
+}
+
+/// 写到运行时目录后交给 xdg-open，不等待看图软件退出
+fn open_qr_image(png: &[u8]) -> Result<()> {
+    let path = Config::qr_image_path();
+    std::fs::write(&path, png)?;
+    std::process::Command::new("xdg-open")
+        .arg(&path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .process_group(0)
+        .spawn()?;
+    Ok(())
+}
+
+/// 确认后删除已保存的配置；确认框按 Esc → 回主菜单
+async fn forget_network(name: &str, net: &Backend, ui: &Frontend) {
+    if ui.confirm(&t!(ConfirmForget, name = name)).await {
+        match net.forget(name).await {
+            Ok(_) => notify::normal(t!(Forgotten), &t!(ForgottenBody, name = name)),
+            Err(e) => notify::critical(t!(ForgetFailed), &e.to_string()),
+        }
+    }
+}
+
 // ════════════════════════════════════════════════════════════════
 // 连接辅助函数
 // ════════════════════════════════════════════════════════════════
 
-async fn do_connect_new(ssid: &str, password: Option<&str>, cfg: &Config) {
-    let mut pass = password.map(str::to_string);
+/// 网卡选择子菜单；Esc 返回 None
+async fn pick_interface(cfg: &Config, net: &Backend, ui: &Frontend) -> Result<Option<String>> {
+    let devices = net.devices().await?;
+    let current = net.interfaces().station();
+    let items: Vec<String> = devices
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            let selected = current.as_deref().map_or(i == 0, |c| c == d.name);
+            d.display_line(selected)
+        })
+        .collect();
+    let opts = SelectOpts::lines(items.len().min(cfg.max_lines));
+    let chosen = ui
+        .select(&items, t!(InterfacePrompt), opts)
+        .await
+        .and_then(Selection::row);
+    Ok(chosen.and_then(|i| devices.get(i)).map(|d| d.name.clone()))
+}
+
+/// BSS 展开菜单：外层 None = Esc 返回，Some(None) = 自动选择
+async fn pick_bss(ap: &AccessPoint, cfg: &Config, ui: &Frontend) -> Option<Option<String>> {
+    let mut items = vec![t!(BssAuto).to_string()];
+    items.extend(ap.bss.iter().map(|b| b.display_line()));
+    let prompt = format!("📡 {}", ap.ssid);
+    let opts = SelectOpts::lines(items.len().min(cfg.max_lines));
+    let idx = ui.select(&items, &prompt, opts).await?.row()?;
+    Some(idx.checked_sub(1).map(|i| ap.bss[i].bssid.clone()))
+}
+
+async fn do_connect_new(mut req: ConnectRequest, cfg: &Config, net: &Backend, ui: &Frontend) {
+    let ssid = req.ssid.clone();
 
     for attempt in 1..=cfg.max_retry {
         if attempt > 1 {
             notify::critical(
-                "密码错误",
-                &format!(
-                    "第 {} 次输入有误，请重试 ({attempt}/{})",
-                    attempt - 1,
-                    cfg.max_retry
+                t!(WrongPassword),
+                &t!(
+                    RetryBody,
+                    n = attempt - 1,
+                    attempt = attempt,
+                    max = cfg.max_retry
                 ),
             );
-            let hint = format!("第 {attempt} 次");
+            let hint = t!(RetryHint, n = attempt);
             // 重试密码框按 Esc → 放弃连接，回主菜单
-            match rofi::password_prompt(&hint, cfg).await {
-                Some(p) if !p.is_empty() => pass = Some(p),
+            match ui.password_prompt(&hint).await {
+                Some(p) if !p.is_empty() => req.password = Some(p),
                 _ => {
-                    notify::low("已取消", &format!("放弃连接 {ssid}"));
+                    notify::low(t!(Cancelled), &t!(GaveUp, ssid = ssid));
                     return;
                 }
             }
         }
 
-        notify::normal("连接中…", &format!("{ssid}（{attempt}/{}）", cfg.max_retry));
+        notify::normal(
+            t!(Connecting),
+            &t!(
+                ConnectingAttempt,
+                ssid = ssid,
+                attempt = attempt,
+                max = cfg.max_retry
+            ),
+        );
 
-        match nmcli::connect_new(ssid, pass.as_deref(), cfg).await {
+        match net.connect_new(&req, cfg.connect_timeout).await {
             ConnectResult::Success { ip } => {
-                handle_post_connect_with_ip(ssid, &ip, cfg).await;
+                handle_post_connect_with_ip(&ssid, &ip, cfg, net).await;
                 return;
             }
             ConnectResult::WrongPassword => {
                 if attempt == cfg.max_retry {
-                    notify::critical(
-                        "连接失败",
-                        &format!("已重试 {} 次，密码始终错误", cfg.max_retry),
-                    );
+                    notify::critical(t!(ConnectFailed), &t!(RetriesExhausted, n = cfg.max_retry));
                 }
             }
             ConnectResult::Timeout => {
-                notify::critical("连接超时", &format!("{ssid} 连接超时，请检查信号强度"));
+                notify::critical(t!(TitleTimeout), &t!(TimeoutBody, ssid = ssid));
+                return;
+            }
+            ConnectResult::NotFound => {
+                notify::critical(t!(ConnectFailed), &t!(NotNearby, ssid = ssid));
                 return;
             }
-            ConnectResult::Failed(msg) => {
-                notify::critical("连接失败", &msg);
+            ConnectResult::EapFailed(msg) | ConnectResult::Failed(msg) => {
+                notify::critical(t!(ConnectFailed), &msg);
                 return;
             }
         }
     }
 }
 
-async fn handle_post_connect(ssid: &str, cfg: &Config) {
-    let ip = nmcli::get_ip().await.unwrap_or_else(|| "未知".into());
-    handle_post_connect_with_ip(ssid, &ip, cfg).await;
+/// 802.1X 向导：依次询问 EAP 方法、内层认证、身份、证书与密码；任一步 Esc 返回 None
+async fn prompt_enterprise(ssid: &str, ui: &Frontend) -> Option<EnterpriseConfig> {
+    let methods: Vec<String> = EapMethod::ALL.iter().map(|m| m.to_string()).collect();
+    let prompt = t!(EapMethodPrompt, ssid = ssid);
+    let opts = SelectOpts {
+        no_custom: true,
+        ..SelectOpts::lines(methods.len())
+    };
+    let idx = ui.select(&methods, &prompt, opts).await?.row()?;
+    let eap = *EapMethod::ALL.get(idx)?;
+
+    let choices = eap.phase2_choices();
+    let phase2 = if choices.is_empty() {
+        None
+    } else {
+        let items: Vec<String> = choices.iter().map(|p| p.to_string()).collect();
+        let opts = SelectOpts {
+            no_custom: true,
+            ..SelectOpts::lines(items.len())
+        };
+        let idx = ui.select(&items, t!(Phase2Prompt), opts).await?.row()?;
+        choices.get(idx).copied()
+    };
+
+    let identity = ui.input_prompt(t!(IdentityPrompt)).await?;
+    if identity.trim().is_empty() {
+        notify::critical(t!(TitleError), t!(IdentityEmpty));
+        return None;
+    }
+    let anonymous_identity = optional_input(t!(AnonIdentityPrompt), ui).await?;
+    let ca_cert = optional_path(t!(CaCertPrompt), ui).await?;
+    let domain_match = optional_input(t!(DomainMatchPrompt), ui).await?;
+
+    let mut config = EnterpriseConfig {
+        eap,
+        phase2,
+        identity: identity.trim().to_string(),
+        anonymous_identity,
+        ca_cert,
+        domain_match,
+        password: None,
+        client_cert: None,
+        private_key: None,
+        private_key_password: None,
+    };
+    if eap == EapMethod::Tls {
+        let (Some(cert), Some(key)) = (
+            optional_path(t!(ClientCertPrompt), ui).await?,
+            optional_path(t!(PrivateKeyPrompt), ui).await?,
+        ) else {
+            notify::critical(t!(TitleError), t!(TlsNeedsCert));
+            return None;
+        };
+        config.client_cert = Some(cert);
+        config.private_key = Some(key);
+        config.private_key_password = ui
+            .password_prompt(t!(PrivateKeyHint))
+            .await
+            .filter(|p| !p.is_empty());
+    } else {
+        match ui.password_prompt("").await {
+            Some(p) if !p.is_empty() => config.password = Some(p),
+            _ => return None,
+        }
+    }
+    Some(config)
+}
+
+/// 可留空的输入框：外层 None = Esc，Some(None) = 留空
+async fn optional_input(prompt: &str, ui: &Frontend) -> Option<Option<String>> {
+    let s = ui.input_prompt(prompt).await?;
+    let s = s.trim();
+    Some((!s.is_empty()).then(|| s.to_string()))
 }
 
-async fn handle_post_connect_with_ip(ssid: &str, ip: &str, cfg: &Config) {
-    let (ok, ping_ms) = nmcli::ping_check(&cfg.ping_host, cfg.ping_count).await;
+/// 可留空的文件路径，展开 ~ 并转成绝对路径；文件不存在时提示并按 Esc 处理
+async fn optional_path(prompt: &str, ui: &Frontend) -> Option<Option<String>> {
+    let Some(raw) = optional_input(prompt, ui).await? else {
+        return Some(None);
+    };
+    let expanded = match raw.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()?.join(rest),
+        None => std::path::PathBuf::from(&raw),
+    };
+    match std::fs::canonicalize(&expanded) {
+        Ok(p) => Some(Some(p.to_string_lossy().into_owned())),
+        Err(e) => {
+            notify::critical(t!(FileUnavailable), &format!("{raw}: {e}"));
+            None
+        }
+    }
+}
+
+/// 802.1X 只尝试一次：重试无法补全证书等参数，失败原因直接通知
+async fn do_connect_enterprise(req: ConnectRequest, cfg: &Config, net: &Backend) {
+    let ssid = req.ssid.clone();
+    notify::normal(t!(Connecting), &t!(ConnectingEap, ssid = ssid));
+    match net.connect_new(&req, cfg.connect_timeout).await {
+        ConnectResult::Success { ip } => handle_post_connect_with_ip(&ssid, &ip, cfg, net).await,
+        ConnectResult::WrongPassword => notify::critical(t!(AuthFailed), t!(BadCredentials)),
+        ConnectResult::EapFailed(msg) => {
+            notify::critical(t!(EapFailed), &t!(EapFailedBody, msg = msg))
+        }
+        ConnectResult::Timeout => notify::critical(t!(TitleTimeout), &t!(EapTimeout, ssid = ssid)),
+        ConnectResult::NotFound => notify::critical(t!(ConnectFailed), &t!(NotNearby, ssid = ssid)),
+        ConnectResult::Failed(msg) => notify::critical(t!(ConnectFailed), &msg),
+    }
+}
+
+async fn handle_post_connect_with_ip(ssid: &str, ip: &str, cfg: &Config, net: &Backend) {
+    let (ok, ping_ms) = ping::ping_check(&cfg.ping_host, cfg.ping_count).await;
     let net_status = if ok {
-        ping_ms.map_or("✓ 网络畅通".into(), |ms| {
-            format!("✓ 网络畅通 ({:.0}ms)", ms)
+        ping_ms.map_or(t!(Online).into(), |ms| {
+            t!(OnlinePing, ms = format!("{:.0}", ms))
         })
     } else {
-        "⚠ 已连接但无法访问互联网".into()
+        t!(NoInternet).into()
     };
-    notify::normal("已连接 ✓", &format!("{ssid}\nIP: {ip}\n{net_status}"));
-    try_auto_vpn(ssid, cfg).await;
+    notify::normal(t!(Connected), &format!("{ssid}\nIP: {ip}\n{net_status}"));
+    try_auto_vpn(ssid, cfg, net).await;
 }
 
-async fn try_auto_vpn(ssid: &str, cfg: &Config) {
+async fn try_auto_vpn(ssid: &str, cfg: &Config, net: &Backend) {
     for (vpn, trigger) in &cfg.auto_vpn {
         if trigger == ssid {
-            notify::low("VPN", &format!("正在启动 {vpn}…"));
-            let ok = tokio::process::Command::new("nmcli")
-                .args(["connection", "up", vpn])
-                .status()
-                .await
-                .map(|s| s.success())
-                .unwrap_or(false);
-            if ok {
-                notify::normal("VPN 已连接", vpn)
-            } else {
-                notify::critical("VPN 失败", &format!("无法启动 {vpn}"))
+            notify::low("VPN", &t!(VpnStarting, vpn = vpn));
+            match net.activate_vpn(vpn).await {
+                Ok(_) => notify::normal(t!(VpnConnected), vpn),
+                Err(e) => notify::critical(t!(VpnFailed), &e.to_string()),
             }
         }
     }
@@ -529,43 +1178,241 @@ async fn try_auto_vpn(ssid: &str, cfg: &Config) {
 // 热点（内部所有 Esc 均静默返回，由调用方回到主菜单）
 // ════════════════════════════════════════════════════════════════
 
-async fn handle_hotspot(cfg: &Config) {
-    if let Some(active) = nmcli::hotspot_active().await {
-        if rofi::confirm("关闭热点？", cfg).await {
-            let _ = tokio::process::Command::new("nmcli")
-                .args(["connection", "down", &active])
-                .status()
-                .await;
-            notify::normal("热点已关闭", "");
+async fn handle_hotspot(net: &Backend, ui: &Frontend) {
+    if let Some(active) = net.hotspot_active().await {
+        if ui.confirm(t!(ConfirmHotspotOff)).await {
+            let _ = net.hotspot_down(&active).await;
+            notify::normal(t!(HotspotOff), "");
         }
         return;
     }
 
-    if let Some(profile) = nmcli::hotspot_profile().await {
-        let _ = tokio::process::Command::new("nmcli")
-            .args(["connection", "up", &profile])
-            .status()
-            .await;
-        notify::normal("热点已开启", &profile);
+    if let Some(profile) = net.hotspot_profile().await {
+        let _ = net.hotspot_up(&profile).await;
+        notify::normal(t!(HotspotOn), &profile);
         return;
     }
 
     // Esc 输入名称 → 静默返回主菜单
-    let hs_ssid = match rofi::input_prompt("📡 热点名称: ", cfg).await {
+    let hs_ssid = match ui.input_prompt(t!(HotspotNamePrompt)).await {
         Some(s) if !s.is_empty() => s,
         _ => return,
     };
     // Esc 输入密码 → 静默返回主菜单
-    let hs_pass = match rofi::password_prompt("热点密码（至少8位）", cfg).await {
+    let hs_pass = match ui.password_prompt(t!(HotspotPassPrompt)).await {
         Some(p) if !p.is_empty() => p,
         _ => return,
     };
     if hs_pass.len() < 8 {
-        notify::critical("错误", "密码至少需要 8 位");
+        notify::critical(t!(TitleError), t!(PasswordTooShort));
         return;
     }
-    match nmcli::create_hotspot(&hs_ssid, &hs_pass).await {
-        Ok(_) => notify::normal("热点已开启", &format!("SSID: {hs_ssid}")),
-        Err(e) => notify::critical("热点失败", &e.to_string()),
+    match net.create_hotspot(&hs_ssid, &hs_pass).await {
+        Ok(_) => notify::normal(t!(HotspotOn), &format!("SSID: {hs_ssid}")),
+        Err(e) => notify::critical(t!(HotspotFailed), &e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::fake::FakeBackend;
+    use backend::NetworkBackend;
+    use menu::fake::FakeMenu;
+    use types::{group_by_ssid, Bss, ScanEntry};
+
+    /// 一个 SSID 及其各个 BSS (BSSID, 信号)
+    fn ap(ssid: &str, security: Security, bss: &[(&str, u8)]) -> AccessPoint {
+        let entries = bss
+            .iter()
+            .map(|&(bssid, signal)| ScanEntry {
+                ssid: ssid.into(),
+                security: security.clone(),
+                bss: Bss::new(bssid.into(), 2437, signal, false),
+            })
+            .collect();
+        group_by_ssid(entries).remove(0)
+    }
+
+    /// 不弹通知、只 ping 本机一次的配置
+    fn test_config() -> Config {
+        notify::set_sink(Some(Box::new(|_, _, _| {})));
+        Config {
+            ping_host: "127.0.0.1".into(),
+            ping_count: 1,
+            ..Config::default()
+        }
+    }
+
+    async fn run(
+        action: MenuAction,
+        net: &std::sync::Arc<FakeBackend>,
+        ui: &std::sync::Arc<FakeMenu>,
+    ) -> Nav {
+        let cfg = test_config();
+        let (backend, frontend): (Backend, Frontend) = (net.clone(), ui.clone());
+        let curr = net.current_ssid().await;
+        let aps = net.list_access_points().await.unwrap();
+        let nav = handle_action(action, &cfg, &backend, &frontend, &curr, &aps)
+            .await
+            .unwrap();
+        assert!(ui.finished(), "unused answers, prompts: {:?}", ui.prompts());
+        nav
+    }
+
+    #[tokio::test]
+    async fn toggle_radio() {
+        let net = FakeBackend::new(vec![], &[]);
+        let ui = FakeMenu::new(vec![]);
+        let nav = run(MenuAction::ToggleRadio, &net, &ui).await;
+        assert!(matches!(nav, Nav::Refresh));
+        assert_eq!(net.calls(), ["set_radio false"]);
+        assert_eq!(net.radio_state().await, RadioState::Disabled);
+    }
+
+    #[tokio::test]
+    async fn saved_network_with_picked_bss() {
+        let home = ap(
+            "Home",
+            Security::wpa2_psk(),
+            &[("AA:00:00:00:00:01", 80), ("AA:00:00:00:00:02", 40)],
+        );
+        let net = FakeBackend::new(vec![home.clone()], &["Home"]);
+        // BSS 菜单第 0 行是“自动”，第 2 行是信号较弱的那个
+        let ui = FakeMenu::new(vec![Some(Selection::Row(2))]);
+        run(MenuAction::Connect(home), &net, &ui).await;
+        assert_eq!(net.calls(), ["connect_saved Home AA:00:00:00:00:02"]);
+    }
+
+    #[tokio::test]
+    async fn new_network_retries_wrong_password() {
+        let cafe = ap("Cafe", Security::wpa2_psk(), &[("AA:00:00:00:00:03", 60)]);
+        let net = FakeBackend::new(vec![cafe.clone()], &[]);
+        net.state.lock().unwrap().results = vec![ConnectResult::WrongPassword];
+        let ui = FakeMenu::new(vec![
+            Some(Selection::Custom("first".into())),
+            Some(Selection::Custom("second".into())),
+        ]);
+        run(MenuAction::Connect(cafe), &net, &ui).await;
+        assert_eq!(
+            net.calls(),
+            [
+                "connect_new Cafe first hidden=false",
+                "connect_new Cafe second hidden=false"
+            ]
+        );
+        assert_eq!(net.current_ssid().await.as_deref(), Some("Cafe"));
+    }
+
+    #[tokio::test]
+    async fn open_network_needs_confirmation() {
+        let free = ap("Free", Security::default(), &[("AA:00:00:00:00:04", 50)]);
+        let net = FakeBackend::new(vec![free.clone()], &[]);
+        // 确认框选“否”
+        let ui = FakeMenu::new(vec![Some(Selection::Row(1))]);
+        let nav = run(MenuAction::Connect(free.clone()), &net, &ui).await;
+        assert!(matches!(nav, Nav::Back));
+        assert!(net.calls().is_empty());
+
+        let ui = FakeMenu::new(vec![Some(Selection::Row(0))]);
+        run(MenuAction::Connect(free), &net, &ui).await;
+        assert_eq!(net.calls(), ["connect_new Free - hidden=false"]);
+    }
+
+    #[tokio::test]
+    async fn forget_picked_profile() {
+        let net = FakeBackend::new(vec![], &["Home", "Work"]);
+        let ui = FakeMenu::new(vec![Some(Selection::Row(1)), Some(Selection::Row(0))]);
+        run(MenuAction::Forget, &net, &ui).await;
+        assert_eq!(net.calls(), ["forget Work"]);
+        assert_eq!(net.saved_connections().await.unwrap(), ["Home"]);
+
+        // 确认框 Esc 不删除
+        let ui = FakeMenu::new(vec![Some(Selection::Row(0)), None]);
+        run(MenuAction::Forget, &net, &ui).await;
+        assert_eq!(net.calls(), ["forget Work"]);
+    }
+
+    #[tokio::test]
+    async fn manual_entry_of_unknown_ssid_is_hidden() {
+        let net = FakeBackend::new(vec![], &[]);
+        let ui = FakeMenu::new(vec![Some(Selection::Custom("Lab, secret pw".into()))]);
+        run(MenuAction::Manual, &net, &ui).await;
+        assert_eq!(net.calls(), ["connect_new Lab secret pw hidden=true"]);
+    }
+
+    /// 主菜单的行：两个功能行，之后是网络行
+    fn menu_rows(aps: &[AccessPoint]) -> Vec<MenuAction> {
+        let mut actions = vec![MenuAction::ToggleRadio, MenuAction::Refresh];
+        actions.extend(aps.iter().cloned().map(MenuAction::Connect));
+        actions
+    }
+
+    fn picked(action: &MenuAction) -> Option<&str> {
+        match action {
+            MenuAction::Connect(ap) => ap.bss.first().map(|b| b.bssid.as_str()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parse_action_header_rows() {
+        let aps = vec![ap(
+            "Home",
+            Security::wpa2_psk(),
+            &[("AA:00:00:00:00:01", 70)],
+        )];
+        let keys = [NetworkAction::Details, NetworkAction::Forget];
+        let parse = |choice| parse_action(choice, menu_rows(&aps), &keys, &aps);
+
+        assert!(matches!(parse(Selection::Row(0)), MenuAction::ToggleRadio));
+        assert!(matches!(parse(Selection::Row(1)), MenuAction::Refresh));
+        // 快捷键落在功能行或越界时只刷新
+        assert!(matches!(parse(Selection::Key(0, 0)), MenuAction::Refresh));
+        assert!(matches!(parse(Selection::Key(5, 2)), MenuAction::Refresh));
+        assert!(matches!(parse(Selection::Row(9)), MenuAction::Refresh));
+        assert!(matches!(
+            parse(Selection::Key(1, 2)),
+            MenuAction::OnNetwork(NetworkAction::Forget, ap) if ap.ssid == "Home"
+        ));
+    }
+
+    #[test]
+    fn parse_action_duplicate_ssids_by_row() {
+        // 扫描到的隐藏 AP 都是空 SSID，只能靠行号区分
+        let aps = vec![
+            ap("", Security::wpa2_psk(), &[("AA:00:00:00:00:01", 70)]),
+            ap("", Security::wpa2_psk(), &[("AA:00:00:00:00:02", 50)]),
+        ];
+        let parse = |choice| parse_action(choice, menu_rows(&aps), &[], &aps);
+        assert_eq!(picked(&parse(Selection::Row(2))), Some("AA:00:00:00:00:01"));
+        assert_eq!(picked(&parse(Selection::Row(3))), Some("AA:00:00:00:00:02"));
+        // 自由输入的空串不会选中隐藏 AP
+        assert!(matches!(
+            parse(Selection::Custom(" ".into())),
+            MenuAction::Refresh
+        ));
+    }
+
+    #[test]
+    fn parse_action_custom_input() {
+        let aps = vec![
+            ap("Home", Security::wpa2_psk(), &[("AA:00:00:00:00:01", 70)]),
+            ap(
+                "Home 5G",
+                Security::wpa2_psk(),
+                &[("AA:00:00:00:00:02", 60)],
+            ),
+            AccessPoint::saved_hidden("Lab".into()),
+        ];
+        let parse =
+            |text: &str| parse_action(Selection::Custom(text.into()), menu_rows(&aps), &[], &aps);
+        assert!(matches!(parse("  Home 5G "), MenuAction::Connect(ap) if ap.ssid == "Home 5G"));
+        assert!(matches!(parse("Home"), MenuAction::Connect(ap) if ap.ssid == "Home"));
+        // 已保存的隐藏网络没有 BSS，也能按名字选中
+        assert!(matches!(parse("Lab"), MenuAction::Connect(ap) if ap.hidden));
+        // 只接受整体匹配
+        assert!(matches!(parse("Hom"), MenuAction::Refresh));
+        assert!(matches!(parse("home"), MenuAction::Refresh));
     }
 }
@@ -0,0 +1,71 @@
+// src/markup.rs — Pango 标记：转义与着色，用于 rofi -markup-rows
+
+/// 转义 Pango 标记中的特殊字符，SSID 等任意文字都须先经过这里
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\'' => out.push_str("&apos;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 给文字加颜色 / 粗体；颜色为空且不加粗时只转义，空文字不输出标签
+pub fn span(text: &str, color: &str, bold: bool) -> String {
+    if text.is_empty() || (color.is_empty() && !bold) {
+        return escape(text);
+    }
+    let mut attrs = String::new();
+    if !color.is_empty() {
+        attrs += &format!(" foreground=\"{}\"", escape(color));
+    }
+    if bold {
+        attrs += " weight=\"bold\"";
+    }
+    format!("<span{attrs}>{}</span>", escape(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Colors, Glyphs, DEFAULT_ROW_FORMAT};
+    use crate::types::{group_by_ssid, Bss, ScanEntry, Security};
+
+    #[test]
+    fn escape_and_span() {
+        assert_eq!(
+            escape(r#"<b>Tom & "Jerry's"</b>"#),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&apos;s&quot;&lt;/b&gt;"
+        );
+        assert_eq!(span("a<b", "", false), "a&lt;b");
+        assert_eq!(span("", "#fff", true), "");
+        assert_eq!(
+            span("x&y", "\"red", true),
+            "<span foreground=\"&quot;red\" weight=\"bold\">x&amp;y</span>"
+        );
+    }
+
+    #[test]
+    fn ssid_is_escaped_in_markup_rows() {
+        // 不超过默认模板的 20 列，不会被截断
+        let ssid = r#"<i>Bo & "Al's"</i>"#;
+        let ap = group_by_ssid(vec![ScanEntry {
+            ssid: ssid.into(),
+            security: Security::wpa2_psk(),
+            bss: Bss::new("AA:00:00:00:00:01".into(), 2437, 70, false),
+        }])
+        .remove(0);
+        let colors = Colors::default();
+        for saved in [false, true] {
+            let row = ap.display_markup(DEFAULT_ROW_FORMAT, &Glyphs::default(), saved, &colors);
+            assert!(row.contains(&escape(ssid)), "{row}");
+            assert!(!row.contains("<i>") && !row.contains("& "), "{row}");
+        }
+    }
+}
@@ -0,0 +1,358 @@
+// src/menu.rs — 菜单前端抽象：rofi / wofi / fuzzel / bemenu / tofi / dmenu / 终端
+
+use crate::bemenu::Bemenu;
+use crate::config::{Config, MenuKind};
+use crate::dmenu::Dmenu;
+use crate::fuzzel::Fuzzel;
+use crate::i18n::t;
+use crate::notify;
+use crate::rofi::Rofi;
+use crate::template;
+use crate::tofi::Tofi;
+use crate::wofi::Wofi;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// 菜单用途，rofi 按此选用 [menus.*] 中的外观覆盖
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Role {
+    /// 主菜单及其它列表
+    #[default]
+    Main,
+    Password,
+    Confirm,
+    Info,
+    Qr,
+}
+
+/// 列表选择的可选参数；前端不支持的项直接忽略
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SelectOpts<'a> {
+    /// 显示行数
+    pub lines: Option<usize>,
+    /// 列表上方的说明文字
+    pub message: Option<&'a str>,
+    /// 默认高亮行（0-indexed）
+    pub active: Option<usize>,
+    /// 标记为紧急（醒目样式）的行
+    pub urgent: &'a [usize],
+    /// 窗口宽度（字符数）
+    pub width: Option<usize>,
+    /// 只能从列表中选，不接受自由输入
+    pub no_custom: bool,
+    /// 主菜单；script 模式下子菜单会追加“返回”行
+    pub root: bool,
+    /// 无操作超过这么久就返回 Expired 以便重新加载（仅 TUI 支持）
+    pub refresh: Option<Duration>,
+    /// 自定义快捷键（rofi 按键语法），按下时返回 Key
+    pub keys: &'a [String],
+    /// 行文字是 Pango 标记（仅在 supports_markup 时传入）
+    pub markup: bool,
+    /// 菜单用途
+    pub role: Role,
+}
+
+impl SelectOpts<'_> {
+    pub fn lines(n: usize) -> Self {
+        Self {
+            lines: Some(n),
+            ..Default::default()
+        }
+    }
+}
+
+/// 一次选择的结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selection {
+    /// 列表中的第几行（0-indexed）
+    Row(usize),
+    /// 列表外的自由输入
+    Custom(String),
+    /// 等待超时，调用方应重新加载列表
+    Expired,
+    /// 在某行上按了第几个自定义快捷键：(快捷键下标, 行)
+    Key(usize, usize),
+}
+
+impl Selection {
+    /// 只认列表行，自由输入视为未选
+    pub fn row(self) -> Option<usize> {
+        match self {
+            Selection::Row(i) | Selection::Key(_, i) => Some(i),
+            Selection::Custom(_) | Selection::Expired => None,
+        }
+    }
+
+    /// 选中行的文字或输入的文字
+    pub fn text(self, items: &[String]) -> String {
+        match self {
+            Selection::Row(i) | Selection::Key(_, i) => items.get(i).cloned().unwrap_or_default(),
+            Selection::Custom(s) => s,
+            Selection::Expired => String::new(),
+        }
+    }
+}
+
+/// 菜单前端：各实现把选择 / 输入 / 确认 / 展示映射到对应工具支持的参数
+#[async_trait]
+pub trait Menu: Send + Sync {
+    /// 列表选择，返回选中行的下标或自由输入，Esc 返回 None
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection>;
+
+    /// 隐藏输入的单行文本
+    async fn password(&self, prompt: &str) -> Option<String>;
+
+    /// 是否支持 SelectOpts::keys
+    fn supports_keys(&self) -> bool {
+        false
+    }
+
+    /// 是否支持 SelectOpts::markup
+    fn supports_markup(&self) -> bool {
+        false
+    }
+
+    /// 单行密码输入
+    async fn password_prompt(&self, hint: &str) -> Option<String> {
+        let prompt = if hint.is_empty() {
+            t!(PasswordPrompt).to_string()
+        } else {
+            t!(PasswordPromptHint, hint = hint)
+        };
+        self.password(&prompt).await
+    }
+
+    /// 单行文本输入
+    async fn input_prompt(&self, prompt: &str) -> Option<String> {
+        let choice = self.select(&[], prompt, SelectOpts::lines(1)).await?;
+        Some(choice.text(&[]))
+    }
+
+    /// 二选一确认（返回 true = 确认）
+    async fn confirm(&self, message: &str) -> bool {
+        let items = vec![t!(Yes).to_string(), t!(No).to_string()];
+        let opts = SelectOpts {
+            no_custom: true,
+            role: Role::Confirm,
+            ..SelectOpts::lines(2)
+        };
+        self.select(&items, message, opts).await == Some(Selection::Row(0))
+    }
+
+    /// 只读展示多行文字
+    async fn show_info(&self, title: &str, content: &str) {
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let opts = SelectOpts {
+            lines: Some(lines.len()),
+            message: Some(t!(EscToClose)),
+            no_custom: true,
+            role: Role::Info,
+            ..Default::default()
+        };
+        let _ = self.select(&lines, title, opts).await;
+    }
+
+    /// 展示 UTF-8 二维码及其下方的动作，返回所选动作的下标；
+    /// 没有消息区的前端把二维码逐行当作列表项
+    async fn show_qr(&self, ssid: &str, qr_text: &str, actions: &[String]) -> Option<usize> {
+        let mut lines: Vec<String> = qr_text.lines().map(str::to_string).collect();
+        let width = lines.iter().map(|l| template::width(l)).max().unwrap_or(40);
+        let offset = lines.len();
+        lines.extend(actions.iter().cloned());
+        lines.push(t!(CloseRow).to_string());
+        let opts = SelectOpts {
+            lines: Some(lines.len()),
+            width: Some(width + 4),
+            no_custom: true,
+            role: Role::Qr,
+            ..Default::default()
+        };
+        match self.select(&lines, &format!("📷 {ssid}"), opts).await? {
+            Selection::Row(i) => i.checked_sub(offset).filter(|&i| i < actions.len()),
+            _ => None,
+        }
+    }
+}
+
+/// 运行时使用的菜单前端
+pub type Frontend = Arc<dyn Menu>;
+
+/// 按配置构建前端；auto 时按显示服务器在 PATH 中挑第一个可用的
+pub fn from_config(cfg: &Config) -> Frontend {
+    let kind = match cfg.menu {
+        MenuKind::Auto => detect(),
+        kind => kind,
+    };
+    let cfg = cfg.clone();
+    match kind {
+        MenuKind::Auto | MenuKind::Rofi => Arc::new(Rofi::new(cfg)),
+        MenuKind::Wofi => Arc::new(Wofi::new(cfg)),
+        MenuKind::Fuzzel => Arc::new(Fuzzel::new(cfg)),
+        MenuKind::Bemenu => Arc::new(Bemenu::new(cfg)),
+        MenuKind::Tofi => Arc::new(Tofi::new(cfg)),
+        MenuKind::Dmenu => Arc::new(Dmenu::new(cfg)),
+    }
+}
+
+/// Wayland 下优先原生启动器，X11 下优先 rofi；都找不到时回落到 rofi
+fn detect() -> MenuKind {
+    let candidates: &[(&str, MenuKind)] = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[
+            ("fuzzel", MenuKind::Fuzzel),
+            ("wofi", MenuKind::Wofi),
+            ("tofi", MenuKind::Tofi),
+            ("bemenu", MenuKind::Bemenu),
+            ("rofi", MenuKind::Rofi),
+        ]
+    } else {
+        &[
+            ("rofi", MenuKind::Rofi),
+            ("dmenu", MenuKind::Dmenu),
+            ("bemenu", MenuKind::Bemenu),
+        ]
+    };
+    candidates
+        .iter()
+        .find(|(bin, _)| in_path(bin))
+        .map_or(MenuKind::Rofi, |(_, kind)| *kind)
+}
+
+fn in_path(bin: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(bin).is_file()))
+}
+
+/// 启动 dmenu 风格的程序：候选项写入 stdin，返回 stdout，Esc / 空输出返回 None
+pub async fn run(program: &str, args: &[String], items: &[String]) -> Option<String> {
+    match run_status(program, args, items).await? {
+        (0, s) if !s.is_empty() => Some(s),
+        _ => None,
+    }
+}
+
+/// 同 run，但保留退出码（rofi 的自定义快捷键以 10+ 退出），Esc 等无输出时 stdout 为空
+pub async fn run_status(program: &str, args: &[String], items: &[String]) -> Option<(i32, String)> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            let msg = format!("{}: {e}", t!(CannotStart, name = program));
+            notify::critical(t!(TitleMenu), &msg)
+        })
+        .ok()?;
+
+    // 写完后必须 drop/关闭 stdin，否则菜单会一直等待更多输入而不显示界面
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(items.join("\n").as_bytes()).await;
+    }
+
+    let out = child.wait_with_output().await.ok()?;
+    // 被信号终止时没有退出码，按 Esc 处理
+    let code = out.status.code()?;
+    Some((
+        code,
+        String::from_utf8_lossy(&out.stdout).trim().to_string(),
+    ))
+}
+
+/// 不能直接输出下标的前端回显整行文字，按整行映射回下标；找不到则是自由输入
+pub fn match_row(items: &[String], text: String) -> Selection {
+    match items.iter().position(|i| i.trim() == text) {
+        Some(i) => Selection::Row(i),
+        None => Selection::Custom(text),
+    }
+}
+
+/// rofi -location 编号（0–8）对应的锚点名，fuzzel / tofi 通用
+pub fn anchor(position: u8) -> &'static str {
+    match position {
+        1 => "top-left",
+        2 => "top",
+        3 => "top-right",
+        4 => "right",
+        5 => "bottom-right",
+        6 => "bottom",
+        7 => "bottom-left",
+        8 => "left",
+        _ => "center",
+    }
+}
+
+/// "DejaVu Sans Mono 8" → ("DejaVu Sans Mono", Some(8))
+pub fn split_font(font: &str) -> (&str, Option<u32>) {
+    match font.rsplit_once(' ') {
+        Some((family, size)) => match size.parse() {
+            Ok(size) => (family, Some(size)),
+            Err(_) => (font, None),
+        },
+        None => (font, None),
+    }
+}
+
+/// 没有消息区的前端把说明文字拼到提示符后
+pub fn prompt_with(prompt: &str, message: Option<&str>) -> String {
+    match message {
+        Some(msg) => format!("{} — {msg} ", prompt.trim_end_matches([' ', ':'])),
+        None => prompt.to_string(),
+    }
+}
+
+/// 按预设顺序作答的假前端，供菜单逻辑测试；记录每次弹出的提示
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    pub struct FakeMenu {
+        answers: Mutex<VecDeque<Option<Selection>>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl FakeMenu {
+        /// answers 依次作为每次 select 的结果，用完后视为按了 Esc
+        pub fn new(answers: Vec<Option<Selection>>) -> Arc<Self> {
+            Arc::new(Self {
+                answers: Mutex::new(answers.into()),
+                prompts: Mutex::new(vec![]),
+            })
+        }
+
+        pub fn prompts(&self) -> Vec<String> {
+            self.prompts.lock().unwrap().clone()
+        }
+
+        /// 预设的回答是否都用掉了
+        pub fn finished(&self) -> bool {
+            self.answers.lock().unwrap().is_empty()
+        }
+    }
+
+    #[async_trait]
+    impl Menu for FakeMenu {
+        async fn select(
+            &self,
+            _items: &[String],
+            prompt: &str,
+            _opts: SelectOpts<'_>,
+        ) -> Option<Selection> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            self.answers.lock().unwrap().pop_front().flatten()
+        }
+
+        async fn password(&self, prompt: &str) -> Option<String> {
+            let choice = self.select(&[], prompt, SelectOpts::default()).await?;
+            Some(choice.text(&[]))
+        }
+    }
+}
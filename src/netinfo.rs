@@ -0,0 +1,65 @@
+// src/netinfo.rs — 从内核 / 系统文件读取 IP、网关、DNS（供不自带 IP 管理的后端使用）
+
+use std::ffi::CStr;
+use std::net::Ipv4Addr;
+
+/// 接口上的第一个 IPv4 地址，格式 addr/prefix
+pub fn ipv4_address(ifname: &str) -> Option<String> {
+    let mut addrs: *mut libc::ifaddrs = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut addrs) } != 0 {
+        return None;
+    }
+
+    let mut found = None;
+    let mut cur = addrs;
+    while !cur.is_null() {
+        let ifa = unsafe { &*cur };
+        cur = ifa.ifa_next;
+        if ifa.ifa_addr.is_null() {
+            continue;
+        }
+        let name = unsafe { CStr::from_ptr(ifa.ifa_name) }.to_string_lossy();
+        let family = unsafe { (*ifa.ifa_addr).sa_family } as i32;
+        if name != ifname || family != libc::AF_INET {
+            continue;
+        }
+        let addr = unsafe { *(ifa.ifa_addr as *const libc::sockaddr_in) };
+        let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+        let prefix = if ifa.ifa_netmask.is_null() {
+            32
+        } else {
+            let mask = unsafe { *(ifa.ifa_netmask as *const libc::sockaddr_in) };
+            u32::from_be(mask.sin_addr.s_addr).count_ones()
+        };
+        found = Some(format!("{ip}/{prefix}"));
+        break;
+    }
+
+    unsafe { libc::freeifaddrs(addrs) };
+    found
+}
+
+/// 接口的默认网关（读 /proc/net/route）
+pub fn default_gateway(ifname: &str) -> Option<String> {
+    let text = std::fs::read_to_string("/proc/net/route").ok()?;
+    // Iface  Destination  Gateway  Flags ...；数值是按本机字节序打印的十六进制
+    text.lines().skip(1).find_map(|l| {
+        let cols: Vec<&str> = l.split_whitespace().collect();
+        if cols.len() < 3 || cols[0] != ifname || cols[1] != "00000000" {
+            return None;
+        }
+        let raw = u32::from_str_radix(cols[2], 16).ok()?;
+        Some(Ipv4Addr::from(raw.to_ne_bytes()).to_string())
+    })
+}
+
+/// /etc/resolv.conf 中的 nameserver 列表
+pub fn dns_servers() -> Vec<String> {
+    std::fs::read_to_string("/etc/resolv.conf")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("nameserver"))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
@@ -0,0 +1,1520 @@
+// src/nm_dbus.rs — 直接通过 D-Bus 访问 NetworkManager，不启动任何 nmcli 子进程
+
+use crate::backend::{Interfaces, NetworkBackend};
+use crate::i18n::t;
+use crate::ping;
+use crate::types::{
+    group_by_ssid, AccessPoint, ApMode, Bss, ConnectRequest, ConnectResult, ConnectionDetails,
+    EnterpriseConfig, RadioState, ScanEntry, Security, WifiDevice,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+use zbus::{Connection, Proxy};
+
+const NM: &str = "org.freedesktop.NetworkManager";
+const NM_PATH: &str = "/org/freedesktop/NetworkManager";
+const SETTINGS_PATH: &str = "/org/freedesktop/NetworkManager/Settings";
+
+const IFACE_PROPS: &str = "org.freedesktop.DBus.Properties";
+const IFACE_DEVICE: &str = "org.freedesktop.NetworkManager.Device";
+const IFACE_WIRELESS: &str = "org.freedesktop.NetworkManager.Device.Wireless";
+const IFACE_AP: &str = "org.freedesktop.NetworkManager.AccessPoint";
+const IFACE_SETTINGS: &str = "org.freedesktop.NetworkManager.Settings";
+const IFACE_CONNECTION: &str = "org.freedesktop.NetworkManager.Settings.Connection";
+const IFACE_ACTIVE: &str = "org.freedesktop.NetworkManager.Connection.Active";
+const IFACE_IP4: &str = "org.freedesktop.NetworkManager.IP4Config";
+
+/// NMDeviceType::WIFI
+const DEVICE_TYPE_WIFI: u32 = 2;
+
+// NMActiveConnectionState
+const ACTIVE_ACTIVATED: u32 = 2;
+const ACTIVE_DEACTIVATED: u32 = 4;
+
+// NMDeviceStateReason 中与密码 / 超时相关的几项
+const REASON_NO_SECRETS: u32 = 7;
+const REASON_SUPPLICANT_DISCONNECT: u32 = 8;
+const REASON_SUPPLICANT_CONFIG_FAILED: u32 = 9;
+const REASON_SUPPLICANT_FAILED: u32 = 10;
+const REASON_SUPPLICANT_TIMEOUT: u32 = 11;
+const REASON_SSID_NOT_FOUND: u32 = 53;
+
+// NM80211Mode
+const NM_802_11_MODE_ADHOC: u32 = 1;
+const NM_802_11_MODE_INFRA: u32 = 2;
+const NM_802_11_MODE_MESH: u32 = 4;
+
+// NM80211ApFlags
+const AP_FLAGS_PRIVACY: u32 = 0x1;
+
+// NMWepKeyType
+const WEP_KEY_TYPE_KEY: u32 = 1;
+const WEP_KEY_TYPE_PASSPHRASE: u32 = 2;
+
+/// GetSettings / GetSecrets 返回的 a{sa{sv}}
+type Settings = HashMap<String, HashMap<String, OwnedValue>>;
+
+/// 通过系统总线直接调用 NetworkManager
+pub struct NmDbus {
+    conn: OnceCell<Connection>,
+    ifaces: Interfaces,
+}
+
+/// 激活等待的结果
+enum Activation {
+    Done,
+    Failed(u32),
+    TimedOut,
+}
+
+impl NmDbus {
+    pub fn new(ifaces: Interfaces) -> Self {
+        Self {
+            conn: OnceCell::new(),
+            ifaces,
+        }
+    }
+
+    /// 懒连接系统总线，整个进程只建一次
+    async fn conn(&self) -> Result<&Connection> {
+        let conn = self
+            .conn
+            .get_or_try_init(|| async { Connection::system().await })
+            .await?;
+        Ok(conn)
+    }
+
+    async fn proxy(&self, path: &str, iface: &'static str) -> Result<Proxy<'static>> {
+        let proxy = Proxy::new(self.conn().await?, NM, path.to_string(), iface).await?;
+        Ok(proxy)
+    }
+
+    /// 一次性读取对象某个接口的全部属性
+    async fn get_all(&self, path: &str, iface: &str) -> Result<HashMap<String, OwnedValue>> {
+        let props = self.proxy(path, IFACE_PROPS).await?;
+        Ok(props.call("GetAll", &(iface,)).await?)
+    }
+
+    /// 所有 Wi-Fi 设备及其接口名
+    async fn wifi_devices(&self) -> Result<Vec<(OwnedObjectPath, String)>> {
+        let nm = self.proxy(NM_PATH, NM).await?;
+        let devices: Vec<OwnedObjectPath> = nm.call("GetDevices", &()).await?;
+        let mut found = Vec::new();
+        for dev in devices {
+            let props = self.get_all(dev.as_str(), IFACE_DEVICE).await?;
+            if prop::<u32>(&props, "DeviceType") == Some(DEVICE_TYPE_WIFI) {
+                found.push((dev, prop(&props, "Interface").unwrap_or_default()));
+            }
+        }
+        Ok(found)
+    }
+
+    /// 按接口名找设备；None 取第一个 Wi-Fi 设备
+    async fn device_named(&self, ifname: Option<String>) -> Result<OwnedObjectPath> {
+        let devices = self.wifi_devices().await?;
+        match ifname {
+            Some(name) => devices
+                .into_iter()
+                .find(|(_, n)| *n == name)
+                .map(|(d, _)| d)
+                .ok_or_else(|| anyhow!(t!(NoWifiDeviceNamed, name = name))),
+            None => devices
+                .into_iter()
+                .next()
+                .map(|(d, _)| d)
+                .ok_or_else(|| anyhow!(t!(NoWifiDevice))),
+        }
+    }
+
+    /// 当前选中的 Wi-Fi 设备
+    async fn wifi_device(&self) -> Result<OwnedObjectPath> {
+        self.device_named(self.ifaces.station()).await
+    }
+
+    /// 热点使用的 Wi-Fi 设备
+    async fn hotspot_device(&self) -> Result<OwnedObjectPath> {
+        self.device_named(self.ifaces.hotspot()).await
+    }
+
+    /// 设备当前关联的 AP 路径（未连接时为 "/"）
+    async fn active_ap(&self, device: &OwnedObjectPath) -> Result<OwnedObjectPath> {
+        let wireless = self.proxy(device.as_str(), IFACE_WIRELESS).await?;
+        Ok(wireless.get_property("ActiveAccessPoint").await?)
+    }
+
+    async fn read_ap(&self, path: &OwnedObjectPath, in_use: bool) -> Result<ScanEntry> {
+        let props = self.get_all(path.as_str(), IFACE_AP).await?;
+        let ssid_bytes: Vec<u8> = prop(&props, "Ssid").unwrap_or_default();
+        let signal: u8 = prop(&props, "Strength").unwrap_or(0);
+        let security = ap_security(&props);
+        let mut bss = Bss::new(
+            prop(&props, "HwAddress").unwrap_or_default(),
+            prop(&props, "Frequency").unwrap_or(0),
+            signal,
+            in_use,
+        );
+        // MaxBitrate 单位是 kbit/s
+        bss.max_rate = prop::<u32>(&props, "MaxBitrate").unwrap_or(0) / 1000;
+        bss.mode = match prop::<u32>(&props, "Mode").unwrap_or(0) {
+            NM_802_11_MODE_ADHOC => ApMode::AdHoc,
+            NM_802_11_MODE_INFRA => ApMode::Infra,
+            NM_802_11_MODE_MESH => ApMode::Mesh,
+            _ => ApMode::Unknown,
+        };
+        Ok(ScanEntry {
+            ssid: String::from_utf8_lossy(&ssid_bytes).to_string(),
+            security,
+            bss,
+        })
+    }
+
+    /// 设备扫描结果中该 SSID 的加密方式；隐藏网络等扫不到时为 None
+    async fn scanned_security(&self, device: &OwnedObjectPath, ssid: &str) -> Option<Security> {
+        let wireless = self.proxy(device.as_str(), IFACE_WIRELESS).await.ok()?;
+        let paths: Vec<OwnedObjectPath> = wireless.call("GetAllAccessPoints", &()).await.ok()?;
+        for path in &paths {
+            if let Ok(entry) = self.read_ap(path, false).await {
+                if entry.ssid == ssid {
+                    return Some(entry.security);
+                }
+            }
+        }
+        None
+    }
+
+    /// 按 BSSID 查找设备上扫描到的 AP 对象
+    async fn find_ap(&self, device: &OwnedObjectPath, bssid: &str) -> Result<OwnedObjectPath> {
+        let wireless = self.proxy(device.as_str(), IFACE_WIRELESS).await?;
+        let paths: Vec<OwnedObjectPath> = wireless.call("GetAllAccessPoints", &()).await?;
+        for path in paths {
+            let ap = self.proxy(path.as_str(), IFACE_AP).await?;
+            let hw: String = ap.get_property("HwAddress").await.unwrap_or_default();
+            if hw.eq_ignore_ascii_case(bssid) {
+                return Ok(path);
+            }
+        }
+        Err(anyhow!(t!(BssidNotNearby, bssid = bssid)))
+    }
+
+    /// 所有已保存连接：(路径, 设置)
+    async fn saved_settings(&self) -> Result<Vec<(OwnedObjectPath, Settings)>> {
+        let settings = self.proxy(SETTINGS_PATH, IFACE_SETTINGS).await?;
+        let paths: Vec<OwnedObjectPath> = settings.call("ListConnections", &()).await?;
+        let mut out = Vec::with_capacity(paths.len());
+        for path in paths {
+            let conn = self.proxy(path.as_str(), IFACE_CONNECTION).await?;
+            if let Ok(s) = conn.call::<_, _, Settings>("GetSettings", &()).await {
+                out.push((path, s));
+            }
+        }
+        Ok(out)
+    }
+
+    /// 按名称（connection.id）查找已保存的 Wi-Fi 连接
+    async fn find_connection(&self, name: &str) -> Result<OwnedObjectPath> {
+        self.saved_settings()
+            .await?
+            .into_iter()
+            .find(|(_, s)| setting_str(s, "connection", "id").as_deref() == Some(name))
+            .map(|(p, _)| p)
+            .ok_or_else(|| anyhow!(t!(ProfileNotFound, name = name)))
+    }
+
+    /// 按名称查找活跃连接
+    async fn find_active(&self, name: &str) -> Result<OwnedObjectPath> {
+        let nm = self.proxy(NM_PATH, NM).await?;
+        let actives: Vec<OwnedObjectPath> = nm.get_property("ActiveConnections").await?;
+        for path in actives {
+            let id: String = self
+                .proxy(path.as_str(), IFACE_ACTIVE)
+                .await?
+                .get_property("Id")
+                .await?;
+            if id == name {
+                return Ok(path);
+            }
+        }
+        Err(anyhow!(t!(NotActive, name = name)))
+    }
+
+    /// 激活已保存的连接，返回活跃连接路径
+    /// `specific` 为 AP 对象路径时只连接该 BSSID，"/" 表示由 NM 自选
+    async fn activate(
+        &self,
+        conn: &OwnedObjectPath,
+        device: &str,
+        specific: &str,
+    ) -> Result<OwnedObjectPath> {
+        let nm = self.proxy(NM_PATH, NM).await?;
+        let device = ObjectPath::try_from(device)?;
+        let specific = ObjectPath::try_from(specific)?;
+        Ok(nm
+            .call("ActivateConnection", &(conn, device, specific))
+            .await?)
+    }
+
+    /// 新建连接并激活，返回 (连接路径, 活跃连接路径)
+    async fn add_and_activate(
+        &self,
+        settings: HashMap<&str, HashMap<&str, Value<'_>>>,
+        device: &OwnedObjectPath,
+    ) -> Result<(OwnedObjectPath, OwnedObjectPath)> {
+        let nm = self.proxy(NM_PATH, NM).await?;
+        let root = ObjectPath::try_from("/")?;
+        Ok(nm
+            .call("AddAndActivateConnection", &(settings, device, root))
+            .await?)
+    }
+
+    /// 轮询活跃连接状态，直到激活成功、失败或超时
+    async fn wait_activated(
+        &self,
+        active: &OwnedObjectPath,
+        device: &OwnedObjectPath,
+        timeout: u64,
+    ) -> Activation {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout);
+        loop {
+            let state = match self.proxy(active.as_str(), IFACE_ACTIVE).await {
+                Ok(p) => p.get_property::<u32>("State").await.ok(),
+                Err(_) => None,
+            };
+            match state {
+                Some(ACTIVE_ACTIVATED) => return Activation::Done,
+                // 激活失败后对象可能直接消失，读不到状态同样视为失败
+                Some(ACTIVE_DEACTIVATED) | None => {
+                    return Activation::Failed(self.state_reason(device).await)
+                }
+                Some(_) => {}
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Activation::TimedOut;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+
+    /// 设备最近一次状态变化的原因码
+    async fn state_reason(&self, device: &OwnedObjectPath) -> u32 {
+        let reason: Option<(u32, u32)> = match self.proxy(device.as_str(), IFACE_DEVICE).await {
+            Ok(p) => p.get_property("StateReason").await.ok(),
+            Err(_) => None,
+        };
+        reason.map(|(_, r)| r).unwrap_or(0)
+    }
+
+    /// 激活已保存的连接，返回 (连接路径, 设备路径, 活跃连接路径)
+    async fn activate_saved(
+        &self,
+        name: &str,
+        bssid: Option<&str>,
+    ) -> Result<(OwnedObjectPath, OwnedObjectPath, OwnedObjectPath)> {
+        let conn = self.find_connection(name).await?;
+        let device = self.wifi_device().await?;
+        let specific = match bssid {
+            Some(b) => self.find_ap(&device, b).await?,
+            None => OwnedObjectPath::try_from("/")?,
+        };
+        let active = self
+            .activate(&conn, device.as_str(), specific.as_str())
+            .await?;
+        Ok((conn, device, active))
+    }
+
+    /// 把激活等待的结果翻译成 ConnectResult
+    async fn activation_result(&self, outcome: Activation, enterprise: bool) -> ConnectResult {
+        match outcome {
+            Activation::Done => {
+                let ip = self.get_ip().await.unwrap_or_else(|| t!(Unknown).into());
+                ConnectResult::Success { ip }
+            }
+            // 802.1X 下 supplicant 断开多半是服务器拒绝或证书不匹配
+            Activation::Failed(
+                code @ (REASON_SUPPLICANT_DISCONNECT
+                | REASON_SUPPLICANT_CONFIG_FAILED
+                | REASON_SUPPLICANT_FAILED),
+            ) if enterprise => ConnectResult::EapFailed(t!(EapRejected, code = code)),
+            Activation::Failed(REASON_NO_SECRETS | REASON_SUPPLICANT_DISCONNECT) => {
+                ConnectResult::WrongPassword
+            }
+            Activation::Failed(REASON_SUPPLICANT_TIMEOUT) | Activation::TimedOut => {
+                ConnectResult::Timeout
+            }
+            Activation::Failed(REASON_SSID_NOT_FOUND) => ConnectResult::NotFound,
+            Activation::Failed(code) => ConnectResult::Failed(t!(NmActivationFailed, code = code)),
+        }
+    }
+
+    /// 已保存的连接是否为 802.1X
+    async fn is_enterprise(&self, conn: &OwnedObjectPath) -> bool {
+        let settings: Option<Settings> = match self.proxy(conn.as_str(), IFACE_CONNECTION).await {
+            Ok(p) => p.call("GetSettings", &()).await.ok(),
+            Err(_) => None,
+        };
+        settings.is_some_and(|s| {
+            setting_str(&s, "802-11-wireless-security", "key-mgmt").as_deref() == Some("wpa-eap")
+        })
+    }
+
+    async fn delete_path(&self, path: &OwnedObjectPath) -> Result<()> {
+        let conn = self.proxy(path.as_str(), IFACE_CONNECTION).await?;
+        conn.call::<_, _, ()>("Delete", &()).await?;
+        Ok(())
+    }
+
+    /// 设备当前的 IP4Config 对象属性
+    async fn ip4_config(&self) -> Result<HashMap<String, OwnedValue>> {
+        let device = self.wifi_device().await?;
+        let path: OwnedObjectPath = self
+            .proxy(device.as_str(), IFACE_DEVICE)
+            .await?
+            .get_property("Ip4Config")
+            .await?;
+        if path.as_str() == "/" {
+            return Err(anyhow!(t!(NoIpv4)));
+        }
+        self.get_all(path.as_str(), IFACE_IP4).await
+    }
+
+    /// 热点即 802-11-wireless.mode = ap 的连接
+    async fn hotspot_names(&self) -> Result<Vec<String>> {
+        Ok(self
+            .saved_settings()
+            .await?
+            .iter()
+            .filter(|(_, s)| setting_str(s, "802-11-wireless", "mode").as_deref() == Some("ap"))
+            .filter_map(|(_, s)| setting_str(s, "connection", "id"))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl NetworkBackend for NmDbus {
+    fn name(&self) -> &'static str {
+        "nm-dbus"
+    }
+
+    // ── 查询 ─────────────────────────────────────────────────
+
+    async fn rescan(&self) {
+        if let Ok(device) = self.wifi_device().await {
+            if let Ok(wireless) = self.proxy(device.as_str(), IFACE_WIRELESS).await {
+                let opts: HashMap<&str, Value> = HashMap::new();
+                let _ = wireless.call::<_, _, ()>("RequestScan", &(opts,)).await;
+            }
+        }
+    }
+
+    async fn list_access_points(&self) -> Result<Vec<AccessPoint>> {
+        let device = self.wifi_device().await?;
+        let active = self.active_ap(&device).await?;
+        let wireless = self.proxy(device.as_str(), IFACE_WIRELESS).await?;
+        let paths: Vec<OwnedObjectPath> = wireless.call("GetAllAccessPoints", &()).await?;
+
+        let mut entries = Vec::with_capacity(paths.len());
+        for path in &paths {
+            // AP 可能在读取途中消失，跳过即可
+            if let Ok(entry) = self.read_ap(path, path == &active).await {
+                entries.push(entry);
+            }
+        }
+
+        // 与 nmcli 后端一致：同 SSID 归组，当前连接置顶，信号降序
+        Ok(group_by_ssid(entries))
+    }
+
+    async fn current_ssid(&self) -> Option<String> {
+        let device = self.wifi_device().await.ok()?;
+        let active = self.active_ap(&device).await.ok()?;
+        if active.as_str() == "/" {
+            return None;
+        }
+        self.read_ap(&active, true).await.ok().map(|e| e.ssid)
+    }
+
+    async fn current_link(&self) -> Option<(String, u8)> {
+        let device = self.wifi_device().await.ok()?;
+        let active = self.active_ap(&device).await.ok()?;
+        if active.as_str() == "/" {
+            return None;
+        }
+        let entry = self.read_ap(&active, true).await.ok()?;
+        Some((entry.ssid, entry.bss.signal))
+    }
+
+    async fn saved_connections(&self) -> Result<Vec<String>> {
+        Ok(self
+            .saved_settings()
+            .await?
+            .iter()
+            .filter(|(_, s)| {
+                setting_str(s, "connection", "type").as_deref() == Some("802-11-wireless")
+            })
+            .filter_map(|(_, s)| setting_str(s, "connection", "id"))
+            .collect())
+    }
+
+    async fn saved_hidden(&self) -> Result<Vec<String>> {
+        Ok(self
+            .saved_settings()
+            .await?
+            .iter()
+            .filter(|(_, s)| {
+                s.get("802-11-wireless")
+                    .and_then(|w| prop::<bool>(w, "hidden"))
+                    .unwrap_or(false)
+            })
+            .filter_map(|(_, s)| setting_str(s, "connection", "id"))
+            .collect())
+    }
+
+    async fn saved_password(&self, name: &str) -> Option<String> {
+        let path = self.find_connection(name).await.ok()?;
+        let conn = self.proxy(path.as_str(), IFACE_CONNECTION).await.ok()?;
+        let secrets: Settings = conn
+            .call("GetSecrets", &("802-11-wireless-security",))
+            .await
+            .ok()?;
+        setting_str(&secrets, "802-11-wireless-security", "psk")
+    }
+
+    async fn get_ip(&self) -> Option<String> {
+        // 稍等一下让 DHCP 完成
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let props = self.ip4_config().await.ok()?;
+        first_address(&props)
+    }
+
+    async fn get_details(&self, ssid: &str, ping_host: &str) -> Result<ConnectionDetails> {
+        let (ip4, ping_ms) = tokio::join!(self.ip4_config(), ping::ping_once(ping_host));
+        let ip4 = ip4.unwrap_or_default();
+
+        let ip = first_address(&ip4).unwrap_or_else(|| "N/A".into());
+        let gateway = prop::<String>(&ip4, "Gateway")
+            .filter(|g| !g.is_empty())
+            .unwrap_or_else(|| "N/A".into());
+        let dns = prop::<Vec<HashMap<String, OwnedValue>>>(&ip4, "NameserverData")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|d| prop::<String>(d, "address"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let device = self.wifi_device().await?;
+        let active = self.active_ap(&device).await?;
+        let (security, signal) = if active.as_str() == "/" {
+            ("--".to_string(), "--".to_string())
+        } else {
+            let props = self.get_all(active.as_str(), IFACE_AP).await?;
+            let strength: u8 = prop(&props, "Strength").unwrap_or(0);
+            (ap_security(&props).to_string(), strength.to_string())
+        };
+
+        Ok(ConnectionDetails {
+            ssid: ssid.to_string(),
+            ip,
+            gateway,
+            dns: if dns.is_empty() { "N/A".into() } else { dns },
+            security,
+            signal,
+            ping_ms,
+        })
+    }
+
+    fn interfaces(&self) -> &Interfaces {
+        &self.ifaces
+    }
+
+    async fn devices(&self) -> Result<Vec<WifiDevice>> {
+        let mut devices = Vec::new();
+        for (path, name) in self.wifi_devices().await? {
+            let props = self.get_all(path.as_str(), IFACE_DEVICE).await?;
+            let active: Option<OwnedObjectPath> = prop(&props, "ActiveConnection");
+            let connection = match active.filter(|p| p.as_str() != "/") {
+                Some(p) => self
+                    .proxy(p.as_str(), IFACE_ACTIVE)
+                    .await?
+                    .get_property::<String>("Id")
+                    .await
+                    .ok(),
+                None => None,
+            };
+            devices.push(WifiDevice {
+                name,
+                state: device_state_label(prop(&props, "State").unwrap_or(0)).into(),
+                connection,
+            });
+        }
+        Ok(devices)
+    }
+
+    // ── 连接管理 ─────────────────────────────────────────────
+
+    async fn connect_saved(&self, name: &str, bssid: Option<&str>, timeout: u64) -> ConnectResult {
+        let (conn, device, active) = match self.activate_saved(name, bssid).await {
+            Ok(r) => r,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        let outcome = self.wait_activated(&active, &device, timeout).await;
+        let enterprise = self.is_enterprise(&conn).await;
+        self.activation_result(outcome, enterprise).await
+    }
+
+    async fn connect_new(&self, req: &ConnectRequest, timeout: u64) -> ConnectResult {
+        let ssid = req.ssid.as_str();
+        let device = match self.wifi_device().await {
+            Ok(d) => d,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+
+        let mut wireless = HashMap::from([("ssid", Value::from(ssid.as_bytes()))]);
+        if let Some(b) = &req.bssid {
+            match parse_mac(b) {
+                Some(mac) => {
+                    wireless.insert("bssid", Value::from(mac));
+                }
+                None => return ConnectResult::Failed(t!(InvalidBssid, bssid = b)),
+            }
+        }
+        if req.hidden {
+            wireless.insert("hidden", Value::from(true));
+        }
+        let mut settings: HashMap<&str, HashMap<&str, Value>> = HashMap::new();
+        settings.insert("802-11-wireless", wireless);
+        if let Some(eap) = &req.enterprise {
+            settings.insert(
+                "802-11-wireless-security",
+                HashMap::from([("key-mgmt", Value::from("wpa-eap"))]),
+            );
+            settings.insert("802-1x", eap_settings(eap));
+        } else {
+            // 与 nmcli 一致：按扫描到的 AP 加密方式决定 key-mgmt
+            let security = self.scanned_security(&device, ssid).await;
+            if let Some(group) = security_settings(req.password.as_deref(), security.as_ref()) {
+                settings.insert("802-11-wireless-security", group);
+            }
+        }
+
+        let (conn, active) = match self.add_and_activate(settings, &device).await {
+            Ok(r) => r,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+
+        let outcome = self.wait_activated(&active, &device, timeout).await;
+        if !matches!(outcome, Activation::Done) {
+            // 清理残留 profile
+            let _ = self.delete_path(&conn).await;
+        }
+        self.activation_result(outcome, req.enterprise.is_some())
+            .await
+    }
+
+    async fn disconnect(&self, name: &str) -> Result<()> {
+        let active = self.find_active(name).await?;
+        let nm = self.proxy(NM_PATH, NM).await?;
+        nm.call::<_, _, ()>("DeactivateConnection", &(active,))
+            .await
+            .map_err(|_| anyhow!(t!(DisconnectFailed)))
+    }
+
+    async fn forget(&self, name: &str) -> Result<()> {
+        let path = self.find_connection(name).await?;
+        self.delete_path(&path)
+            .await
+            .map_err(|_| anyhow!(t!(ForgetFailed)))
+    }
+
+    // ── 无线电 & 热点 ─────────────────────────────────────────
+
+    async fn radio_state(&self) -> RadioState {
+        let enabled = match self.proxy(NM_PATH, NM).await {
+            Ok(nm) => nm
+                .get_property::<bool>("WirelessEnabled")
+                .await
+                .unwrap_or(false),
+            Err(_) => false,
+        };
+        if enabled {
+            RadioState::Enabled
+        } else {
+            RadioState::Disabled
+        }
+    }
+
+    async fn set_radio(&self, enable: bool) -> Result<()> {
+        let nm = self.proxy(NM_PATH, NM).await?;
+        nm.set_property("WirelessEnabled", enable).await?;
+        Ok(())
+    }
+
+    async fn hotspot_active(&self) -> Option<String> {
+        let hotspots = self.hotspot_names().await.ok()?;
+        for name in hotspots {
+            if self.find_active(&name).await.is_ok() {
+                return Some(name);
+            }
+        }
+        None
+    }
+
+    async fn hotspot_profile(&self) -> Option<String> {
+        self.hotspot_names().await.ok()?.into_iter().next()
+    }
+
+    async fn hotspot_up(&self, name: &str) -> Result<()> {
+        let conn = self.find_connection(name).await?;
+        let device = self.hotspot_device().await?;
+        self.activate(&conn, device.as_str(), "/").await?;
+        Ok(())
+    }
+
+    async fn hotspot_down(&self, name: &str) -> Result<()> {
+        self.disconnect(name).await
+    }
+
+    async fn create_hotspot(&self, ssid: &str, password: &str) -> Result<()> {
+        let device = self.hotspot_device().await?;
+        let settings: HashMap<&str, HashMap<&str, Value>> = HashMap::from([
+            (
+                "connection",
+                HashMap::from([
+                    ("id", Value::from("Hotspot")),
+                    ("type", Value::from("802-11-wireless")),
+                    ("autoconnect", Value::from(false)),
+                ]),
+            ),
+            (
+                "802-11-wireless",
+                HashMap::from([
+                    ("ssid", Value::from(ssid.as_bytes())),
+                    ("mode", Value::from("ap")),
+                ]),
+            ),
+            (
+                "802-11-wireless-security",
+                security_settings(Some(password), Some(&Security::wpa2_psk())).unwrap_or_default(),
+            ),
+            ("ipv4", HashMap::from([("method", Value::from("shared"))])),
+        ]);
+        self.add_and_activate(settings, &device)
+            .await
+            .map_err(|e| anyhow!("{}: {e}", t!(HotspotCreateFailed)))?;
+        Ok(())
+    }
+
+    async fn activate_vpn(&self, name: &str) -> Result<()> {
+        let conn = self.find_connection(name).await?;
+        self.activate(&conn, "/", "/")
+            .await
+            .map_err(|_| anyhow!(t!(CannotStart, name = name)))?;
+        Ok(())
+    }
+}
+
+// ── 属性解析辅助 ─────────────────────────────────────────────
+
+/// 从 a{sv} 中取出并转换一个值
+fn prop<T>(map: &HashMap<String, OwnedValue>, key: &str) -> Option<T>
+where
+    T: TryFrom<OwnedValue>,
+{
+    map.get(key)
+        .and_then(|v| v.try_clone().ok())
+        .and_then(|v| T::try_from(v).ok())
+}
+
+/// 802-1x 设置组；证书字段是以 NUL 结尾的 file:// URI 字节串
+fn eap_settings(eap: &EnterpriseConfig) -> HashMap<&'static str, Value<'static>> {
+    let cert = |path: &str| Value::from(format!("file://{path}\0").into_bytes());
+    let mut group = HashMap::from([
+        ("eap", Value::from(vec![eap.eap.to_string().to_lowercase()])),
+        ("identity", Value::from(eap.identity.clone())),
+    ]);
+    if let Some(p) = eap.phase2 {
+        group.insert("phase2-auth", Value::from(p.to_string().to_lowercase()));
+    }
+    if let Some(v) = &eap.anonymous_identity {
+        group.insert("anonymous-identity", Value::from(v.clone()));
+    }
+    if let Some(v) = &eap.ca_cert {
+        group.insert("ca-cert", cert(v));
+    }
+    if let Some(v) = &eap.domain_match {
+        group.insert("domain-suffix-match", Value::from(v.clone()));
+    }
+    if let Some(v) = &eap.password {
+        group.insert("password", Value::from(v.clone()));
+    }
+    if let Some(v) = &eap.client_cert {
+        group.insert("client-cert", cert(v));
+    }
+    if let Some(v) = &eap.private_key {
+        group.insert("private-key", cert(v));
+    }
+    if let Some(v) = &eap.private_key_password {
+        group.insert("private-key-password", Value::from(v.clone()));
+    }
+    group
+}
+
+/// 个人网络的 802-11-wireless-security 设置组；扫不到时有密码按 WPA-PSK 处理，
+/// 无需该设置组（开放网络）时为 None
+fn security_settings(
+    password: Option<&str>,
+    security: Option<&Security>,
+) -> Option<HashMap<&'static str, Value<'static>>> {
+    let Some(p) = password else {
+        // OWE 免密码但仍需声明 key-mgmt
+        return security
+            .filter(|s| s.is_owe())
+            .map(|_| HashMap::from([("key-mgmt", Value::from("owe"))]));
+    };
+    if security.is_some_and(|s| s.is_wep()) {
+        // 5 / 13 个字符或 10 / 26 位十六进制为密钥，其余按口令（MD5 派生）
+        let is_key = matches!(p.len(), 5 | 13)
+            || (matches!(p.len(), 10 | 26) && p.bytes().all(|b| b.is_ascii_hexdigit()));
+        let key_type = if is_key {
+            WEP_KEY_TYPE_KEY
+        } else {
+            WEP_KEY_TYPE_PASSPHRASE
+        };
+        return Some(HashMap::from([
+            ("key-mgmt", Value::from("none")),
+            ("wep-key0", Value::from(p.to_string())),
+            ("wep-key-type", Value::from(key_type)),
+        ]));
+    }
+    let key_mgmt = if security.is_some_and(|s| s.is_sae_only()) {
+        "sae"
+    } else {
+        "wpa-psk"
+    };
+    Some(HashMap::from([
+        ("key-mgmt", Value::from(key_mgmt)),
+        ("psk", Value::from(p.to_string())),
+    ]))
+}
+
+/// 从 a{sa{sv}} 中取出字符串设置项
+fn setting_str(settings: &Settings, group: &str, key: &str) -> Option<String> {
+    settings.get(group).and_then(|g| prop::<String>(g, key))
+}
+
+/// IP4Config.AddressData 中的第一个地址，格式同 nmcli：addr/prefix
+fn first_address(ip4: &HashMap<String, OwnedValue>) -> Option<String> {
+    let data: Vec<HashMap<String, OwnedValue>> = prop(ip4, "AddressData")?;
+    let first = data.first()?;
+    let addr: String = prop(first, "address")?;
+    let prefix: u32 = prop(first, "prefix").unwrap_or(0);
+    Some(format!("{addr}/{prefix}"))
+}
+
+/// "AA:BB:CC:DD:EE:FF" → 6 字节
+fn parse_mac(s: &str) -> Option<Vec<u8>> {
+    let bytes: Vec<u8> = s
+        .split(':')
+        .map(|p| u8::from_str_radix(p, 16).ok())
+        .collect::<Option<_>>()?;
+    (bytes.len() == 6).then_some(bytes)
+}
+
+/// AccessPoint 属性中的加密信息
+fn ap_security(props: &HashMap<String, OwnedValue>) -> Security {
+    Security::from_nm_flags(
+        prop::<u32>(props, "Flags").unwrap_or(0) & AP_FLAGS_PRIVACY != 0,
+        prop(props, "WpaFlags").unwrap_or(0),
+        prop(props, "RsnFlags").unwrap_or(0),
+    )
+}
+
+/// NMDeviceState → 与 nmcli device 一致的状态文字
+fn device_state_label(state: u32) -> &'static str {
+    match state {
+        10 => "unmanaged",
+        20 => "unavailable",
+        30 => "disconnected",
+        40..=90 => "connecting",
+        100 => "connected",
+        110 => "deactivating",
+        120 => "failed",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{nm_sec, Band, EapMethod, KeyMgmt};
+    use std::io::BufRead;
+    use std::sync::{Arc, Mutex};
+    use zbus::object_server::ObjectServer;
+
+    const DEVICE_PATH: &str = "/org/freedesktop/NetworkManager/Devices/1";
+    const IP4_PATH: &str = "/org/freedesktop/NetworkManager/IP4Config/1";
+    const AP_PATH: &str = "/org/freedesktop/NetworkManager/AccessPoint";
+
+    // RsnFlags：PSK / SAE + CCMP
+    const RSN_PSK: u32 = nm_sec::KEY_MGMT_PSK | nm_sec::PAIR_CCMP | nm_sec::GROUP_CCMP;
+    const RSN_SAE: u32 = nm_sec::KEY_MGMT_SAE | nm_sec::PAIR_CCMP | nm_sec::GROUP_CCMP;
+
+    fn owned<'a>(v: impl Into<Value<'a>>) -> OwnedValue {
+        v.into().try_to_owned().unwrap()
+    }
+
+    fn path(s: &str) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(s.to_string()).unwrap()
+    }
+
+    /// 私有的 dbus-daemon 会话总线，drop 时结束
+    struct PrivateBus {
+        daemon: std::process::Child,
+        address: String,
+    }
+
+    impl PrivateBus {
+        fn start() -> Option<Self> {
+            let mut daemon = std::process::Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            std::io::BufReader::new(daemon.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+            Some(Self {
+                daemon,
+                address: address.trim().to_string(),
+            })
+        }
+    }
+
+    impl Drop for PrivateBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    /// 假 NM 的可变状态：下一次激活的结果，以及收到的请求
+    #[derive(Default)]
+    struct State {
+        /// 新活跃连接的 State，设备 StateReason 的原因码
+        active_state: u32,
+        reason: u32,
+        added: Vec<Settings>,
+        activated: Vec<String>,
+        deleted: Vec<String>,
+        scans: u32,
+        next_id: u32,
+    }
+
+    type Shared = Arc<Mutex<State>>;
+
+    struct MockNm {
+        state: Shared,
+    }
+
+    impl MockNm {
+        /// 新建活跃连接对象，State 取当前设定的结果
+        async fn new_active(&self, server: &ObjectServer, id: String) -> OwnedObjectPath {
+            let (n, state) = {
+                let mut s = self.state.lock().unwrap();
+                s.next_id += 1;
+                (s.next_id, s.active_state)
+            };
+            let active = path(&format!(
+                "/org/freedesktop/NetworkManager/ActiveConnection/{n}"
+            ));
+            server.at(&active, MockActive { state, id }).await.unwrap();
+            active
+        }
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager")]
+    impl MockNm {
+        fn get_devices(&self) -> Vec<OwnedObjectPath> {
+            vec![path(DEVICE_PATH)]
+        }
+
+        async fn activate_connection(
+            &self,
+            conn: OwnedObjectPath,
+            _device: OwnedObjectPath,
+            _specific: OwnedObjectPath,
+            #[zbus(object_server)] server: &ObjectServer,
+        ) -> OwnedObjectPath {
+            self.state.lock().unwrap().activated.push(conn.to_string());
+            self.new_active(server, conn.to_string()).await
+        }
+
+        async fn add_and_activate_connection(
+            &self,
+            settings: Settings,
+            _device: OwnedObjectPath,
+            _specific: OwnedObjectPath,
+            #[zbus(object_server)] server: &ObjectServer,
+        ) -> (OwnedObjectPath, OwnedObjectPath) {
+            let ssid: Vec<u8> = prop(&settings["802-11-wireless"], "ssid").unwrap_or_default();
+            let ssid = String::from_utf8_lossy(&ssid).to_string();
+            self.state.lock().unwrap().added.push(settings);
+            let active = self.new_active(server, ssid.clone()).await;
+            let conn = path(&format!("{SETTINGS_PATH}/new{}", active.as_str().len()));
+            let profile = MockConnection {
+                path: conn.to_string(),
+                id: ssid,
+                kind: "802-11-wireless",
+                hidden: false,
+                psk: None,
+                state: self.state.clone(),
+            };
+            server.at(&conn, profile).await.unwrap();
+            (conn, active)
+        }
+
+        #[zbus(property)]
+        fn active_connections(&self) -> Vec<OwnedObjectPath> {
+            vec![]
+        }
+
+        #[zbus(property)]
+        fn wireless_enabled(&self) -> bool {
+            true
+        }
+    }
+
+    struct MockDevice {
+        state: Shared,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.Device")]
+    impl MockDevice {
+        #[zbus(property)]
+        fn device_type(&self) -> u32 {
+            DEVICE_TYPE_WIFI
+        }
+
+        #[zbus(property)]
+        fn interface(&self) -> String {
+            "wlan0".into()
+        }
+
+        #[zbus(property)]
+        fn state(&self) -> u32 {
+            100
+        }
+
+        #[zbus(property)]
+        fn state_reason(&self) -> (u32, u32) {
+            (120, self.state.lock().unwrap().reason)
+        }
+
+        #[zbus(property)]
+        fn ip4_config(&self) -> OwnedObjectPath {
+            path(IP4_PATH)
+        }
+
+        #[zbus(property)]
+        fn active_connection(&self) -> OwnedObjectPath {
+            path("/")
+        }
+    }
+
+    struct MockWireless {
+        aps: usize,
+        state: Shared,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.Device.Wireless")]
+    impl MockWireless {
+        fn get_all_access_points(&self) -> Vec<OwnedObjectPath> {
+            (1..=self.aps)
+                .map(|n| path(&format!("{AP_PATH}/{n}")))
+                .collect()
+        }
+
+        fn request_scan(&self, _options: HashMap<String, OwnedValue>) {
+            self.state.lock().unwrap().scans += 1;
+        }
+
+        #[zbus(property)]
+        fn active_access_point(&self) -> OwnedObjectPath {
+            path(&format!("{AP_PATH}/1"))
+        }
+    }
+
+    struct MockAp {
+        ssid: &'static str,
+        strength: u8,
+        hw_address: &'static str,
+        frequency: u32,
+        privacy: bool,
+        rsn: u32,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.AccessPoint")]
+    impl MockAp {
+        #[zbus(property)]
+        fn ssid(&self) -> Vec<u8> {
+            self.ssid.as_bytes().to_vec()
+        }
+
+        #[zbus(property)]
+        fn strength(&self) -> u8 {
+            self.strength
+        }
+
+        #[zbus(property)]
+        fn hw_address(&self) -> String {
+            self.hw_address.into()
+        }
+
+        #[zbus(property)]
+        fn frequency(&self) -> u32 {
+            self.frequency
+        }
+
+        #[zbus(property)]
+        fn max_bitrate(&self) -> u32 {
+            866_700
+        }
+
+        #[zbus(property)]
+        fn mode(&self) -> u32 {
+            NM_802_11_MODE_INFRA
+        }
+
+        #[zbus(property)]
+        fn flags(&self) -> u32 {
+            if self.privacy {
+                AP_FLAGS_PRIVACY
+            } else {
+                0
+            }
+        }
+
+        #[zbus(property)]
+        fn wpa_flags(&self) -> u32 {
+            0
+        }
+
+        #[zbus(property)]
+        fn rsn_flags(&self) -> u32 {
+            self.rsn
+        }
+    }
+
+    struct MockSettings {
+        connections: Vec<&'static str>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.Settings")]
+    impl MockSettings {
+        fn list_connections(&self) -> Vec<OwnedObjectPath> {
+            self.connections.iter().map(|p| path(p)).collect()
+        }
+    }
+
+    struct MockConnection {
+        path: String,
+        id: String,
+        kind: &'static str,
+        hidden: bool,
+        psk: Option<&'static str>,
+        state: Shared,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.Settings.Connection")]
+    impl MockConnection {
+        fn get_settings(&self) -> Settings {
+            HashMap::from([
+                (
+                    "connection".to_string(),
+                    HashMap::from([
+                        ("id".to_string(), owned(self.id.as_str())),
+                        ("type".to_string(), owned(self.kind)),
+                    ]),
+                ),
+                (
+                    "802-11-wireless".to_string(),
+                    HashMap::from([("hidden".to_string(), owned(self.hidden))]),
+                ),
+            ])
+        }
+
+        fn get_secrets(&self, _setting: String) -> Settings {
+            let group = self
+                .psk
+                .map(|p| HashMap::from([("psk".to_string(), owned(p))]))
+                .unwrap_or_default();
+            HashMap::from([("802-11-wireless-security".to_string(), group)])
+        }
+
+        fn delete(&self) {
+            self.state.lock().unwrap().deleted.push(self.path.clone());
+        }
+    }
+
+    struct MockActive {
+        state: u32,
+        id: String,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.Connection.Active")]
+    impl MockActive {
+        #[zbus(property)]
+        fn state(&self) -> u32 {
+            self.state
+        }
+
+        #[zbus(property)]
+        fn id(&self) -> String {
+            self.id.clone()
+        }
+    }
+
+    struct MockIp4;
+
+    #[zbus::interface(name = "org.freedesktop.NetworkManager.IP4Config")]
+    impl MockIp4 {
+        #[zbus(property)]
+        fn address_data(&self) -> Vec<HashMap<String, OwnedValue>> {
+            vec![HashMap::from([
+                ("address".to_string(), owned("192.168.1.5")),
+                ("prefix".to_string(), owned(24u32)),
+            ])]
+        }
+
+        #[zbus(property)]
+        fn gateway(&self) -> String {
+            "192.168.1.1".into()
+        }
+    }
+
+    /// 私有总线上的假 NetworkManager 与连向它的后端
+    struct Fixture {
+        nm: NmDbus,
+        state: Shared,
+        _server: Connection,
+        _bus: PrivateBus,
+    }
+
+    /// 找不到 dbus-daemon 时返回 None，调用方跳过测试
+    async fn fixture() -> Option<Fixture> {
+        let Some(bus) = PrivateBus::start() else {
+            eprintln!("dbus-daemon not found, skipping");
+            return None;
+        };
+        let state = Shared::default();
+        let aps = [
+            ("Home", 80, "AA:BB:CC:DD:EE:01", 5180, false, RSN_PSK),
+            ("Home", 40, "AA:BB:CC:DD:EE:02", 2437, false, RSN_PSK),
+            ("WPA3", 60, "AA:BB:CC:DD:EE:03", 5500, false, RSN_SAE),
+            ("Old", 50, "AA:BB:CC:DD:EE:04", 2462, true, 0),
+            ("", 30, "AA:BB:CC:DD:EE:05", 2412, false, RSN_PSK),
+            ("Free", 20, "AA:BB:CC:DD:EE:06", 2412, false, 0),
+        ];
+        let profiles = [
+            ("Home", "802-11-wireless", false, Some("secret")),
+            ("Lab", "802-11-wireless", true, None),
+            ("Office VPN", "vpn", false, None),
+        ];
+
+        let mut builder = zbus::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .name(NM)
+            .unwrap()
+            .serve_at(
+                NM_PATH,
+                MockNm {
+                    state: state.clone(),
+                },
+            )
+            .unwrap()
+            .serve_at(
+                DEVICE_PATH,
+                MockDevice {
+                    state: state.clone(),
+                },
+            )
+            .unwrap()
+            .serve_at(
+                DEVICE_PATH,
+                MockWireless {
+                    aps: aps.len(),
+                    state: state.clone(),
+                },
+            )
+            .unwrap()
+            .serve_at(IP4_PATH, MockIp4)
+            .unwrap();
+        for (n, (ssid, strength, hw_address, frequency, privacy, rsn)) in
+            aps.into_iter().enumerate()
+        {
+            let ap = MockAp {
+                ssid,
+                strength,
+                hw_address,
+                frequency,
+                privacy,
+                rsn,
+            };
+            builder = builder
+                .serve_at(format!("{AP_PATH}/{}", n + 1), ap)
+                .unwrap();
+        }
+        let mut paths = vec![];
+        for (n, (id, kind, hidden, psk)) in profiles.into_iter().enumerate() {
+            let p: &'static str = format!("{SETTINGS_PATH}/{}", n + 1).leak();
+            paths.push(p);
+            let conn = MockConnection {
+                path: p.to_string(),
+                id: id.to_string(),
+                kind,
+                hidden,
+                psk,
+                state: state.clone(),
+            };
+            builder = builder.serve_at(p, conn).unwrap();
+        }
+        builder = builder
+            .serve_at(SETTINGS_PATH, MockSettings { connections: paths })
+            .unwrap();
+        let server = builder.build().await.unwrap();
+
+        let client = zbus::connection::Builder::address(bus.address.as_str())
+            .unwrap()
+            .build()
+            .await
+            .unwrap();
+        let nm = NmDbus {
+            conn: OnceCell::new_with(Some(client)),
+            ifaces: Interfaces::new(None, None),
+        };
+        Some(Fixture {
+            nm,
+            state,
+            _server: server,
+            _bus: bus,
+        })
+    }
+
+    impl Fixture {
+        /// 下一次激活以 active_state 结束，失败原因为 reason
+        fn outcome(&self, active_state: u32, reason: u32) {
+            let mut s = self.state.lock().unwrap();
+            s.active_state = active_state;
+            s.reason = reason;
+        }
+
+        /// 最近一次 AddAndActivateConnection 中的 802-11-wireless-security 设置组
+        fn last_security(&self) -> Option<HashMap<String, String>> {
+            let s = self.state.lock().unwrap();
+            let group = s.added.last()?.get("802-11-wireless-security")?;
+            Some(
+                group
+                    .iter()
+                    .map(|(k, v)| {
+                        let v = prop::<String>(group, k)
+                            .or_else(|| prop::<u32>(group, k).map(|n| n.to_string()))
+                            .unwrap_or_else(|| format!("{v:?}"));
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    fn request(ssid: &str, password: Option<&str>) -> ConnectRequest {
+        ConnectRequest {
+            ssid: ssid.into(),
+            password: password.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn scan_reads_access_points() {
+        let Some(f) = fixture().await else { return };
+        f.nm.rescan().await;
+        assert_eq!(f.state.lock().unwrap().scans, 1);
+
+        let aps = f.nm.list_access_points().await.unwrap();
+        let find = |ssid: &str| aps.iter().find(|ap| ap.ssid == ssid).unwrap();
+        let home = &aps[0];
+        assert_eq!(home.ssid, "Home");
+        assert!(home.in_use);
+        assert_eq!(home.signal, 80);
+        assert_eq!(home.bss.len(), 2);
+        assert_eq!(home.bss[0].band, Band::G5);
+        assert_eq!(home.bss[0].max_rate, 866);
+        assert_eq!(home.security.key_mgmt, [KeyMgmt::Psk]);
+        assert!(find("WPA3").security.is_sae_only());
+        assert!(find("Old").security.is_wep());
+        assert!(find("Free").security.is_open());
+        assert!(find("").hidden);
+
+        assert_eq!(f.nm.current_ssid().await.as_deref(), Some("Home"));
+    }
+
+    #[tokio::test]
+    async fn saved_profiles() {
+        let Some(f) = fixture().await else { return };
+        assert_eq!(f.nm.saved_connections().await.unwrap(), ["Home", "Lab"]);
+        assert_eq!(f.nm.saved_hidden().await.unwrap(), ["Lab"]);
+        assert_eq!(f.nm.saved_password("Home").await.as_deref(), Some("secret"));
+        assert_eq!(f.nm.saved_password("Lab").await, None);
+    }
+
+    #[tokio::test]
+    async fn security_settings_follow_scanned_ap() {
+        let Some(f) = fixture().await else { return };
+        // 立即失败，只检查提交的设置
+        f.outcome(ACTIVE_DEACTIVATED, 99);
+        let cases = [
+            (
+                "Home",
+                Some("password"),
+                Some(("wpa-psk", "psk", "password")),
+            ),
+            ("WPA3", Some("password"), Some(("sae", "psk", "password"))),
+            ("Old", Some("abcde"), Some(("none", "wep-key0", "abcde"))),
+            (
+                "Ghost",
+                Some("password"),
+                Some(("wpa-psk", "psk", "password")),
+            ),
+            ("Free", None, None),
+        ];
+        for (ssid, password, expected) in cases {
+            f.nm.connect_new(&request(ssid, password), 5).await;
+            let group = f.last_security();
+            match expected {
+                Some((key_mgmt, key, value)) => {
+                    let group = group.unwrap_or_else(|| panic!("{ssid}: no security group"));
+                    assert_eq!(group["key-mgmt"], key_mgmt, "{ssid}");
+                    assert_eq!(group[key], value, "{ssid}");
+                }
+                None => assert!(group.is_none(), "{ssid}"),
+            }
+        }
+        f.nm.connect_new(&request("Old", Some("0123456789")), 5)
+            .await;
+        assert_eq!(
+            f.last_security().unwrap()["wep-key-type"],
+            WEP_KEY_TYPE_KEY.to_string()
+        );
+        f.nm.connect_new(&request("Old", Some("long passphrase")), 5)
+            .await;
+        assert_eq!(
+            f.last_security().unwrap()["wep-key-type"],
+            WEP_KEY_TYPE_PASSPHRASE.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn activation_outcome_maps_to_connect_result() {
+        let Some(f) = fixture().await else { return };
+
+        f.outcome(ACTIVE_ACTIVATED, 0);
+        match f
+            .nm
+            .connect_new(&request("Home", Some("password")), 5)
+            .await
+        {
+            ConnectResult::Success { ip } => assert_eq!(ip, "192.168.1.5/24"),
+            other => panic!("{other:?}"),
+        }
+        assert!(f.state.lock().unwrap().deleted.is_empty());
+
+        // 一直处于 activating
+        f.outcome(1, 0);
+        let result =
+            f.nm.connect_new(&request("Home", Some("password")), 1)
+                .await;
+        assert!(matches!(result, ConnectResult::Timeout), "{result:?}");
+
+        let eap = EnterpriseConfig {
+            eap: EapMethod::Peap,
+            phase2: None,
+            identity: "alice".into(),
+            anonymous_identity: None,
+            ca_cert: None,
+            domain_match: None,
+            password: Some("pw".into()),
+            client_cert: None,
+            private_key: None,
+            private_key_password: None,
+        };
+        let enterprise = ConnectRequest {
+            enterprise: Some(eap),
+            ..request("Corp", None)
+        };
+        for (reason, req, expected) in [
+            (
+                REASON_NO_SECRETS,
+                request("Home", Some("x")),
+                "WrongPassword",
+            ),
+            (
+                REASON_SUPPLICANT_DISCONNECT,
+                request("Home", Some("x")),
+                "WrongPassword",
+            ),
+            (
+                REASON_SUPPLICANT_DISCONNECT,
+                enterprise.clone(),
+                "EapFailed",
+            ),
+            (REASON_SUPPLICANT_FAILED, enterprise, "EapFailed"),
+            (
+                REASON_SUPPLICANT_TIMEOUT,
+                request("Home", Some("x")),
+                "Timeout",
+            ),
+            (
+                REASON_SSID_NOT_FOUND,
+                request("Home", Some("x")),
+                "NotFound",
+            ),
+            (99, request("Home", Some("x")), "Failed"),
+        ] {
+            f.outcome(ACTIVE_DEACTIVATED, reason);
+            let result = f.nm.connect_new(&req, 5).await;
+            let name = format!("{result:?}");
+            assert!(name.starts_with(expected), "reason {reason}: {name}");
+        }
+        // 失败的连接都被删除（超时一次 + 七种原因）
+        assert_eq!(f.state.lock().unwrap().deleted.len(), 8);
+    }
+
+    #[tokio::test]
+    async fn saved_activation_maps_to_connect_result() {
+        let Some(f) = fixture().await else { return };
+
+        f.outcome(ACTIVE_ACTIVATED, 0);
+        let result = f.nm.connect_saved("Home", None, 5).await;
+        assert!(
+            matches!(result, ConnectResult::Success { .. }),
+            "{result:?}"
+        );
+
+        f.outcome(ACTIVE_DEACTIVATED, REASON_NO_SECRETS);
+        let result = f.nm.connect_saved("Home", None, 5).await;
+        assert!(matches!(result, ConnectResult::WrongPassword), "{result:?}");
+
+        f.outcome(1, 0);
+        let result = f.nm.connect_saved("Home", None, 1).await;
+        assert!(matches!(result, ConnectResult::Timeout), "{result:?}");
+
+        let result = f.nm.connect_saved("Nowhere", None, 5).await;
+        assert!(matches!(result, ConnectResult::Failed(_)), "{result:?}");
+        // 已保存的配置失败后保留
+        assert!(f.state.lock().unwrap().deleted.is_empty());
+    }
+}
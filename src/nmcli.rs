@@ -1,408 +1,736 @@
-// src/nmcli.rs — 所有 nmcli 调用封装
-
-use crate::config::Config;
-use crate::types::{AccessPoint, ConnectResult, RadioState, Security};
+// src/nmcli.rs — 基于 nmcli 子进程的 NetworkBackend 实现
+
+use crate::backend::{Interfaces, NetworkBackend};
+use crate::i18n::t;
+use crate::ping;
+use crate::types::{
+    group_by_ssid, nm_sec, AccessPoint, ApMode, Band, Bss, ConnectRequest, ConnectResult,
+    ConnectionDetails, EnterpriseConfig, RadioState, ScanEntry, Security, WifiDevice,
+};
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::process::Output;
 use std::time::Duration;
 use tokio::process::Command;
 
-// ── 查询 ─────────────────────────────────────────────────────
-
-/// 触发一次 Wi-Fi 重新扫描（不等待结果）
-pub async fn rescan() {
-    let _ = Command::new("nmcli")
-        .args(["dev", "wifi", "rescan"])
-        .output()
-        .await;
+/// 通过 nmcli 命令行操作 NetworkManager
+pub struct Nmcli {
+    ifaces: Interfaces,
 }
 
-/// 获取接入点列表，按信号强度降序
-pub async fn list_access_points() -> Result<Vec<AccessPoint>> {
-    let out = Command::new("nmcli")
-        .args([
-            "--fields",
-            "IN-USE,SSID,SECURITY,SIGNAL,BARS",
-            "--terse",
-            "device",
-            "wifi",
-            "list",
-        ])
-        .output()
-        .await?;
+impl Nmcli {
+    pub fn new(ifaces: Interfaces) -> Self {
+        Self { ifaces }
+    }
 
-    let stdout = String::from_utf8_lossy(&out.stdout);
-    let mut aps: Vec<AccessPoint> = stdout
-        .lines()
-        .filter(|l| !l.starts_with("--"))
-        .filter_map(parse_ap_line)
-        .collect();
+    /// 选中网卡时追加 `ifname <dev>`，否则由 NM 自选
+    fn ifname(&self) -> Vec<String> {
+        match self.ifaces.station() {
+            Some(dev) => vec!["ifname".into(), dev],
+            None => vec![],
+        }
+    }
 
-    // 信号强度降序，当前连接的始终置顶
-    aps.sort_by(|a, b| b.in_use.cmp(&a.in_use).then(b.signal.cmp(&a.signal)));
+    /// `dev wifi con` 无法设置 802-1x.*，企业网络改为先 add 再 up
+    async fn connect_enterprise(
+        &self,
+        req: &ConnectRequest,
+        eap: &EnterpriseConfig,
+        timeout: u64,
+    ) -> ConnectResult {
+        let ssid = req.ssid.as_str();
+        let mut args: Vec<String> = vec![
+            "connection".into(),
+            "add".into(),
+            "type".into(),
+            "wifi".into(),
+            "con-name".into(),
+            ssid.into(),
+            "ssid".into(),
+            ssid.into(),
+            "wifi-sec.key-mgmt".into(),
+            "wpa-eap".into(),
+            "802-1x.eap".into(),
+            eap.eap.to_string().to_lowercase(),
+            "802-1x.identity".into(),
+            eap.identity.clone(),
+        ];
+        let optional = [
+            (
+                "802-1x.phase2-auth",
+                eap.phase2.map(|p| p.to_string().to_lowercase()),
+            ),
+            ("802-1x.anonymous-identity", eap.anonymous_identity.clone()),
+            ("802-1x.ca-cert", eap.ca_cert.clone()),
+            ("802-1x.domain-suffix-match", eap.domain_match.clone()),
+            ("802-1x.password", eap.password.clone()),
+            ("802-1x.client-cert", eap.client_cert.clone()),
+            ("802-1x.private-key", eap.private_key.clone()),
+            (
+                "802-1x.private-key-password",
+                eap.private_key_password.clone(),
+            ),
+            ("802-11-wireless.bssid", req.bssid.clone()),
+            ("802-11-wireless.hidden", req.hidden.then(|| "yes".into())),
+        ];
+        for (key, value) in optional {
+            if let Some(v) = value {
+                args.push(key.into());
+                args.push(v);
+            }
+        }
+
+        match Command::new("nmcli").args(&args).output().await {
+            Ok(out) if out.status.success() => {}
+            Ok(out) => {
+                let msg = String::from_utf8_lossy(&out.stderr).trim().to_string();
+                return ConnectResult::Failed(msg);
+            }
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        }
 
-    // 去重（同一 SSID 可能出现在多个信道）
-    aps.dedup_by(|a, b| a.ssid == b.ssid && !a.in_use);
+        let out = match Command::new("nmcli")
+            .args(["--wait", &timeout.to_string(), "connection", "up", ssid])
+            .args(self.ifname())
+            .output()
+            .await
+        {
+            Ok(out) => out,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        if out.status.success() {
+            let ip = self.get_ip().await.unwrap_or_else(|| t!(Unknown).into());
+            return ConnectResult::Success { ip };
+        }
+
+        // 清理残留 profile
+        let _ = Command::new("nmcli")
+            .args(["connection", "delete", ssid])
+            .output()
+            .await;
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        let msg = stderr
+            .lines()
+            .last()
+            .unwrap_or(t!(UnknownError))
+            .to_string();
+        let lower = stderr.to_lowercase();
+        if lower.contains("secrets") {
+            ConnectResult::WrongPassword
+        } else if lower.contains("timeout") {
+            ConnectResult::Timeout
+        } else if lower.contains("802.1x") || lower.contains("supplicant") {
+            ConnectResult::EapFailed(msg)
+        } else {
+            ConnectResult::Failed(msg)
+        }
+    }
+}
 
-    Ok(aps)
+/// 按 nmcli 失败输出区分密码错误、超时与其他错误
+fn failure_result(out: &Output) -> ConnectResult {
+    let stderr = String::from_utf8_lossy(&out.stderr).to_lowercase();
+    let stdout = String::from_utf8_lossy(&out.stdout).to_lowercase();
+    let combined = format!("{stderr}{stdout}");
+    // "No network with SSID 'x' found" / 隐藏网络 "could not be found"
+    if combined.contains("no network with ssid") || combined.contains("could not be found") {
+        ConnectResult::NotFound
+    } else if combined.contains("secrets")
+        || combined.contains("password")
+        || combined.contains("authentication")
+        || combined.contains("802-11-wireless-security")
+    {
+        ConnectResult::WrongPassword
+    } else if combined.contains("timeout") {
+        ConnectResult::Timeout
+    } else {
+        let msg = String::from_utf8_lossy(&out.stderr)
+            .lines()
+            .last()
+            .unwrap_or(t!(UnknownError))
+            .to_string();
+        ConnectResult::Failed(msg)
+    }
 }
 
-fn parse_ap_line(line: &str) -> Option<AccessPoint> {
-    // nmcli -t 用 ':' 分隔，但 SSID 本身可能含 ':'，需谨慎处理
-    // 格式: IN-USE:SSID:SECURITY:SIGNAL:BARS
-    let parts: Vec<&str> = line.splitn(5, ':').collect();
-    if parts.len() < 5 {
-        return None;
+#[async_trait]
+impl NetworkBackend for Nmcli {
+    fn name(&self) -> &'static str {
+        "nmcli"
     }
 
-    let in_use = parts[0].trim() == "*";
-    let ssid = parts[1].trim().to_string();
-    let security = Security::from(parts[2].trim());
-    let signal = parts[3].trim().parse::<u8>().unwrap_or(0);
-    let bars = parts[4].trim().to_string();
+    // ── 查询 ─────────────────────────────────────────────────
 
-    if ssid.is_empty() || ssid == "--" {
-        return None;
+    /// 触发一次 Wi-Fi 重新扫描（不等待结果）
+    async fn rescan(&self) {
+        let _ = Command::new("nmcli")
+            .args(["dev", "wifi", "rescan"])
+            .args(self.ifname())
+            .output()
+            .await;
     }
 
-    Some(AccessPoint {
-        ssid,
-        security,
-        signal,
-        bars,
-        in_use,
-    })
-}
+    /// 获取接入点列表，按信号强度降序
+    async fn list_access_points(&self) -> Result<Vec<AccessPoint>> {
+        let out = Command::new("nmcli")
+            .args([
+                "--fields",
+                "IN-USE,BSSID,SSID,MODE,CHAN,FREQ,RATE,SIGNAL,BARS,SECURITY,WPA-FLAGS,RSN-FLAGS",
+                "--terse",
+                "device",
+                "wifi",
+                "list",
+            ])
+            .args(self.ifname())
+            .output()
+            .await?;
+
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        let entries = terse_records(&stdout, 12)
+            .into_iter()
+            .filter_map(|f| parse_ap_fields(&f))
+            .collect();
+
+        // 同一 SSID 的多个 BSSID 归组，信号强度降序，当前连接的始终置顶
+        Ok(group_by_ssid(entries))
+    }
 
-/// 获取 Wi-Fi 无线电状态
-pub async fn radio_state() -> RadioState {
-    let out = Command::new("nmcli")
-        .args(["-fields", "WIFI", "general"])
-        .output()
-        .await
-        .unwrap();
-    let s = String::from_utf8_lossy(&out.stdout);
-    if s.contains("enabled") {
-        RadioState::Enabled
-    } else {
-        RadioState::Disabled
+    /// 当前已连接的 SSID（None 表示未连接）
+    async fn current_ssid(&self) -> Option<String> {
+        let out = Command::new("nmcli")
+            .env("LANGUAGE", "C")
+            .args([
+                "-t",
+                "-f",
+                "ACTIVE,SSID",
+                "dev",
+                "wifi",
+                "list",
+                "--rescan",
+                "no",
+            ])
+            .args(self.ifname())
+            .output()
+            .await
+            .ok()?;
+        terse_records(&String::from_utf8_lossy(&out.stdout), 2)
+            .into_iter()
+            .find(|f| f[0] == "yes")
+            .map(|f| f[1].clone())
     }
-}
 
-/// 当前已连接的 SSID（None 表示未连接）
-pub async fn current_ssid() -> Option<String> {
-    let out = Command::new("nmcli")
-        .env("LANGUAGE", "C")
-        .args(["-t", "-f", "active,ssid", "dev", "wifi"])
-        .output()
-        .await
-        .ok()?;
-    String::from_utf8_lossy(&out.stdout)
-        .lines()
-        .find(|l| l.starts_with("yes:"))
-        .map(|l| l[4..].to_string())
-}
+    /// 用上次扫描的结果，不触发重新扫描
+    async fn current_link(&self) -> Option<(String, u8)> {
+        let out = Command::new("nmcli")
+            .env("LANGUAGE", "C")
+            .args([
+                "-t",
+                "-f",
+                "ACTIVE,SIGNAL,SSID",
+                "dev",
+                "wifi",
+                "list",
+                "--rescan",
+                "no",
+            ])
+            .args(self.ifname())
+            .output()
+            .await
+            .ok()?;
+        terse_records(&String::from_utf8_lossy(&out.stdout), 3)
+            .into_iter()
+            .find(|f| f[0] == "yes")
+            .map(|f| (f[2].clone(), f[1].parse().unwrap_or(0)))
+    }
 
-/// 已保存的所有 Wi-Fi connection 名称
-pub async fn saved_connections() -> Result<Vec<String>> {
-    let out = Command::new("nmcli")
-        .args(["-t", "-f", "NAME,TYPE", "connection", "show"])
-        .output()
-        .await?;
-    let names = String::from_utf8_lossy(&out.stdout)
-        .lines()
-        .filter(|l| l.contains("wireless"))
-        .filter_map(|l| l.split(':').next().map(str::to_string))
-        .collect();
-    Ok(names)
-}
+    /// 已保存的所有 Wi-Fi connection 名称
+    async fn saved_connections(&self) -> Result<Vec<String>> {
+        let out = Command::new("nmcli")
+            .args(["-t", "-f", "NAME,TYPE", "connection", "show"])
+            .output()
+            .await?;
+        let names = terse_records(&String::from_utf8_lossy(&out.stdout), 2)
+            .into_iter()
+            .filter(|f| f[1] == "802-11-wireless")
+            .map(|f| f[0].clone())
+            .collect();
+        Ok(names)
+    }
 
-/// 查询已保存连接的密码（需要 polkit 授权）
-pub async fn saved_password(ssid: &str) -> Option<String> {
-    let out = Command::new("nmcli")
-        .args([
-            "-s",
-            "-t",
-            "-f",
+    /// 已保存连接中 802-11-wireless.hidden 为 yes 的；先列出 UUID，再一次 show 全部配置
+    async fn saved_hidden(&self) -> Result<Vec<String>> {
+        let out = Command::new("nmcli")
+            .args(["-t", "-f", "UUID,TYPE", "connection", "show"])
+            .output()
+            .await?;
+        let uuids: Vec<String> = terse_records(&String::from_utf8_lossy(&out.stdout), 2)
+            .into_iter()
+            .filter(|f| f[1] == "802-11-wireless")
+            .map(|mut f| f.swap_remove(0))
+            .collect();
+        if uuids.is_empty() {
+            return Ok(vec![]);
+        }
+        let out = Command::new("nmcli")
+            .args([
+                "-t",
+                "-f",
+                "connection.id,802-11-wireless.hidden",
+                "connection",
+                "show",
+            ])
+            .args(uuids.iter().flat_map(|u| ["uuid", u.as_str()]))
+            .output()
+            .await?;
+        Ok(hidden_profiles(&String::from_utf8_lossy(&out.stdout)))
+    }
+
+    /// 查询已保存连接的密码（需要 polkit 授权）
+    async fn saved_password(&self, name: &str) -> Option<String> {
+        let out = Command::new("nmcli")
+            .args([
+                "-s",
+                "-t",
+                "-f",
+                "802-11-wireless-security.psk",
+                "connection",
+                "show",
+                name,
+            ])
+            .output()
+            .await
+            .ok()?;
+        terse_value(
+            &String::from_utf8_lossy(&out.stdout),
             "802-11-wireless-security.psk",
-            "connection",
-            "show",
-            ssid,
-        ])
-        .output()
-        .await
-        .ok()?;
-    let s = String::from_utf8_lossy(&out.stdout);
-    s.lines()
-        .find(|l| l.contains("802-11-wireless-security.psk"))
-        .and_then(|l| l.split(':').nth(1))
-        .map(str::to_string)
-}
+        )
+    }
 
-// ── 连接管理 ─────────────────────────────────────────────────
-
-/// 唤起已保存的 profile
-pub async fn connect_saved(ssid: &str, cfg: &Config) -> Result<()> {
-    let status = Command::new("nmcli")
-        .args([
-            "--wait",
-            &cfg.connect_timeout.to_string(),
-            "connection",
-            "up",
-            ssid,
-        ])
-        .status()
-        .await?;
-    if status.success() {
-        Ok(())
-    } else {
-        Err(anyhow!("唤起失败"))
+    async fn get_ip(&self) -> Option<String> {
+        // 稍等一下让 DHCP 完成
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let out = Command::new("nmcli")
+            .args(["-t", "-f", "IP4.ADDRESS", "dev", "show"])
+            .args(self.ifaces.station())
+            .output()
+            .await
+            .ok()?;
+        terse_value(&String::from_utf8_lossy(&out.stdout), "IP4.ADDRESS[1]")
+    }
+
+    async fn get_details(&self, ssid: &str, ping_host: &str) -> Result<ConnectionDetails> {
+        // 并发获取设备信息和 ping
+        let (dev_info, ping_ms) = tokio::join!(
+            get_dev_info(self.ifaces.station()),
+            ping::ping_once(ping_host)
+        );
+        let (ip, gateway, dns) = dev_info;
+
+        // 信号强度
+        let signal_out = Command::new("nmcli")
+            .args(["-t", "-f", "IN-USE,SIGNAL", "dev", "wifi", "list"])
+            .args(self.ifname())
+            .output()
+            .await?;
+        let signal = terse_records(&String::from_utf8_lossy(&signal_out.stdout), 2)
+            .into_iter()
+            .find(|f| f[0] == "*")
+            .map(|f| f[1].clone())
+            .unwrap_or_else(|| "--".into());
+
+        // 安全类型
+        let sec_out = Command::new("nmcli")
+            .args(["-t", "-f", "IN-USE,SECURITY", "dev", "wifi", "list"])
+            .args(self.ifname())
+            .output()
+            .await?;
+        let security = terse_records(&String::from_utf8_lossy(&sec_out.stdout), 2)
+            .into_iter()
+            .find(|f| f[0] == "*")
+            .map(|f| f[1].clone())
+            .unwrap_or_else(|| "--".into());
+
+        Ok(ConnectionDetails {
+            ssid: ssid.to_string(),
+            ip,
+            gateway,
+            dns,
+            security,
+            signal,
+            ping_ms,
+        })
     }
-}
 
-/// 连接新网络，返回带语义的结果
-pub async fn connect_new(ssid: &str, password: Option<&str>, cfg: &Config) -> ConnectResult {
-    let mut args = vec![
-        "--wait".to_string(),
-        cfg.connect_timeout.to_string(),
-        "dev".into(),
-        "wifi".into(),
-        "con".into(),
-        ssid.to_string(),
-    ];
-    if let Some(p) = password {
-        args.push("password".into());
-        args.push(p.to_string());
-    }
-
-    match Command::new("nmcli").args(&args).output().await {
-        Err(e) => ConnectResult::Failed(e.to_string()),
-        Ok(out) => {
-            if out.status.success() {
-                let ip = get_ip().await.unwrap_or_else(|| "未知".into());
+    fn interfaces(&self) -> &Interfaces {
+        &self.ifaces
+    }
+
+    async fn devices(&self) -> Result<Vec<WifiDevice>> {
+        let out = Command::new("nmcli")
+            .args(["-t", "-f", "DEVICE,TYPE,STATE,CONNECTION", "device"])
+            .output()
+            .await?;
+        Ok(terse_records(&String::from_utf8_lossy(&out.stdout), 4)
+            .into_iter()
+            .filter(|f| f[1] == "wifi")
+            .map(|f| WifiDevice {
+                name: f[0].clone(),
+                state: f[2].clone(),
+                connection: Some(f[3].clone()).filter(|c| !c.is_empty() && c != "--"),
+            })
+            .collect())
+    }
+
+    // ── 连接管理 ─────────────────────────────────────────────
+
+    /// 唤起已保存的 profile
+    async fn connect_saved(&self, name: &str, bssid: Option<&str>, timeout: u64) -> ConnectResult {
+        let timeout = timeout.to_string();
+        let mut args = vec!["--wait", &timeout, "connection", "up", name];
+        if let Some(b) = bssid {
+            args.extend(["ap", b]);
+        }
+        match Command::new("nmcli")
+            .args(&args)
+            .args(self.ifname())
+            .output()
+            .await
+        {
+            Err(e) => ConnectResult::Failed(e.to_string()),
+            Ok(out) if out.status.success() => {
+                let ip = self.get_ip().await.unwrap_or_else(|| t!(Unknown).into());
                 ConnectResult::Success { ip }
-            } else {
-                let stderr = String::from_utf8_lossy(&out.stderr).to_lowercase();
-                let stdout = String::from_utf8_lossy(&out.stdout).to_lowercase();
-                let combined = format!("{stderr}{stdout}");
-                // 清理残留 profile
-                let _ = Command::new("nmcli")
-                    .args(["connection", "delete", ssid])
-                    .output()
-                    .await;
-                if combined.contains("secrets")
-                    || combined.contains("password")
-                    || combined.contains("authentication")
-                    || combined.contains("802-11-wireless-security")
-                {
-                    ConnectResult::WrongPassword
-                } else if combined.contains("timeout") {
-                    ConnectResult::Timeout
+            }
+            Ok(out) => failure_result(&out),
+        }
+    }
+
+    /// 连接新网络，返回带语义的结果
+    async fn connect_new(&self, req: &ConnectRequest, timeout: u64) -> ConnectResult {
+        if let Some(eap) = &req.enterprise {
+            return self.connect_enterprise(req, eap, timeout).await;
+        }
+        let ssid = req.ssid.as_str();
+        let mut args = vec![
+            "--wait".to_string(),
+            timeout.to_string(),
+            "dev".into(),
+            "wifi".into(),
+            "con".into(),
+            ssid.to_string(),
+        ];
+        if let Some(p) = &req.password {
+            args.push("password".into());
+            args.push(p.clone());
+        }
+        if let Some(b) = &req.bssid {
+            args.push("bssid".into());
+            args.push(b.clone());
+        }
+        if req.hidden {
+            args.push("hidden".into());
+            args.push("yes".into());
+        }
+        args.extend(self.ifname());
+
+        match Command::new("nmcli").args(&args).output().await {
+            Err(e) => ConnectResult::Failed(e.to_string()),
+            Ok(out) => {
+                if out.status.success() {
+                    let ip = self.get_ip().await.unwrap_or_else(|| t!(Unknown).into());
+                    ConnectResult::Success { ip }
                 } else {
-                    let msg = String::from_utf8_lossy(&out.stderr)
-                        .lines()
-                        .last()
-                        .unwrap_or("未知错误")
-                        .to_string();
-                    ConnectResult::Failed(msg)
+                    // 清理残留 profile
+                    let _ = Command::new("nmcli")
+                        .args(["connection", "delete", ssid])
+                        .output()
+                        .await;
+                    failure_result(&out)
                 }
             }
         }
     }
-}
 
-/// 断开当前活跃连接
-pub async fn disconnect(ssid: &str) -> Result<()> {
-    let status = Command::new("nmcli")
-        .args(["connection", "down", ssid])
-        .status()
-        .await?;
-    if status.success() {
+    /// 断开当前活跃连接
+    async fn disconnect(&self, name: &str) -> Result<()> {
+        let status = Command::new("nmcli")
+            .args(["connection", "down", name])
+            .status()
+            .await?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(t!(DisconnectFailed)))
+        }
+    }
+
+    /// 删除已保存的 connection profile
+    async fn forget(&self, name: &str) -> Result<()> {
+        let status = Command::new("nmcli")
+            .args(["connection", "delete", name])
+            .status()
+            .await?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(t!(ForgetFailed)))
+        }
+    }
+
+    // ── 无线电 & 热点 ─────────────────────────────────────────
+
+    /// 获取 Wi-Fi 无线电状态
+    async fn radio_state(&self) -> RadioState {
+        let out = Command::new("nmcli")
+            .args(["-fields", "WIFI", "general"])
+            .output()
+            .await
+            .unwrap();
+        let s = String::from_utf8_lossy(&out.stdout);
+        if s.contains("enabled") {
+            RadioState::Enabled
+        } else {
+            RadioState::Disabled
+        }
+    }
+
+    async fn set_radio(&self, enable: bool) -> Result<()> {
+        let arg = if enable { "on" } else { "off" };
+        Command::new("nmcli")
+            .args(["radio", "wifi", arg])
+            .status()
+            .await?;
         Ok(())
-    } else {
-        Err(anyhow!("断开失败"))
     }
-}
 
-/// 删除已保存的 connection profile
-pub async fn delete_connection(name: &str) -> Result<()> {
-    let status = Command::new("nmcli")
-        .args(["connection", "delete", name])
-        .status()
-        .await?;
-    if status.success() {
+    async fn hotspot_active(&self) -> Option<String> {
+        let out = Command::new("nmcli")
+            .args(["-t", "-f", "NAME,DEVICE", "connection", "show", "--active"])
+            .output()
+            .await
+            .ok()?;
+        terse_records(&String::from_utf8_lossy(&out.stdout), 2)
+            .into_iter()
+            .map(|mut f| f.swap_remove(0))
+            .find(|name| is_hotspot_name(name))
+    }
+
+    async fn hotspot_profile(&self) -> Option<String> {
+        let out = Command::new("nmcli")
+            .args(["-t", "-f", "NAME,TYPE", "connection", "show"])
+            .output()
+            .await
+            .ok()?;
+        terse_records(&String::from_utf8_lossy(&out.stdout), 2)
+            .into_iter()
+            .filter(|f| f[1] == "802-11-wireless")
+            .map(|mut f| f.swap_remove(0))
+            .find(|name| is_hotspot_name(name))
+    }
+
+    async fn hotspot_up(&self, name: &str) -> Result<()> {
+        let mut args = vec!["connection".to_string(), "up".into(), name.into()];
+        if let Some(dev) = self.ifaces.hotspot() {
+            args.extend(["ifname".into(), dev]);
+        }
+        Command::new("nmcli").args(&args).status().await?;
         Ok(())
-    } else {
-        Err(anyhow!("删除失败"))
     }
-}
 
-// ── 无线电 & 热点 ─────────────────────────────────────────────
+    async fn hotspot_down(&self, name: &str) -> Result<()> {
+        Command::new("nmcli")
+            .args(["connection", "down", name])
+            .status()
+            .await?;
+        Ok(())
+    }
 
-pub async fn set_radio(enable: bool) -> Result<()> {
-    let arg = if enable { "on" } else { "off" };
-    Command::new("nmcli")
-        .args(["radio", "wifi", arg])
-        .status()
-        .await?;
-    Ok(())
-}
+    async fn create_hotspot(&self, ssid: &str, password: &str) -> Result<()> {
+        // 热点绑定到配置的网卡，另一块网卡可以继续保持连接
+        let dev = self.ifaces.hotspot().unwrap_or_else(|| "*".into());
+        let status = Command::new("nmcli")
+            .args([
+                "con",
+                "add",
+                "type",
+                "wifi",
+                "ifname",
+                &dev,
+                "con-name",
+                "Hotspot",
+                "autoconnect",
+                "no",
+                "ssid",
+                ssid,
+                "802-11-wireless.mode",
+                "ap",
+                "802-11-wireless-security.key-mgmt",
+                "wpa-psk",
+                "802-11-wireless-security.psk",
+                password,
+                "ipv4.method",
+                "shared",
+            ])
+            .status()
+            .await?;
+        if !status.success() {
+            return Err(anyhow!(t!(HotspotCreateFailed)));
+        }
 
-pub async fn hotspot_active() -> Option<String> {
-    let out = Command::new("nmcli")
-        .args(["-t", "-f", "NAME,DEVICE", "connection", "show", "--active"])
-        .output()
-        .await
-        .ok()?;
-    String::from_utf8_lossy(&out.stdout)
-        .lines()
-        .find(|l| {
-            let name = l.split(':').next().unwrap_or("").to_lowercase();
-            name.contains("hotspot") || name.contains("热点")
-        })
-        .map(|l| l.split(':').next().unwrap_or("").to_string())
+        Command::new("nmcli")
+            .args(["con", "up", "Hotspot"])
+            .status()
+            .await?;
+        Ok(())
+    }
+
+    async fn activate_vpn(&self, name: &str) -> Result<()> {
+        let status = Command::new("nmcli")
+            .args(["connection", "up", name])
+            .status()
+            .await?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!(t!(CannotStart, name = name)))
+        }
+    }
 }
 
-pub async fn hotspot_profile() -> Option<String> {
-    let out = Command::new("nmcli")
-        .args(["-t", "-f", "NAME,TYPE", "connection", "show"])
-        .output()
-        .await
-        .ok()?;
-    String::from_utf8_lossy(&out.stdout)
-        .lines()
-        .filter(|l| l.contains("wireless"))
-        .find(|l| {
-            let name = l.split(':').next().unwrap_or("").to_lowercase();
-            name.contains("hotspot") || name.contains("热点")
-        })
-        .map(|l| l.split(':').next().unwrap_or("").to_string())
+// ── terse 输出解析 ───────────────────────────────────────────
+
+/// 拆分 nmcli -t 输出的一行：字段以 ':' 分隔，字段内的 ':' 与 '\' 被转义成 "\:" 和 "\\"
+fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().expect("至少有一个字段");
+        match c {
+            '\\' => field.push(chars.next().unwrap_or('\\')),
+            ':' => fields.push(String::new()),
+            _ => field.push(c),
+        }
+    }
+    fields
 }
 
-pub async fn create_hotspot(ssid: &str, password: &str) -> Result<()> {
-    let status = Command::new("nmcli")
-        .args([
-            "con",
-            "add",
-            "type",
-            "wifi",
-            "ifname",
-            "*",
-            "con-name",
-            "Hotspot",
-            "autoconnect",
-            "no",
-            "ssid",
-            ssid,
-            "802-11-wireless.mode",
-            "ap",
-            "802-11-wireless-security.key-mgmt",
-            "wpa-psk",
-            "802-11-wireless-security.psk",
-            password,
-            "ipv4.method",
-            "shared",
-        ])
-        .status()
-        .await?;
-    if !status.success() {
-        return Err(anyhow!("创建热点失败"));
-    }
-
-    Command::new("nmcli")
-        .args(["con", "up", "Hotspot"])
-        .status()
-        .await?;
-    Ok(())
+/// 按行拆分 terse 输出，只保留字段数正好为 `n` 的记录
+fn terse_records(text: &str, n: usize) -> Vec<Vec<String>> {
+    text.lines()
+        .map(split_terse)
+        .filter(|f| f.len() == n)
+        .collect()
 }
 
-// ── 网络信息 ─────────────────────────────────────────────────
+/// 多行模式（dev show / connection show）中取 `key:value` 的 value
+fn terse_value(text: &str, key: &str) -> Option<String> {
+    terse_records(text, 2)
+        .into_iter()
+        .find(|f| f[0] == key)
+        .map(|mut f| f.swap_remove(1))
+}
 
-pub async fn get_ip() -> Option<String> {
-    // 稍等一下让 DHCP 完成
-    tokio::time::sleep(Duration::from_millis(500)).await;
-    let out = Command::new("nmcli")
-        .args(["-t", "-f", "IP4.ADDRESS", "dev", "show"])
-        .output()
-        .await
-        .ok()?;
-    String::from_utf8_lossy(&out.stdout)
-        .lines()
-        .find(|l| l.contains("IP4.ADDRESS[1]"))
-        .and_then(|l| l.split(':').nth(1))
-        .map(str::to_string)
+/// 多个配置的 `connection show` 输出中 hidden 为 yes 的配置名；每个配置以 connection.id 开头
+fn hidden_profiles(text: &str) -> Vec<String> {
+    let mut hidden = vec![];
+    let mut name = None;
+    for f in terse_records(text, 2) {
+        match (f[0].as_str(), f[1].as_str()) {
+            ("connection.id", id) => name = Some(id.to_string()),
+            ("802-11-wireless.hidden", "yes") => hidden.extend(name.take()),
+            _ => {}
+        }
+    }
+    hidden
 }
 
-#[derive(Debug)]
-pub struct ConnectionDetails {
-    pub ssid: String,
-    pub ip: String,
-    pub gateway: String,
-    pub dns: String,
-    pub security: String,
-    pub signal: String,
-    pub ping_ms: Option<f64>,
+fn is_hotspot_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("hotspot") || name.contains("热点")
 }
 
-pub async fn get_details(ssid: &str, ping_host: &str) -> Result<ConnectionDetails> {
-    // 并发获取设备信息和 ping
-    let (dev_info, ping_ms) = tokio::join!(get_dev_info(), ping_once(ping_host),);
-    let (ip, gateway, dns) = dev_info;
+fn parse_ap_fields(fields: &[String]) -> Option<ScanEntry> {
+    // 字段: IN-USE, BSSID, SSID, MODE, CHAN, FREQ, RATE, SIGNAL, BARS, SECURITY, WPA-FLAGS, RSN-FLAGS
+    // （已按 terse 规则反转义）；隐藏网络的 SSID 为空或 "--"
+    let ssid = match fields[2].as_str() {
+        "--" => String::new(),
+        s => s.to_string(),
+    };
 
-    // 信号强度
-    let signal_out = Command::new("nmcli")
-        .args(["-t", "-f", "IN-USE,SIGNAL", "dev", "wifi"])
-        .output()
-        .await?;
-    let signal = String::from_utf8_lossy(&signal_out.stdout)
-        .lines()
-        .find(|l| l.starts_with("*:"))
-        .and_then(|l| l.split(':').nth(1))
-        .unwrap_or("--")
-        .to_string();
-
-    // 安全类型
-    let sec_out = Command::new("nmcli")
-        .args(["-t", "-f", "IN-USE,SECURITY", "dev", "wifi"])
-        .output()
-        .await?;
-    let security = String::from_utf8_lossy(&sec_out.stdout)
-        .lines()
-        .find(|l| l.starts_with("*:"))
-        .and_then(|l| l.split(':').nth(1))
-        .unwrap_or("--")
-        .to_string();
-
-    Ok(ConnectionDetails {
-        ssid: ssid.to_string(),
-        ip,
-        gateway,
-        dns,
-        security,
-        signal,
-        ping_ms,
+    let frequency = leading_number(&fields[5]);
+    Some(ScanEntry {
+        ssid,
+        // WPA / RSN 都为空时只能靠 SECURITY 列区分 WEP 与开放网络
+        security: Security::from_nm_flags(
+            fields[9].contains("WEP"),
+            nm_flag_bits(&fields[10]),
+            nm_flag_bits(&fields[11]),
+        ),
+        bss: Bss {
+            bssid: fields[1].clone(),
+            channel: leading_number(&fields[4]),
+            frequency,
+            band: Band::from_frequency(frequency),
+            max_rate: leading_number(&fields[6]),
+            mode: match fields[3].trim() {
+                "Infra" => ApMode::Infra,
+                "Ad-Hoc" => ApMode::AdHoc,
+                "Mesh" => ApMode::Mesh,
+                _ => ApMode::Unknown,
+            },
+            signal: fields[7].trim().parse::<u8>().unwrap_or(0),
+            bars: fields[8].trim().to_string(),
+            in_use: fields[0].trim() == "*",
+        },
     })
 }
 
-async fn get_dev_info() -> (String, String, String) {
+/// nmcli 的 WPA-FLAGS / RSN-FLAGS 文字（如 "pair_ccmp group_ccmp psk"）→ 标志位
+fn nm_flag_bits(s: &str) -> u32 {
+    s.split_whitespace()
+        .map(|t| match t {
+            "pair_wep40" => nm_sec::PAIR_WEP40,
+            "pair_wep104" => nm_sec::PAIR_WEP104,
+            "pair_tkip" => nm_sec::PAIR_TKIP,
+            "pair_ccmp" => nm_sec::PAIR_CCMP,
+            "group_wep40" => nm_sec::GROUP_WEP40,
+            "group_wep104" => nm_sec::GROUP_WEP104,
+            "group_tkip" => nm_sec::GROUP_TKIP,
+            "group_ccmp" => nm_sec::GROUP_CCMP,
+            "psk" => nm_sec::KEY_MGMT_PSK,
+            "802.1X" => nm_sec::KEY_MGMT_802_1X,
+            "sae" => nm_sec::KEY_MGMT_SAE,
+            "owe" => nm_sec::KEY_MGMT_OWE,
+            "owe_tm" => nm_sec::KEY_MGMT_OWE_TM,
+            "eap_suite_b_192" => nm_sec::KEY_MGMT_EAP_SUITE_B_192,
+            _ => 0,
+        })
+        .fold(0, |acc, b| acc | b)
+}
+
+/// "5180 MHz" / "540 Mbit/s" → 开头的数字
+fn leading_number(s: &str) -> u32 {
+    s.split_whitespace()
+        .next()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0)
+}
+
+async fn get_dev_info(device: Option<String>) -> (String, String, String) {
     let out = Command::new("nmcli")
         .args(["-t", "-f", "IP4.ADDRESS,IP4.GATEWAY,IP4.DNS", "dev", "show"])
+        .args(device)
         .output()
         .await
         .unwrap();
     let text = String::from_utf8_lossy(&out.stdout);
 
-    let extract = |prefix: &str| -> String {
-        text.lines()
-            .find(|l| l.contains(prefix))
-            .and_then(|l| l.split(':').nth(1))
-            .unwrap_or("N/A")
-            .to_string()
-    };
+    let extract = |key: &str| -> String { terse_value(&text, key).unwrap_or_else(|| "N/A".into()) };
 
-    let dns: String = text
-        .lines()
-        .filter(|l| l.contains("IP4.DNS"))
-        .filter_map(|l| l.split(':').nth(1))
+    let dns: String = terse_records(&text, 2)
+        .into_iter()
+        .filter(|f| f[0].starts_with("IP4.DNS"))
+        .map(|mut f| f.swap_remove(1))
         .collect::<Vec<_>>()
         .join(", ");
 
@@ -413,38 +741,78 @@ async fn get_dev_info() -> (String, String, String) {
     )
 }
 
-/// 单次 ping，返回往返时延毫秒数
-pub async fn ping_once(host: &str) -> Option<f64> {
-    let out = Command::new("ping")
-        .args(["-c", "1", "-W", "2", host])
-        .output()
-        .await
-        .ok()?;
-    let text = String::from_utf8_lossy(&out.stdout);
-    // "rtt min/avg/max/mdev = 1.234/1.234/1.234/0.000 ms"
-    text.lines()
-        .find(|l| l.contains("rtt") || l.contains("round-trip"))
-        .and_then(|l| l.split('/').nth(4))
-        .and_then(|s| s.parse::<f64>().ok())
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-/// 多次 ping 连通性检测，返回 (成功, 平均ms)
-pub async fn ping_check(host: &str, count: u8) -> (bool, Option<f64>) {
-    let out = Command::new("ping")
-        .args(["-c", &count.to_string(), "-W", "2", host])
-        .output()
-        .await;
-    match out {
-        Err(_) => (false, None),
-        Ok(o) if !o.status.success() => (false, None),
-        Ok(o) => {
-            let text = String::from_utf8_lossy(&o.stdout);
-            let ms = text
-                .lines()
-                .find(|l| l.contains("rtt") || l.contains("round-trip"))
-                .and_then(|l| l.split('/').nth(4))
-                .and_then(|s| s.parse::<f64>().ok());
-            (true, ms)
-        }
+    #[test]
+    fn hidden_profiles_from_multi_show() {
+        let text = "connection.id:Home\n802-11-wireless.hidden:no\n\n\
+                    connection.id:Lab\\:5G\n802-11-wireless.hidden:yes\n\n\
+                    connection.id:Attic\n802-11-wireless.hidden:yes\n";
+        assert_eq!(hidden_profiles(text), ["Lab:5G", "Attic"]);
+    }
+
+    #[test]
+    fn split_terse_unescapes() {
+        assert_eq!(split_terse(r"a\:b:c"), ["a:b", "c"]);
+        assert_eq!(split_terse(r"a\\:b"), ["a\\", "b"]);
+        assert_eq!(split_terse(r"a\\\:b"), ["a\\:b"]);
+        // 末尾的空字段也要保留，否则字段数对不上
+        assert_eq!(split_terse("a:b:"), ["a", "b", ""]);
+        assert_eq!(split_terse(""), [""]);
+        // 行尾孤立的反斜杠按字面保留
+        assert_eq!(split_terse(r"a\"), ["a\\"]);
+    }
+
+    #[test]
+    fn scan_line_with_escaped_bssid() {
+        let text = concat!(
+            r"*:AA\:BB\:CC\:DD\:EE\:01:Cafe\: 2.4G:Infra:6:2437 MHz:130 Mbit/s:72:▂▄▆_:WPA2:(none):pair_ccmp group_ccmp psk",
+            "\n",
+            r" :AA\:BB\:CC\:DD\:EE\:02::Infra:36:5180 MHz:540 Mbit/s:40:▂▄__::(none):(none)",
+            "\n",
+            "malformed:line\n",
+        );
+        let records = terse_records(text, 12);
+        assert_eq!(records.len(), 2);
+
+        let cafe = parse_ap_fields(&records[0]).unwrap();
+        assert_eq!(cafe.ssid, "Cafe: 2.4G");
+        assert_eq!(cafe.bss.bssid, "AA:BB:CC:DD:EE:01");
+        assert_eq!(cafe.bss.channel, 6);
+        assert_eq!(cafe.bss.frequency, 2437);
+        assert_eq!(cafe.bss.max_rate, 130);
+        assert_eq!(cafe.bss.signal, 72);
+        assert!(cafe.bss.in_use);
+        assert_eq!(cafe.security, Security::wpa2_psk());
+
+        // SECURITY 列为空（末尾附近的空字段）：开放的隐藏网络
+        let hidden = parse_ap_fields(&records[1]).unwrap();
+        assert_eq!(hidden.ssid, "");
+        assert_eq!(hidden.bss.bssid, "AA:BB:CC:DD:EE:02");
+        assert_eq!(hidden.bss.band, Band::G5);
+        assert!(!hidden.bss.in_use);
+        assert!(hidden.security.is_open());
+    }
+
+    #[test]
+    fn terse_value_multiline() {
+        let text = concat!(
+            "GENERAL.DEVICE:wlan0\n",
+            "IP4.ADDRESS[1]:192.168.1.5/24\n",
+            "IP4.GATEWAY:\n",
+            "IP6.ADDRESS[1]:fe80\\:\\:1/64\n",
+        );
+        assert_eq!(
+            terse_value(text, "GENERAL.DEVICE").as_deref(),
+            Some("wlan0")
+        );
+        assert_eq!(terse_value(text, "IP4.GATEWAY").as_deref(), Some(""));
+        assert_eq!(
+            terse_value(text, "IP6.ADDRESS[1]").as_deref(),
+            Some("fe80::1/64")
+        );
+        assert_eq!(terse_value(text, "IP4.DNS[1]"), None);
     }
 }
@@ -1,8 +1,23 @@
 // src/notify.rs — 桌面通知，降级到 stderr
 
+use std::sync::Mutex;
+
 pub enum Urgency { Low, Normal, Critical }
 
+/// 接管通知的回调（TUI 模式下写进状态栏，不再调用 notify-send）
+pub type Sink = Box<dyn Fn(&Urgency, &str, &str) + Send>;
+
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+pub fn set_sink(sink: Option<Sink>) {
+    if let Ok(mut s) = SINK.lock() { *s = sink; }
+}
+
 pub fn send(urgency: Urgency, title: &str, body: &str) {
+    if let Ok(s) = SINK.lock() {
+        if let Some(sink) = s.as_ref() { return sink(&urgency, title, body); }
+    }
+
     let u = match urgency {
         Urgency::Low      => "low",
         Urgency::Normal   => "normal",
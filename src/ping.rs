@@ -0,0 +1,38 @@
+// src/ping.rs — 连通性检测，与具体网络后端无关
+
+use tokio::process::Command;
+
+/// 单次 ping，返回往返时延毫秒数
+pub async fn ping_once(host: &str) -> Option<f64> {
+    let out = Command::new("ping")
+        .args(["-c", "1", "-W", "2", host])
+        .output()
+        .await
+        .ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    parse_rtt(&text)
+}
+
+/// 多次 ping 连通性检测，返回 (成功, 平均ms)
+pub async fn ping_check(host: &str, count: u8) -> (bool, Option<f64>) {
+    let out = Command::new("ping")
+        .args(["-c", &count.to_string(), "-W", "2", host])
+        .output()
+        .await;
+    match out {
+        Err(_) => (false, None),
+        Ok(o) if !o.status.success() => (false, None),
+        Ok(o) => {
+            let text = String::from_utf8_lossy(&o.stdout);
+            (true, parse_rtt(&text))
+        }
+    }
+}
+
+/// "rtt min/avg/max/mdev = 1.234/1.234/1.234/0.000 ms" → 平均值
+fn parse_rtt(text: &str) -> Option<f64> {
+    text.lines()
+        .find(|l| l.contains("rtt") || l.contains("round-trip"))
+        .and_then(|l| l.split('/').nth(4))
+        .and_then(|s| s.parse::<f64>().ok())
+}
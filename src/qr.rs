@@ -1,25 +1,27 @@
-// src/qr.rs — 用 qrcode crate 生成 UTF-8 块字符二维码
+// src/qr.rs — 用 qrcode crate 生成 Wi-Fi 二维码：UTF-8 块字符、SVG、PNG
 
 use crate::types::Security;
 use anyhow::Result;
-use qrcode::{QrCode, EcLevel};
-use qrcode::render::unicode;
+use clap::ValueEnum;
+use qrcode::{QrCode, EcLevel, Color};
+use qrcode::render::{svg, unicode};
 
-/// 生成 Wi-Fi 连接二维码字符串（UTF-8 块字符）
-pub fn wifi_qr(ssid: &str, password: &str, security: &Security) -> Result<String> {
-    let sec_str = match security {
-        Security::Open => "nopass",
-        Security::Wep  => "WEP",
-        _              => "WPA",
-    };
-
-    // 转义 SSID/密码中的特殊字符（; , " \）
-    let ssid_esc  = escape_wifi_field(ssid);
-    let pass_esc  = escape_wifi_field(password);
+/// 静区宽度（模块数），二维码标准要求至少 4
+const QUIET_ZONE: u32 = 4;
 
-    let qr_data = format!("WIFI:T:{sec_str};S:{ssid_esc};P:{pass_esc};;");
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum Format {
+    Png,
+    Svg,
+    // UTF-8 块字符，与菜单中显示的一致
+    #[default]
+    Terminal,
+}
 
-    let code = QrCode::with_error_correction_level(qr_data.as_bytes(), EcLevel::M)?;
+/// 生成 Wi-Fi 连接二维码字符串（UTF-8 块字符）
+pub fn wifi_qr(ssid: &str, password: &str, security: &Security) -> Result<String> {
+    let code = wifi_code(ssid, password, security)?;
     let image = code
         .render::<unicode::Dense1x2>()
         .quiet_zone(true)
@@ -34,14 +36,258 @@ pub fn wifi_qr(ssid: &str, password: &str, security: &Security) -> Result<String
     Ok(padded)
 }
 
+/// 按格式导出二维码；size 为图片的最小边长（像素），终端格式忽略
+pub fn export(ssid: &str, password: &str, security: &Security, format: Format, size: u32) -> Result<Vec<u8>> {
+    let code = wifi_code(ssid, password, security)?;
+    let out = match format {
+        Format::Png => png(&code, size),
+        Format::Svg => code
+            .render::<svg::Color>()
+            .quiet_zone(true)
+            .min_dimensions(size, size)
+            .build()
+            .into_bytes(),
+        Format::Terminal => (wifi_qr(ssid, password, security)? + "\n").into_bytes(),
+    };
+    Ok(out)
+}
+
+fn wifi_code(ssid: &str, password: &str, security: &Security) -> Result<QrCode> {
+    let qr_data = wifi_payload(ssid, password, security);
+    Ok(QrCode::with_error_correction_level(qr_data.as_bytes(), EcLevel::M)?)
+}
+
+/// 拼出 WIFI:T:..;S:..;P:..;; 负载
+fn wifi_payload(ssid: &str, password: &str, security: &Security) -> String {
+    // 过渡模式按 WPA 生成，兼容只认 WPA2 的设备；纯 WPA3 才用 SAE
+    let sec_str = if security.is_open() || security.is_owe() {
+        "nopass"
+    } else if security.is_wep() {
+        "WEP"
+    } else if security.is_sae_only() {
+        "SAE"
+    } else {
+        "WPA"
+    };
+
+    // 转义 SSID/密码中的特殊字符（; , : " \）
+    let ssid_esc  = escape_wifi_field(ssid);
+    let pass_esc  = escape_wifi_field(password);
+
+    format!("WIFI:T:{sec_str};S:{ssid_esc};P:{pass_esc};;")
+}
+
 /// 转义 Wi-Fi QR 格式中的保留字符
 fn escape_wifi_field(s: &str) -> String {
     let mut out = String::with_capacity(s.len() + 4);
     for c in s.chars() {
         match c {
-            '\\' | ';' | ',' | '"' => { out.push('\\'); out.push(c); }
+            '\\' | ';' | ',' | ':' | '"' => { out.push('\\'); out.push(c); }
             _ => out.push(c),
         }
     }
     out
 }
+
+// ── PNG ──────────────────────────────────────────────────────
+//
+// 二维码只有黑白两色，直接写 1 位灰度 PNG；数据用 zlib 不压缩块（stored）存放，
+// 不必为此引入压缩库，几百像素的图也只有几十 KB。
+
+/// 渲染为 PNG：每个模块 scale×scale 像素，含静区，边长不小于 size
+fn png(code: &QrCode, size: u32) -> Vec<u8> {
+    let modules = code.width() as u32;
+    let colors  = code.to_colors();
+    let total   = modules + 2 * QUIET_ZONE;
+    let scale   = size.div_ceil(total).max(1);
+    let dim     = total * scale;
+
+    // 每行：过滤类型 0 + 按位打包的像素（1 为白）
+    let row_bytes = dim.div_ceil(8) as usize;
+    let mut raw = Vec::with_capacity((row_bytes + 1) * dim as usize);
+    for y in 0..dim {
+        raw.push(0);
+        let my = (y / scale).checked_sub(QUIET_ZONE).filter(|&m| m < modules);
+        let mut row = vec![0xffu8; row_bytes];
+        for x in 0..dim {
+            let mx = (x / scale).checked_sub(QUIET_ZONE).filter(|&m| m < modules);
+            if let (Some(mx), Some(my)) = (mx, my) {
+                if colors[(my * modules + mx) as usize] == Color::Dark {
+                    row[x as usize / 8] &= !(0x80 >> (x % 8));
+                }
+            }
+        }
+        raw.extend_from_slice(&row);
+    }
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&dim.to_be_bytes());
+    ihdr.extend_from_slice(&dim.to_be_bytes());
+    // 位深 1、灰度、deflate、标准过滤、不隔行
+    ihdr.extend_from_slice(&[1, 0, 0, 0, 0]);
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_chunk(&mut out, b"IHDR", &ihdr);
+    png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    png_chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+/// 长度 + 类型 + 数据 + CRC（覆盖类型和数据）
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// zlib 流，deflate 部分全部是不压缩块（每块最多 65535 字节）
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len  = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::KeyMgmt;
+
+    fn key_mgmt(key_mgmt: Vec<KeyMgmt>) -> Security {
+        Security { key_mgmt, ..Default::default() }
+    }
+
+    #[test]
+    fn payload_escapes_reserved_characters() {
+        assert_eq!(
+            wifi_payload(r#"a;b,c:d\e"f"#, "p;w:d", &Security::wpa2_psk()),
+            r#"WIFI:T:WPA;S:a\;b\,c\:d\\e\"f;P:p\;w\:d;;"#
+        );
+        assert_eq!(wifi_payload("中文", "", &Security::default()), "WIFI:T:nopass;S:中文;P:;;");
+    }
+
+    #[test]
+    fn payload_security_type() {
+        let t = |sec: Security| {
+            let payload = wifi_payload("x", "y", &sec);
+            payload["WIFI:T:".len()..payload.find(";S:").unwrap()].to_string()
+        };
+        assert_eq!(t(Security::default()), "nopass");
+        assert_eq!(t(key_mgmt(vec![KeyMgmt::Owe])), "nopass");
+        assert_eq!(t(Security::wep()), "WEP");
+        assert_eq!(t(Security::wpa2_psk()), "WPA");
+        // 纯 WPA3 用 SAE，WPA2/WPA3 过渡模式仍按 WPA
+        assert_eq!(t(key_mgmt(vec![KeyMgmt::Sae])), "SAE");
+        assert_eq!(t(key_mgmt(vec![KeyMgmt::Psk, KeyMgmt::Sae])), "WPA");
+    }
+
+    #[test]
+    fn checksums_match_known_vectors() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        // 每个 PNG 文件末尾都是这个 CRC
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn zlib_stored_blocks() {
+        assert_eq!(zlib_stored(&[]), [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]);
+
+        // 超过 65535 字节拆成两块，只有最后一块置 BFINAL
+        let data = vec![0xabu8; 70_000];
+        let out = zlib_stored(&data);
+        assert_eq!(out[..7], [0x78, 0x01, 0, 0xff, 0xff, 0, 0]);
+        let second = 7 + 0xffff;
+        let rest = (70_000 - 0xffff) as u16;
+        assert_eq!(out[second], 1);
+        assert_eq!(out[second + 1..second + 3], rest.to_le_bytes());
+        assert_eq!(out[second + 3..second + 5], (!rest).to_le_bytes());
+        assert_eq!(out[out.len() - 4..], adler32(&data).to_be_bytes());
+        assert_eq!(out.len(), 2 + 5 * 2 + data.len() + 4);
+    }
+
+    #[test]
+    fn png_structure() {
+        let code = wifi_code("Home", "password", &Security::wpa2_psk()).unwrap();
+        let png = png(&code, 200);
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+
+        // 逐块检查 CRC，并收集块类型
+        let mut chunks = vec![];
+        let mut pos = 8;
+        while pos < png.len() {
+            let len = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+            let body = &png[pos + 4..pos + 8 + len];
+            let crc = u32::from_be_bytes(png[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(body));
+            chunks.push((body[..4].to_vec(), body[4..].to_vec()));
+            pos += 12 + len;
+        }
+        assert_eq!(pos, png.len());
+        let kinds: Vec<_> = chunks.iter().map(|(k, _)| k.as_slice()).collect();
+        assert_eq!(kinds, [b"IHDR", b"IDAT", b"IEND"]);
+
+        // 边长为模块数（含静区）的整数倍且不小于 size
+        let ihdr = &chunks[0].1;
+        let dim = u32::from_be_bytes(ihdr[..4].try_into().unwrap());
+        let total = code.width() as u32 + 2 * QUIET_ZONE;
+        assert_eq!(ihdr[4..8], dim.to_be_bytes());
+        assert_eq!(ihdr[8..], [1, 0, 0, 0, 0]);
+        assert!(dim >= 200 && dim % total == 0);
+
+        // IDAT 解出的原始数据：每行一个过滤字节 + 按位打包的像素
+        let idat = &chunks[1].1;
+        let mut raw = vec![];
+        let mut p = 2;
+        loop {
+            let last = idat[p] & 1 == 1;
+            let len = u16::from_le_bytes([idat[p + 1], idat[p + 2]]) as usize;
+            raw.extend_from_slice(&idat[p + 5..p + 5 + len]);
+            p += 5 + len;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(idat[p..], adler32(&raw).to_be_bytes());
+        let row = dim.div_ceil(8) as usize + 1;
+        assert_eq!(raw.len(), row * dim as usize);
+        // 第一行在静区内，全白
+        assert!(raw[1..row].iter().all(|&b| b == 0xff));
+    }
+}
@@ -1,151 +1,162 @@
-// src/rofi.rs — 所有 rofi 调用封装
+// src/rofi.rs — rofi 前端
+//
+// 行数和宽度通过 -theme-str 设置（rofi 1.7 起 -lines / -width 已弃用），
+// 放在用户的 theme_str 之前，因此用户主题片段可以覆盖它们。
 
-use crate::config::Config;
-use tokio::io::AsyncWriteExt;
-use tokio::process::Command;
+use crate::config::{Config, MenuStyle};
+use crate::i18n::t;
+use crate::menu::{self, Menu, Role, SelectOpts, Selection};
+use crate::template;
+use async_trait::async_trait;
 
-/// 通用 rofi dmenu，返回用户选择的行，Esc 返回 None
-pub async fn dmenu(
-    items: &[String],
-    prompt: &str,
-    cfg: &Config,
-    extra: &[&str], // 额外参数，如 -mesg、-a、-password
-) -> Option<String> {
-    let input = items.join("\n");
-    let mut args = vec![
-        "-dmenu".to_string(),
-        "-p".to_string(),
-        prompt.to_string(),
-        "-font".to_string(),
-        cfg.font.clone(),
-        "-location".to_string(),
-        cfg.position.to_string(),
-        "-yoffset".to_string(),
-        cfg.y_offset.to_string(),
-        "-xoffset".to_string(),
-        cfg.x_offset.to_string(),
-    ];
-    for e in extra {
-        args.push(e.to_string());
-    }
-
-    let mut child = Command::new("rofi")
-        .args(&args)
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .spawn()
-        .ok()?;
+pub struct Rofi {
+    cfg: Config,
+}
 
-    // 异步写入候选项，写完后必须 drop/关闭 stdin
-    // 否则 rofi 会一直等待更多输入而不显示界面
-    if let Some(mut stdin) = child.stdin.take() {
-        let _ = stdin.write_all(input.as_bytes()).await;
-        // write_all 完成后 stdin 在此 drop，触发 EOF，rofi 才会渲染列表
+impl Rofi {
+    pub fn new(cfg: Config) -> Self {
+        Self { cfg }
     }
 
-    let out = child.wait_with_output().await.ok()?;
-    if out.status.success() {
-        let s = String::from_utf8_lossy(&out.stdout).trim().to_string();
-        if s.is_empty() {
-            None
-        } else {
-            Some(s)
+    fn style(&self, role: Role) -> &MenuStyle {
+        let menus = &self.cfg.menus;
+        match role {
+            Role::Main => &menus.main,
+            Role::Password => &menus.password,
+            Role::Confirm => &menus.confirm,
+            Role::Info => &menus.info,
+            Role::Qr => &menus.qr,
         }
-    } else {
-        None // 用户按了 Esc
     }
-}
 
-/// 单行密码输入（显示为圆点）
-pub async fn password_prompt(hint: &str, cfg: &Config) -> Option<String> {
-    let prompt = format!(
-        "🔒 密码{}: ",
-        if hint.is_empty() {
-            String::new()
-        } else {
-            format!(" ({hint})")
+    /// 公共参数 + 主题 + 调用方的额外参数；layout 为行数、宽度等主题片段
+    fn args(&self, prompt: &str, role: Role, layout: &[String], extra: Vec<String>) -> Vec<String> {
+        let style = self.style(role);
+        let font = style.font.as_ref().unwrap_or(&self.cfg.font);
+        let mut args: Vec<String> = vec![
+            "-dmenu".into(),
+            "-p".into(),
+            prompt.into(),
+            "-font".into(),
+            font.clone(),
+            "-location".into(),
+            self.cfg.position.to_string(),
+            "-yoffset".into(),
+            self.cfg.y_offset.to_string(),
+            "-xoffset".into(),
+            self.cfg.x_offset.to_string(),
+        ];
+        if let Some(theme) = style.theme.as_ref().or(self.cfg.theme.as_ref()) {
+            args.extend(["-theme".into(), theme.clone()]);
+        }
+        let theme_str: Vec<&str> = layout
+            .iter()
+            .map(String::as_str)
+            .chain(self.cfg.theme_str.as_deref())
+            .chain(style.theme_str.as_deref())
+            .collect();
+        if !theme_str.is_empty() {
+            args.extend(["-theme-str".into(), theme_str.join(" ")]);
         }
-    );
-    dmenu(&[], &prompt, cfg, &["-password", "-lines", "0"]).await
+        args.extend(extra);
+        args.extend(self.cfg.extra_args.iter().cloned());
+        args.extend(style.extra_args.iter().cloned());
+        args
+    }
 }
 
-/// 单行文本输入
-pub async fn input_prompt(prompt: &str, cfg: &Config) -> Option<String> {
-    dmenu(&[], prompt, cfg, &["-lines", "1"]).await
+/// 显示行数
+fn lines(n: usize) -> String {
+    format!("listview {{ lines: {n}; }}")
 }
 
-/// 二选一确认（返回 true = 确认）
-pub async fn confirm(message: &str, cfg: &Config) -> bool {
-    let items = vec!["是".to_string(), "否".to_string()];
-    matches!(
-        dmenu(&items, message, cfg, &["-lines", "2"])
-            .await
-            .as_deref(),
-        Some("是")
-    )
+/// 按字符计的窗口宽度
+fn width(chars: usize) -> String {
+    format!("window {{ width: {chars}ch; }}")
 }
 
-/// 在 rofi -mesg 区域显示 UTF-8 二维码
-pub async fn show_qr(ssid: &str, qr_text: &str, cfg: &Config) {
-    let qr_width = qr_text
-        .lines()
-        .next()
-        .map(|l| l.chars().count())
-        .unwrap_or(40);
-    let rofi_width = (qr_width + 4).to_string();
-
-    let items = vec!["── 按 Esc 或 Enter 关闭 ──".to_string()];
-    let prompt = format!("📷 {ssid}");
-    let rofi_width = &format!("-{rofi_width}");
-    let extra = vec![
-        "-mesg",
-        qr_text,
-        "-lines",
-        "1",
-        "-font",
-        "Monospace 9",
-        "-width",
-        rofi_width,
-        "-no-custom",
-    ];
+#[async_trait]
+impl Menu for Rofi {
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut layout: Vec<String> = vec![];
+        if let Some(n) = opts.lines {
+            layout.push(lines(n));
+        }
+        if let Some(w) = opts.width {
+            layout.push(width(w));
+        }
 
-    // show_qr 不关心返回值
-    let _ = dmenu(&items, &prompt, cfg, &extra).await;
-}
+        // 输出 "下标 文字"，自由输入时下标为 -1
+        let mut extra: Vec<String> = vec!["-format".into(), "i s".into()];
+        if let Some(a) = opts.active {
+            extra.extend(["-a".into(), a.to_string()]);
+        }
+        if !opts.urgent.is_empty() {
+            let rows: Vec<String> = opts.urgent.iter().map(usize::to_string).collect();
+            extra.extend(["-u".into(), rows.join(",")]);
+        }
+        if let Some(msg) = opts.message {
+            extra.extend(["-mesg".into(), msg.into()]);
+        }
+        if opts.no_custom {
+            extra.push("-no-custom".into());
+        }
+        if opts.markup {
+            extra.push("-markup-rows".into());
+        }
+        // rofi 最多 19 个自定义键，按下第 N 个时以 10+N-1 退出
+        for (n, key) in opts.keys.iter().take(19).enumerate() {
+            extra.extend([format!("-kb-custom-{}", n + 1), key.clone()]);
+        }
 
-/// 显示连接详情（只读，不需要选择）
-pub async fn show_info(title: &str, content: &str, cfg: &Config) {
-    let lines: Vec<String> = content.lines().map(str::to_string).collect();
-    let extra = vec!["-no-custom", "-mesg", "按 Esc 关闭"];
-    let _ = dmenu(&lines, title, cfg, &extra).await;
-}
+        let args = self.args(prompt, opts.role, &layout, extra);
+        let (code, out) = menu::run_status("rofi", &args, items).await?;
+        let (index, text) = out.split_once(' ').unwrap_or((&out, ""));
+        let row = index.parse::<usize>().ok().filter(|&i| i < items.len());
+        match (code, row) {
+            (0, Some(i)) => Some(Selection::Row(i)),
+            (0, None) if !out.is_empty() => Some(Selection::Custom(text.to_string())),
+            (10..=28, Some(i)) => Some(Selection::Key(code as usize - 10, i)),
+            // 在输入行而非列表行上按快捷键：当作普通输入
+            (10..=28, None) => Some(Selection::Custom(text.to_string())),
+            _ => None,
+        }
+    }
 
-/// 构建带高亮和宽度的主菜单
-pub async fn main_menu(
-    items: &[String],
-    prompt: &str,
-    cfg: &Config,
-    highlight: Option<usize>,  // 高亮行（0-indexed）
-    warning_msg: Option<&str>, // 顶部警告文字
-    max_lines: usize,
-) -> Option<String> {
-    let width = items.iter().map(|s| s.chars().count()).max().unwrap_or(40) + 4;
+    fn supports_keys(&self) -> bool {
+        true
+    }
 
-    let mut extra: Vec<String> = vec![
-        "-lines".into(),
-        max_lines.to_string(),
-        "-width".into(),
-        format!("-{width}"),
-    ];
-    if let Some(hl) = highlight {
-        extra.push("-a".into());
-        extra.push(hl.to_string());
+    fn supports_markup(&self) -> bool {
+        true
     }
-    if let Some(msg) = warning_msg {
-        extra.push("-mesg".into());
-        extra.push(msg.to_string());
+
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let layout = ["listview { enabled: false; }".to_string()];
+        let args = self.args(prompt, Role::Password, &layout, vec!["-password".into()]);
+        menu::run("rofi", &args, &[]).await
     }
 
-    let extra_refs: Vec<&str> = extra.iter().map(String::as_str).collect();
-    dmenu(items, prompt, cfg, &extra_refs).await
+    /// 在 -mesg 区域显示二维码，动作放在列表里；字体等见 [menus.qr]
+    async fn show_qr(&self, ssid: &str, qr_text: &str, actions: &[String]) -> Option<usize> {
+        let qr_width = qr_text.lines().next().map(template::width).unwrap_or(40);
+        let mut items = actions.to_vec();
+        items.push(t!(CloseRow).to_string());
+        let layout = [lines(items.len()), width(qr_width + 4)];
+        let extra: Vec<String> = vec![
+            "-mesg".into(),
+            qr_text.into(),
+            "-no-custom".into(),
+            "-format".into(),
+            "i".into(),
+        ];
+        let args = self.args(&format!("📷 {ssid}"), Role::Qr, &layout, extra);
+        let out = menu::run("rofi", &args, &items).await?;
+        out.parse::<usize>().ok().filter(|&i| i < actions.len())
+    }
 }
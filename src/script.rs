@@ -0,0 +1,370 @@
+// src/script.rs — rofi script 模式：rofi -show wifi -modi wifi:rofi-rwifi
+//
+// rofi 每次选择都会重新执行脚本，而菜单流程是一段连续的 async 代码。
+// 因此第一次调用时在后台启动一个会话进程跑完整的 Nav 流程，
+// 之后每次脚本调用只把选择转发给会话、再把下一屏打印给 rofi。
+
+use crate::config::Config;
+use crate::i18n::t;
+use crate::menu::{Frontend, Menu, Role, SelectOpts, Selection};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::process::CommandExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, Mutex};
+
+/// rofi 关闭后（Esc 不会回调脚本）会话最多再等这么久
+const IDLE_TIMEOUT: Duration = Duration::from_secs(600);
+/// 子菜单末尾返回行的 ROFI_INFO
+const BACK_INFO: &str = "back";
+
+/// rofi 回传的一次选择：(ROFI_RETV, 选中行文字, ROFI_INFO)
+type Choice = (u32, Option<String>, Option<String>);
+
+/// 脚本调用发给会话的请求
+#[derive(Serialize, Deserialize)]
+enum Request {
+    /// 新窗口打开：要当前这一屏
+    Show,
+    /// 用户的选择：ROFI_RETV、选中行文字、ROFI_INFO
+    Reply {
+        retv: u32,
+        text: Option<String>,
+        info: Option<String>,
+    },
+    /// 结束会话（新的 rofi 窗口打开时清理旧会话）
+    Quit,
+}
+
+/// 会话返回的一屏菜单
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Screen {
+    prompt: String,
+    message: Option<String>,
+    /// 多行内容（如二维码），作为不可选的行放在最前；active / urgent 不计这些行
+    header: Vec<String>,
+    active: Option<usize>,
+    urgent: Vec<usize>,
+    no_custom: bool,
+    markup: bool,
+    /// (显示文字, ROFI_INFO)
+    rows: Vec<(String, String)>,
+}
+
+impl Screen {
+    /// 按 rofi script 协议输出：模式选项以 \0 开头，行选项用 \0 / \x1f 分隔
+    fn render(&self) -> String {
+        let prompt = one_line(self.prompt.trim_end_matches([' ', ':']));
+        let mut out = format!("\0prompt\x1f{prompt}\n");
+        if let Some(msg) = &self.message {
+            out += &format!("\0message\x1f{}\n", one_line(msg));
+        }
+        let offset = self.header.len();
+        if let Some(a) = self.active {
+            out += &format!("\0active\x1f{}\n", a + offset);
+        }
+        if !self.urgent.is_empty() {
+            let rows: Vec<String> = self
+                .urgent
+                .iter()
+                .map(|r| (r + offset).to_string())
+                .collect();
+            out += &format!("\0urgent\x1f{}\n", rows.join(","));
+        }
+        out += &format!("\0no-custom\x1f{}\n", self.no_custom);
+        out += &format!("\0markup-rows\x1f{}\n", self.markup);
+        for line in &self.header {
+            out += &format!("{}\0nonselectable\x1ftrue\n", one_line(line));
+        }
+        for (text, info) in &self.rows {
+            out += &format!("{}\0info\x1f{}\n", one_line(text), one_line(info));
+        }
+        out
+    }
+}
+
+/// rofi 逐行读取脚本输出，换行和 \0 会把一项拆开
+fn one_line(s: &str) -> String {
+    s.replace(['\n', '\0'], " ")
+}
+
+/// 是否被 rofi 当作 script 模式调用
+pub fn is_active() -> bool {
+    std::env::var_os("ROFI_RETV").is_some()
+}
+
+// ── 脚本端 ───────────────────────────────────────────────────
+
+/// 一次脚本调用：转发选择，打印下一屏；会话结束时什么都不打印，rofi 随之关闭
+pub async fn client() -> Result<()> {
+    let retv: u32 = std::env::var("ROFI_RETV")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let path = Config::script_socket_path()?;
+
+    let request = if retv == 0 {
+        // 新窗口：旧会话可能停在某个子菜单里，直接换一个新的
+        if let Ok(old) = UnixStream::connect(&path).await {
+            let _ = send(old, &Request::Quit).await;
+        }
+        spawn_session()?;
+        Request::Show
+    } else {
+        Request::Reply {
+            retv,
+            text: std::env::args().nth(1),
+            info: std::env::var("ROFI_INFO").ok(),
+        }
+    };
+
+    let stream = connect(&path).await?;
+    if let Some(screen) = send(stream, &request).await? {
+        print!("{}", screen.render());
+    }
+    Ok(())
+}
+
+/// 启动后台会话进程；stdout 不能继承，否则 rofi 会一直等管道关闭
+fn spawn_session() -> Result<()> {
+    let _ = std::fs::remove_file(Config::script_socket_path()?);
+    std::process::Command::new(std::env::current_exe()?)
+        .arg("script-session")
+        .env_remove("ROFI_RETV")
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .process_group(0)
+        .spawn()?;
+    Ok(())
+}
+
+/// 等会话进程绑定好套接字
+async fn connect(path: &std::path::Path) -> Result<UnixStream> {
+    for _ in 0..50 {
+        if let Ok(s) = UnixStream::connect(path).await {
+            return Ok(s);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Err(anyhow!(t!(SessionUnreachable, path = path.display())))
+}
+
+/// 发一条请求，读回一屏（会话结束时为 None）
+async fn send(mut stream: UnixStream, req: &Request) -> Result<Option<Screen>> {
+    let mut line = serde_json::to_string(req)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).await?;
+    if reply.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(&reply)?))
+}
+
+// ── 会话端 ───────────────────────────────────────────────────
+
+/// 在会话进程中运行菜单流程，flow 拿到的前端把每次选择都交给 rofi
+pub async fn serve<F, Fut>(flow: F) -> Result<()>
+where
+    F: FnOnce(Frontend) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let path = Config::script_socket_path()?;
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+    // 套接字会收到输入的密码，只允许自己连接
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    let (screen_tx, mut screen_rx) = mpsc::channel(1);
+    let (reply_tx, reply_rx) = mpsc::channel(1);
+    let ui: Frontend = Arc::new(ScriptMenu {
+        screens: screen_tx,
+        replies: Mutex::new(reply_rx),
+    });
+    let flow = flow(ui);
+    tokio::pin!(flow);
+
+    // 最近一屏，供 Show 请求使用
+    let mut current: Option<Screen> = None;
+    loop {
+        let stream = tokio::select! {
+            res = &mut flow => return res,
+            s = screen_rx.recv() => {
+                current = s;
+                continue;
+            }
+            conn = tokio::time::timeout(IDLE_TIMEOUT, listener.accept()) => match conn {
+                Ok(Ok((stream, _))) => stream,
+                _ => return Ok(()),
+            },
+        };
+
+        let (read, mut write) = stream.into_split();
+        let mut line = String::new();
+        BufReader::new(read).read_line(&mut line).await?;
+        let forward = match serde_json::from_str(&line)? {
+            Request::Quit => return Ok(()),
+            Request::Show => None,
+            Request::Reply { retv, text, info } => Some((retv, text, info)),
+        };
+        if let Some(reply) = forward {
+            let _ = reply_tx.send(reply).await;
+            current = None;
+        }
+
+        // 等流程走到下一次选择；流程结束则回空行让 rofi 关闭
+        if current.is_none() {
+            tokio::select! {
+                res = &mut flow => {
+                    let _ = write.write_all(b"\n").await;
+                    return res;
+                }
+                s = screen_rx.recv() => current = s,
+            }
+        }
+        if let Some(screen) = &current {
+            let mut out = serde_json::to_string(screen)?;
+            out.push('\n');
+            let _ = write.write_all(out.as_bytes()).await;
+        }
+    }
+}
+
+/// 会话中的前端：select 把一屏发给下一次脚本调用，再等 rofi 回传选择
+struct ScriptMenu {
+    screens: mpsc::Sender<Screen>,
+    replies: Mutex<mpsc::Receiver<Choice>>,
+}
+
+#[async_trait]
+impl Menu for ScriptMenu {
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut rows: Vec<(String, String)> = items
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.clone(), i.to_string()))
+            .collect();
+        // 子菜单里 Esc 会直接关掉 rofi，所以额外给一个返回行
+        if !opts.root {
+            rows.push((t!(BackRow).into(), BACK_INFO.into()));
+        }
+        // 消息区只能放一行，多行消息改为列表最前的不可选行
+        let (message, header) = match opts.message {
+            Some(m) if m.contains('\n') => (None, m.lines().map(str::to_string).collect()),
+            m => (m.map(str::to_string), vec![]),
+        };
+        let screen = Screen {
+            prompt: prompt.to_string(),
+            message,
+            header,
+            active: opts.active,
+            urgent: opts.urgent.to_vec(),
+            no_custom: opts.no_custom,
+            markup: opts.markup,
+            rows,
+        };
+        self.screens.send(screen).await.ok()?;
+
+        let (retv, text, info) = self.replies.lock().await.recv().await?;
+        let row = info
+            .as_deref()
+            .and_then(|i| i.parse().ok())
+            .filter(|&i| i < items.len());
+        match (retv, info.as_deref(), row) {
+            (_, Some(BACK_INFO), _) => None,
+            // 自定义快捷键：ROFI_RETV = 10 + (N - 1)
+            (10..=28, _, Some(i)) => Some(Selection::Key(retv as usize - 10, i)),
+            (_, Some(_), Some(i)) => Some(Selection::Row(i)),
+            (_, Some(_), None) => None,
+            // 自定义输入（ROFI_RETV=2）没有 info
+            (_, None, _) => Some(Selection::Custom(text.unwrap_or_default())),
+        }
+    }
+
+    /// 快捷键需要在启动 rofi 时用 -kb-custom-N 传入，见 README
+    fn supports_keys(&self) -> bool {
+        true
+    }
+
+    fn supports_markup(&self) -> bool {
+        true
+    }
+
+    /// script 模式没有密码框，只能当普通输入
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let opts = SelectOpts {
+            message: Some(t!(ScriptInputVisible)),
+            ..Default::default()
+        };
+        Some(self.select(&[], prompt, opts).await?.text(&[]))
+    }
+
+    async fn show_info(&self, title: &str, content: &str) {
+        let lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let opts = SelectOpts {
+            no_custom: true,
+            role: Role::Info,
+            ..Default::default()
+        };
+        let _ = self.select(&lines, title, opts).await;
+    }
+
+    /// 二维码作为不可选的行放在动作前面（消息区只能放一行）
+    async fn show_qr(&self, ssid: &str, qr_text: &str, actions: &[String]) -> Option<usize> {
+        let opts = SelectOpts {
+            message: Some(qr_text),
+            no_custom: true,
+            role: Role::Qr,
+            ..Default::default()
+        };
+        match self.select(actions, &format!("📷 {ssid}"), opts).await? {
+            Selection::Row(i) => Some(i),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiline_content_becomes_nonselectable_rows() {
+        let screen = Screen {
+            prompt: "QR:".into(),
+            header: vec!["██ ▀▀".into(), "▄▄ ██".into()],
+            active: Some(0),
+            urgent: vec![1],
+            no_custom: true,
+            rows: vec![
+                ("Save\nas PNG".into(), "0".into()),
+                ("Open".into(), "1".into()),
+            ],
+            ..Default::default()
+        };
+        let out = screen.render();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "\0prompt\x1fQR");
+        // 行号越过两行二维码
+        assert!(lines.contains(&"\0active\x1f2"));
+        assert!(lines.contains(&"\0urgent\x1f3"));
+        let rows = &lines[lines.len() - 4..];
+        assert_eq!(rows[0], "██ ▀▀\0nonselectable\x1ftrue");
+        assert_eq!(rows[1], "▄▄ ██\0nonselectable\x1ftrue");
+        assert_eq!(rows[2], "Save as PNG\0info\x1f0");
+        assert_eq!(rows[3], "Open\0info\x1f1");
+    }
+}
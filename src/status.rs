@@ -0,0 +1,230 @@
+// src/status.rs — `status` 子命令：给 waybar / polybar / i3blocks 等状态栏输出当前 Wi-Fi 状态
+//
+// --watch 时定时采样，状态变化才输出新的一行，配合 waybar exec、polybar tail、i3blocks persist 使用。
+
+use crate::backend::Backend;
+use crate::config::Config;
+use crate::i18n::t;
+use crate::markup;
+use crate::ping;
+use crate::types::RadioState;
+use clap::ValueEnum;
+use serde_json::json;
+use std::os::unix::process::CommandExt;
+use std::time::{Duration, Instant};
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum Format {
+    // waybar custom 模块（return-type = "json"）
+    Waybar,
+    // 带点击动作标签的单行文字
+    Polybar,
+    // full_text / short_text / color 三行
+    I3blocks,
+    #[default]
+    Text,
+}
+
+/// 一次采样的结果；只有这些字段变化才算状态变化
+#[derive(Debug, Clone, PartialEq)]
+struct State {
+    radio: bool,
+    ssid: Option<String>,
+    signal: u8,
+    ip: Option<String>,
+    online: bool,
+    hotspot: Option<String>,
+}
+
+/// --watch 下两次连通性检测的最短间隔；SSID 变化时立即重测
+const PING_INTERVAL: Duration = Duration::from_secs(60);
+
+impl State {
+    /// 只读活动连接和设备状态；recheck 为 false 且 SSID 未变时沿用 prev 的连通性
+    async fn read(cfg: &Config, net: &Backend, prev: Option<&State>, recheck: bool) -> Self {
+        let (radio, link, hotspot) =
+            tokio::join!(net.radio_state(), net.current_link(), net.hotspot_active(),);
+        let (ssid, signal) = link.unzip();
+        let cached = prev
+            .filter(|p| !recheck && p.ssid == ssid)
+            .map(|p| p.online);
+        let (ip, online) = match &ssid {
+            Some(_) => tokio::join!(net.get_ip(), async {
+                match cached {
+                    Some(online) => online,
+                    None => ping::ping_once(&cfg.ping_host).await.is_some(),
+                }
+            }),
+            None => (None, false),
+        };
+        Self {
+            radio: radio == RadioState::Enabled,
+            ssid,
+            signal: signal.unwrap_or(0),
+            ip,
+            online,
+            hotspot,
+        }
+    }
+
+    /// 状态类别，用作 waybar 的 class
+    fn class(&self) -> &'static str {
+        if !self.radio {
+            "disabled"
+        } else if self.hotspot.is_some() {
+            "hotspot"
+        } else if self.ssid.is_none() {
+            "disconnected"
+        } else if !self.online {
+            "no-internet"
+        } else {
+            "connected"
+        }
+    }
+
+    /// 状态栏上的短文字
+    fn text(&self) -> String {
+        match (self.class(), &self.hotspot, &self.ssid) {
+            ("disabled", _, _) => t!(StatusOff).to_string(),
+            ("hotspot", Some(name), _) => format!("📡 {name}"),
+            (_, _, Some(ssid)) if self.online => format!("{ssid} {}%", self.signal),
+            (_, _, Some(ssid)) => format!("{ssid} {}% ⚠", self.signal),
+            _ => t!(StatusDisconnected).to_string(),
+        }
+    }
+
+    /// 悬停提示：SSID、信号、IP、连通性、热点
+    fn tooltip(&self) -> String {
+        let mut lines = vec![];
+        match &self.ssid {
+            Some(ssid) => {
+                lines.push(t!(
+                    StatusTooltip,
+                    ssid = ssid,
+                    signal = self.signal,
+                    ip = self.ip.as_deref().unwrap_or(t!(Unknown))
+                ));
+                let online = if self.online {
+                    t!(Online)
+                } else {
+                    t!(NoInternet)
+                };
+                lines.push(online.to_string());
+            }
+            None if self.radio => lines.push(t!(NotConnected).to_string()),
+            None => lines.push(t!(StatusOff).to_string()),
+        }
+        if let Some(name) = &self.hotspot {
+            lines.push(format!("{}: {name}", t!(HotspotOn)));
+        }
+        lines.join("\n")
+    }
+
+    fn render(&self, format: Format, cfg: &Config, watch: bool) -> String {
+        match format {
+            Format::Text => self.text(),
+            // waybar 把 text / tooltip 当作 Pango 标记
+            Format::Waybar => json!({
+                "text": markup::escape(&self.text()),
+                "tooltip": markup::escape(&self.tooltip()),
+                "class": self.class(),
+                "percentage": self.signal,
+            })
+            .to_string(),
+            // 左键打开菜单；文字中的 %{ 会被当作格式标签，需转义
+            Format::Polybar => {
+                let exe = menu_command().replace(':', "\\:");
+                let text = self.text().replace("%{", "%%{");
+                format!("%{{A1:{exe} &:}}{text}%{{A}}")
+            }
+            // persist 模式下每行都是新的 full_text
+            Format::I3blocks if watch => self.text(),
+            Format::I3blocks => {
+                let color = match self.class() {
+                    "connected" => cfg.colors.signal(self.signal),
+                    "hotspot" => "",
+                    _ => &cfg.colors.risky,
+                };
+                let short = self.ssid.clone().unwrap_or_else(|| self.text());
+                format!("{}\n{short}\n{color}", self.text())
+            }
+        }
+    }
+}
+
+/// 打开菜单所用的命令（当前可执行文件）
+fn menu_command() -> String {
+    std::env::current_exe()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| "rofi-rwifi".into())
+}
+
+/// 在后台打开菜单，不占用状态栏的 stdout
+fn open_menu() {
+    let _ = std::process::Command::new(menu_command())
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .process_group(0)
+        .spawn();
+}
+
+/// 输出一次状态；watch 时每 interval 采样一次，状态变化才输出，连通性最多每 PING_INTERVAL 测一次
+pub async fn run(cfg: &Config, net: &Backend, format: Format, watch: bool, interval: u64) {
+    // i3blocks 点击时带 BLOCK_BUTTON 重新执行本命令，左键打开菜单
+    if format == Format::I3blocks && std::env::var("BLOCK_BUTTON").as_deref() == Ok("1") {
+        open_menu();
+    }
+
+    let interval = Duration::from_secs(interval.max(1));
+    let mut last: Option<State> = None;
+    let mut last_ping: Option<Instant> = None;
+    loop {
+        let recheck = last_ping.is_none_or(|t| t.elapsed() >= PING_INTERVAL);
+        if recheck {
+            last_ping = Some(Instant::now());
+        }
+        let state = State::read(cfg, net, last.as_ref(), recheck).await;
+        if last.as_ref() != Some(&state) {
+            println!("{}", state.render(format, cfg, watch));
+            last = Some(state);
+        }
+        if !watch {
+            return;
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::fake::FakeBackend;
+    use crate::types::{group_by_ssid, Bss, ScanEntry, Security};
+
+    #[tokio::test]
+    async fn reuses_connectivity_between_pings() {
+        let aps = group_by_ssid(vec![ScanEntry {
+            ssid: "Home".into(),
+            security: Security::wpa2_psk(),
+            bss: Bss::new("AA:BB:CC:DD:EE:01".into(), 5180, 70, true),
+        }]);
+        let net: Backend = FakeBackend::new(aps, &["Home"]);
+        // 不可达的地址：真的去 ping 就会是离线
+        let cfg = Config {
+            ping_host: "192.0.2.1".into(),
+            ..Config::default()
+        };
+        let prev = State {
+            radio: true,
+            ssid: Some("Home".into()),
+            signal: 70,
+            ip: None,
+            online: true,
+            hotspot: None,
+        };
+        let state = State::read(&cfg, &net, Some(&prev), false).await;
+        assert_eq!(state, prev);
+    }
+}
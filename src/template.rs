@@ -0,0 +1,183 @@
+// src/template.rs — 行模板：{name}、{name:<20}、{name:>3}、{name:*^10}，{{ / }} 输出花括号
+//
+// 宽度按终端显示宽度计算（中日韩文字、emoji 占两列），超出列宽的值截断并以 … 结尾。
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 按模板渲染，占位符的值由 value 提供；未知占位符原样保留
+pub fn render(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    render_with(template, value, str::to_string, |_, v| v.to_string())
+}
+
+/// 同 render，但普通文字（含填充）经 text 处理，占位符的值在截断后、补齐前经 field(名称, 值) 处理；
+/// 用于生成 Pango 标记，对齐仍按原始文字计算
+pub fn render_with(
+    template: &str,
+    value: impl Fn(&str) -> Option<String>,
+    text: impl Fn(&str) -> String,
+    field: impl Fn(&str, &str) -> String,
+) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&text(&rest[..pos]));
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&text(&tail[..1]));
+            rest = &tail[2..];
+            continue;
+        }
+        let end = match tail.find('}') {
+            Some(end) if tail.starts_with('{') => end,
+            // 落单的 } 或没有闭合的 {
+            _ => {
+                out.push_str(&text(&tail[..1]));
+                rest = &tail[1..];
+                continue;
+            }
+        };
+        let placeholder = &tail[1..end];
+        let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+        match value(name.trim()) {
+            Some(v) => {
+                let (left, v, right) = pad(&v, spec);
+                out.push_str(&text(&left));
+                out.push_str(&field(name.trim(), &v));
+                out.push_str(&text(&right));
+            }
+            None => out.push_str(&text(&tail[..=end])),
+        }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(&text(rest));
+    out
+}
+
+/// 终端显示宽度
+pub fn width(s: &str) -> usize {
+    s.width()
+}
+
+/// 截断到不超过 max 列，被截断时以 … 结尾
+pub fn truncate(s: &str, max: usize) -> String {
+    if s.width() <= max {
+        return s.to_string();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        // 给 … 留一列
+        if used + w + 1 > max {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if max > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// 对齐说明：[[填充]对齐]宽度，对齐为 < > ^，默认左对齐、空格填充；宽度即列宽，长则截断。
+/// 返回 (左侧填充, 截断后的值, 右侧填充)
+fn pad(value: &str, spec: &str) -> (String, String, String) {
+    let mut chars = spec.chars();
+    let (fill, align, width) = match (chars.next(), chars.next()) {
+        (Some(f), Some(a)) if matches!(a, '<' | '>' | '^') => (f, a, &spec[f.len_utf8() + 1..]),
+        (Some(a), _) if matches!(a, '<' | '>' | '^') => (' ', a, &spec[1..]),
+        _ => (' ', '<', spec),
+    };
+    let (width, value) = match width.trim().parse() {
+        Ok(w) => (w, truncate(value, w)),
+        Err(_) => (0, value.to_string()),
+    };
+    let gap = width.saturating_sub(value.width());
+    let (left, right) = match align {
+        '>' => (gap, 0),
+        '^' => (gap / 2, gap - gap / 2),
+        _ => (0, gap),
+    };
+    let fill = |n: usize| fill.to_string().repeat(n);
+    (fill(left), value, fill(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(name: &str) -> Option<String> {
+        match name {
+            "ssid" => Some("Home".into()),
+            "signal" => Some("7".into()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(render("{{ssid}} {ssid}", fields), "{ssid} Home");
+        assert_eq!(render("}}{{", fields), "}{");
+        // 落单的花括号原样输出
+        assert_eq!(render("a } b { c", fields), "a } b { c");
+    }
+
+    #[test]
+    fn fill_and_align() {
+        assert_eq!(render("[{ssid:8}]", fields), "[Home    ]");
+        assert_eq!(render("[{ssid:<8}]", fields), "[Home    ]");
+        assert_eq!(render("[{ssid:>8}]", fields), "[    Home]");
+        assert_eq!(render("[{ssid:^9}]", fields), "[  Home   ]");
+        assert_eq!(render("[{signal:0>3}]", fields), "[007]");
+        assert_eq!(render("[{ssid:*^8}]", fields), "[**Home**]");
+        assert_eq!(render("[{ssid:·<6}]", fields), "[Home··]");
+        // 没有宽度只对齐不截断
+        assert_eq!(render("[{ssid:>}]", fields), "[Home]");
+    }
+
+    #[test]
+    fn unknown_placeholders_are_kept() {
+        assert_eq!(render("{ssid} {nope:>5} {}", fields), "Home {nope:>5} {}");
+    }
+
+    #[test]
+    fn render_with_wraps_text_and_fields() {
+        let out = render_with(
+            "<{ssid:>6}|{signal}>",
+            fields,
+            |t| t.replace('<', "&lt;").replace('>', "&gt;"),
+            |name, v| format!("[{name}={v}]"),
+        );
+        assert_eq!(out, "&lt;  [ssid=Home]|[signal=7]&gt;");
+    }
+
+    #[test]
+    fn double_width_is_measured_in_columns() {
+        assert_eq!(width("家里的网"), 8);
+        assert_eq!(width("📶Cafe"), 6);
+        let ssid = |s: &'static str| move |_: &str| Some(s.to_string());
+        assert_eq!(render("[{ssid:<10}]", ssid("家里的网")), "[家里的网  ]");
+        assert_eq!(render("[{ssid:>8}]", ssid("📶Cafe")), "[  📶Cafe]");
+        // 中英混排时后续列仍对齐
+        let a = render("{ssid:<8}|", ssid("咖啡Cafe"));
+        let b = render("{ssid:<8}|", ssid("Cafe"));
+        assert_eq!(width(&a), width(&b));
+    }
+
+    #[test]
+    fn truncate_to_exact_width() {
+        // 恰好等宽不截断
+        assert_eq!(truncate("abcde", 5), "abcde");
+        assert_eq!(truncate("abcdef", 5), "abcd…");
+        assert_eq!(truncate("家里的网", 8), "家里的网");
+        assert_eq!(truncate("家里的网络", 8), "家里的…");
+        // 双宽字放不下时留空，由补齐填满
+        assert_eq!(truncate("家里的网络", 6), "家里…");
+        assert_eq!(truncate("家里的网络", 4), "家…");
+        assert_eq!(truncate("abc", 0), "");
+        let ssid = |_: &str| Some("家里的网络".to_string());
+        assert_eq!(render("[{ssid:<6}]", ssid), "[家里… ]");
+        assert_eq!(width(&render("{ssid:<6}", ssid)), 6);
+    }
+}
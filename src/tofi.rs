@@ -0,0 +1,61 @@
+// src/tofi.rs — tofi 前端（Wayland）
+
+use crate::config::Config;
+use crate::menu::{self, Menu, SelectOpts, Selection};
+use async_trait::async_trait;
+
+pub struct Tofi {
+    cfg: Config,
+}
+
+impl Tofi {
+    pub fn new(cfg: Config) -> Self {
+        Self { cfg }
+    }
+
+    async fn dmenu(&self, items: &[String], prompt: &str, extra: Vec<String>) -> Option<String> {
+        let (family, size) = menu::split_font(&self.cfg.font);
+        let mut args: Vec<String> = vec![
+            format!("--prompt-text={prompt}"),
+            format!("--font={family}"),
+            format!("--anchor={}", menu::anchor(self.cfg.position)),
+            format!("--margin-left={}", self.cfg.x_offset),
+            format!("--margin-top={}", self.cfg.y_offset),
+        ];
+        if let Some(size) = size {
+            args.push(format!("--font-size={size}"));
+        }
+        args.extend(extra);
+        menu::run("tofi", &args, items).await
+    }
+}
+
+#[async_trait]
+impl Menu for Tofi {
+    /// 不支持消息区和高亮行
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut extra: Vec<String> = vec![];
+        if let Some(n) = opts.lines {
+            extra.push(format!("--num-results={n}"));
+        }
+        // tofi 默认只接受列表中的项，自由输入需要显式关闭
+        extra.push(format!("--require-match={}", opts.no_custom));
+        let prompt = menu::prompt_with(prompt, opts.message);
+        let text = self.dmenu(items, &prompt, extra).await?;
+        Some(menu::match_row(items, text))
+    }
+
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let extra = vec![
+            "--require-match=false".into(),
+            "--hide-input=true".into(),
+            "--hidden-character=*".into(),
+        ];
+        self.dmenu(&[], prompt, extra).await
+    }
+}
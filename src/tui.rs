@@ -0,0 +1,511 @@
+// src/tui.rs — 终端全屏前端：rofi-rwifi tui
+//
+// 与图形前端共用同一套菜单流程，只是把每次选择画在终端里。
+// 主菜单定时超时返回 Expired，由流程重读缓存，列表因此保持更新。
+
+use crate::i18n::t;
+use crate::menu::{Frontend, Menu, Role, SelectOpts, Selection};
+use crate::notify::{self, Urgency};
+use crate::template;
+use anyhow::Result;
+use async_trait::async_trait;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::future::Future;
+use std::sync::{Arc, Mutex, Weak};
+use tokio::sync::mpsc;
+
+/// PageUp / PageDown 一次移动的行数
+const PAGE: usize = 10;
+
+/// 在终端里运行菜单流程，结束（或出错）后恢复终端
+pub async fn run<F, Fut>(flow: F) -> Result<()>
+where
+    F: FnOnce(Frontend) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let terminal = ratatui::try_init()?;
+    let _stderr = StderrGuard::redirect();
+    let tui = Arc::new(Tui {
+        terminal: Mutex::new(terminal),
+        input: tokio::sync::Mutex::new(spawn_reader()),
+        view: Mutex::new(View::default()),
+        resume: Mutex::new(None),
+    });
+
+    // 通知改写到状态栏，并立即重画（连接等耗时操作期间也能看到进度）
+    let weak: Weak<Tui> = Arc::downgrade(&tui);
+    notify::set_sink(Some(Box::new(move |urgency, title, body| {
+        if let Some(tui) = weak.upgrade() {
+            tui.set_status(urgency, &format!("{title}: {body}"));
+        }
+    })));
+
+    let res = flow(tui).await;
+    notify::set_sink(None);
+    ratatui::restore();
+    res
+}
+
+/// 读键线程转发来的输入
+enum Input {
+    Key(KeyEvent),
+    Resize,
+}
+
+/// crossterm 的读取是阻塞的，放到单独线程里
+fn spawn_reader() -> mpsc::UnboundedReceiver<Input> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || loop {
+        let input = match event::read() {
+            Ok(Event::Key(k)) if k.kind == KeyEventKind::Press => Input::Key(k),
+            Ok(Event::Resize(..)) => Input::Resize,
+            Ok(_) => continue,
+            Err(_) => break,
+        };
+        if tx.send(input).is_err() {
+            break;
+        }
+    });
+    rx
+}
+
+/// 终端界面期间把 stderr 指向 /dev/null，避免后台扫描的输出打乱画面
+struct StderrGuard(libc::c_int);
+
+impl StderrGuard {
+    fn redirect() -> Option<Self> {
+        unsafe {
+            let saved = libc::dup(libc::STDERR_FILENO);
+            let null = libc::open(c"/dev/null".as_ptr(), libc::O_WRONLY);
+            if saved < 0 || null < 0 {
+                return None;
+            }
+            libc::dup2(null, libc::STDERR_FILENO);
+            libc::close(null);
+            Some(Self(saved))
+        }
+    }
+}
+
+impl Drop for StderrGuard {
+    fn drop(&mut self) {
+        unsafe {
+            libc::dup2(self.0, libc::STDERR_FILENO);
+            libc::close(self.0);
+        }
+    }
+}
+
+// ── 界面状态 ─────────────────────────────────────────────────
+
+/// 当前显示的一屏
+#[derive(Default)]
+struct View {
+    screen: Screen,
+    /// 最近一条通知
+    status: Option<(Color, String)>,
+}
+
+enum Screen {
+    /// 列表 + 输入行（纯输入框时列表为空）
+    List(ListView),
+    /// 只读文字：详情面板 / 二维码
+    Text {
+        title: String,
+        body: String,
+        /// 二维码不能折行
+        wrap: bool,
+    },
+}
+
+impl Default for Screen {
+    fn default() -> Self {
+        Screen::Text {
+            title: String::new(),
+            body: String::new(),
+            wrap: true,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ListView {
+    prompt: String,
+    message: Option<String>,
+    items: Vec<String>,
+    active: Option<usize>,
+    urgent: Vec<usize>,
+    /// 输入行：过滤列表，或作为自由输入 / 密码
+    filter: String,
+    /// 在过滤后列表中的位置
+    cursor: usize,
+    /// 密码输入，显示为圆点
+    mask: bool,
+    /// 自定义快捷键，下标与 SelectOpts::keys 对应；无法识别的为 None
+    keys: Vec<Option<(KeyModifiers, KeyCode)>>,
+}
+
+impl ListView {
+    fn new(items: &[String], prompt: &str, opts: &SelectOpts<'_>) -> Self {
+        Self {
+            prompt: prompt.trim_end_matches([' ', ':']).to_string(),
+            message: opts.message.map(str::to_string),
+            items: items.to_vec(),
+            active: opts.active,
+            urgent: opts.urgent.to_vec(),
+            filter: String::new(),
+            cursor: 0,
+            mask: false,
+            keys: opts.keys.iter().map(|k| parse_key(k)).collect(),
+        }
+    }
+
+    /// 按输入行过滤（不区分大小写），返回原始下标
+    fn visible(&self) -> Vec<usize> {
+        let needle = self.filter.to_lowercase();
+        (0..self.items.len())
+            .filter(|&i| self.items[i].to_lowercase().contains(&needle))
+            .collect()
+    }
+}
+
+/// 列表按键处理结果
+enum Step {
+    Stay,
+    Done(Option<Selection>),
+}
+
+// ── 前端 ─────────────────────────────────────────────────────
+
+struct Tui {
+    terminal: Mutex<DefaultTerminal>,
+    input: tokio::sync::Mutex<mpsc::UnboundedReceiver<Input>>,
+    view: Mutex<View>,
+    /// 主菜单超时重载前的光标和输入，重载后恢复
+    resume: Mutex<Option<(usize, String)>>,
+}
+
+impl Tui {
+    fn set_screen(&self, screen: Screen) {
+        self.view.lock().unwrap().screen = screen;
+        self.redraw();
+    }
+
+    fn set_status(&self, urgency: &Urgency, text: &str) {
+        let color = match urgency {
+            Urgency::Low => Color::DarkGray,
+            Urgency::Normal => Color::Reset,
+            Urgency::Critical => Color::Red,
+        };
+        self.view.lock().unwrap().status = Some((color, text.replace('\n', " ")));
+        self.redraw();
+    }
+
+    fn redraw(&self) {
+        let view = self.view.lock().unwrap();
+        let _ = self.terminal.lock().unwrap().draw(|f| draw(f, &view));
+    }
+
+    /// 下一次按键；窗口大小变化时顺便重画
+    async fn next_key(&self) -> Option<KeyEvent> {
+        let mut input = self.input.lock().await;
+        loop {
+            match input.recv().await? {
+                Input::Key(k) => return Some(k),
+                Input::Resize => self.redraw(),
+            }
+        }
+    }
+
+    /// 显示列表并处理按键，直到选择、取消或超时
+    async fn run_list(&self, mut list: ListView, opts: &SelectOpts<'_>) -> Option<Selection> {
+        loop {
+            self.set_screen(Screen::List(list.clone()));
+            let key = match opts.refresh {
+                Some(d) => match tokio::time::timeout(d, self.next_key()).await {
+                    Ok(key) => key?,
+                    Err(_) => {
+                        *self.resume.lock().unwrap() = Some((list.cursor, list.filter));
+                        return Some(Selection::Expired);
+                    }
+                },
+                None => self.next_key().await?,
+            };
+            if let Step::Done(choice) = handle_key(&mut list, key, opts.no_custom) {
+                return choice;
+            }
+        }
+    }
+
+    /// 只读页面，Esc / Enter / q 关闭
+    async fn run_text(&self, title: &str, body: &str, wrap: bool) {
+        self.set_screen(Screen::Text {
+            title: title.to_string(),
+            body: body.to_string(),
+            wrap,
+        });
+        while let Some(key) = self.next_key().await {
+            if is_cancel(&key) || matches!(key.code, KeyCode::Enter | KeyCode::Char('q')) {
+                break;
+            }
+        }
+    }
+}
+
+fn is_cancel(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Esc
+        || (key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c'))
+}
+
+/// rofi 按键语法 → crossterm 按键，如 "Alt+i"、"Control+Delete"、"F5"
+fn parse_key(spec: &str) -> Option<(KeyModifiers, KeyCode)> {
+    let mut parts: Vec<&str> = spec.split('+').collect();
+    let key = parts.pop()?;
+    let mut mods = KeyModifiers::NONE;
+    for m in parts {
+        mods |= match m.to_lowercase().as_str() {
+            "alt" | "mod1" => KeyModifiers::ALT,
+            "control" | "ctrl" => KeyModifiers::CONTROL,
+            "shift" => KeyModifiers::SHIFT,
+            "super" | "mod4" => KeyModifiers::SUPER,
+            _ => return None,
+        };
+    }
+    let code = match key {
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        k if k.chars().count() == 1 => KeyCode::Char(k.chars().next()?.to_ascii_lowercase()),
+        k => KeyCode::F(k.strip_prefix('F')?.parse().ok()?),
+    };
+    Some((mods, code))
+}
+
+fn key_matches(key: &KeyEvent, (mods, code): &(KeyModifiers, KeyCode)) -> bool {
+    let pressed = match key.code {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        c => c,
+    };
+    key.modifiers == *mods && pressed == *code
+}
+
+fn handle_key(list: &mut ListView, key: KeyEvent, no_custom: bool) -> Step {
+    let visible = list.visible();
+    let last = visible.len().saturating_sub(1);
+    let bound = list
+        .keys
+        .iter()
+        .position(|b| b.as_ref().is_some_and(|b| key_matches(&key, b)));
+    if let Some(k) = bound {
+        return match visible.get(list.cursor) {
+            Some(&i) => Step::Done(Some(Selection::Key(k, i))),
+            None => Step::Stay,
+        };
+    }
+    match key.code {
+        _ if is_cancel(&key) => return Step::Done(None),
+        KeyCode::Enter => {
+            if let Some(&i) = visible.get(list.cursor) {
+                return Step::Done(Some(Selection::Row(i)));
+            }
+            if !no_custom {
+                return Step::Done(Some(Selection::Custom(list.filter.clone())));
+            }
+        }
+        KeyCode::Up => list.cursor = list.cursor.saturating_sub(1),
+        KeyCode::Down => list.cursor = (list.cursor + 1).min(last),
+        KeyCode::PageUp => list.cursor = list.cursor.saturating_sub(PAGE),
+        KeyCode::PageDown => list.cursor = (list.cursor + PAGE).min(last),
+        KeyCode::Home => list.cursor = 0,
+        KeyCode::End => list.cursor = last,
+        KeyCode::Backspace => {
+            list.filter.pop();
+            list.cursor = 0;
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            list.filter.push(c);
+            list.cursor = 0;
+        }
+        _ => {}
+    }
+    Step::Stay
+}
+
+#[async_trait]
+impl Menu for Tui {
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut list = ListView::new(items, prompt, &opts);
+        if opts.root {
+            if let Some((cursor, filter)) = self.resume.lock().unwrap().take() {
+                list.cursor = cursor;
+                list.filter = filter;
+            }
+        }
+        self.run_list(list, &opts).await
+    }
+
+    fn supports_keys(&self) -> bool {
+        true
+    }
+
+    async fn password(&self, prompt: &str) -> Option<String> {
+        let opts = SelectOpts::default();
+        let list = ListView {
+            mask: true,
+            ..ListView::new(&[], prompt, &opts)
+        };
+        Some(self.run_list(list, &opts).await?.text(&[]))
+    }
+
+    /// 详情面板
+    async fn show_info(&self, title: &str, content: &str) {
+        self.run_text(title, content, true).await;
+    }
+
+    /// 直接画 qr::wifi_qr 的半块字符输出，放在动作列表上方的消息区
+    async fn show_qr(&self, ssid: &str, qr_text: &str, actions: &[String]) -> Option<usize> {
+        let opts = SelectOpts {
+            message: Some(qr_text),
+            no_custom: true,
+            role: Role::Qr,
+            ..Default::default()
+        };
+        match self.select(actions, &format!("📷 {ssid}"), opts).await? {
+            Selection::Row(i) => Some(i),
+            _ => None,
+        }
+    }
+}
+
+// ── 绘制 ─────────────────────────────────────────────────────
+
+fn draw(f: &mut Frame, view: &View) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(f.area());
+    let hint = match &view.screen {
+        Screen::List(list) => {
+            draw_list(f, main, list);
+            if list.items.is_empty() {
+                t!(TuiHintInput)
+            } else {
+                t!(TuiHintList)
+            }
+        }
+        Screen::Text { title, body, wrap } => {
+            draw_text(f, main, title, body, *wrap);
+            t!(TuiHintText)
+        }
+    };
+
+    let [left, right] = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(template::width(hint) as u16),
+    ])
+    .areas(status);
+    if let Some((color, text)) = &view.status {
+        f.render_widget(
+            Paragraph::new(text.as_str()).style(Style::default().fg(*color)),
+            left,
+        );
+    }
+    f.render_widget(
+        Paragraph::new(hint)
+            .alignment(Alignment::Right)
+            .style(Style::default().fg(Color::DarkGray)),
+        right,
+    );
+}
+
+fn draw_list(f: &mut Frame, area: Rect, list: &ListView) {
+    let message_height = list
+        .message
+        .as_ref()
+        .map_or(0, |m| m.lines().count() as u16 + 2);
+    let [message, rows, input] = Layout::vertical([
+        Constraint::Length(message_height),
+        Constraint::Min(0),
+        Constraint::Length(3),
+    ])
+    .areas(area);
+
+    if let Some(msg) = &list.message {
+        f.render_widget(
+            Paragraph::new(msg.as_str())
+                .style(Style::default().fg(Color::Yellow))
+                .block(Block::default().borders(Borders::ALL)),
+            message,
+        );
+    }
+
+    // 纯输入框只有输入行
+    if list.items.is_empty() {
+        return draw_input(f, input, list, list.prompt.as_str());
+    }
+
+    let visible = list.visible();
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|&i| {
+            let mut style = Style::default();
+            if list.urgent.contains(&i) {
+                style = style.fg(Color::Red);
+            }
+            if list.active == Some(i) {
+                style = style.fg(Color::Green).add_modifier(Modifier::BOLD);
+            }
+            ListItem::new(list.items[i].as_str()).style(style)
+        })
+        .collect();
+    let mut state =
+        ListState::default().with_selected((!visible.is_empty()).then_some(list.cursor));
+    f.render_stateful_widget(
+        List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(list.prompt.as_str()),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("› "),
+        rows,
+        &mut state,
+    );
+
+    draw_input(f, input, list, "");
+}
+
+fn draw_input(f: &mut Frame, area: Rect, list: &ListView, title: &str) {
+    let shown = if list.mask {
+        "•".repeat(list.filter.chars().count())
+    } else {
+        list.filter.clone()
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::raw(shown),
+            Span::styled("█", Style::default().fg(Color::DarkGray)),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title(title)),
+        area,
+    );
+}
+
+fn draw_text(f: &mut Frame, area: Rect, title: &str, body: &str, wrap: bool) {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let mut text = Paragraph::new(body).block(block);
+    text = if wrap {
+        text.wrap(Wrap { trim: false })
+    } else {
+        text.alignment(Alignment::Center)
+    };
+    f.render_widget(text, area);
+}
@@ -1,78 +1,638 @@
 // src/types.rs — 所有核心数据类型
 
+use crate::config::{Colors, Glyphs};
+use crate::i18n::t;
+use crate::markup;
+use crate::template;
 use serde::{Deserialize, Serialize};
 
-/// 单个 Wi-Fi 接入点
+/// 单个 Wi-Fi 网络（同一 SSID 的所有 BSSID 归为一组）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessPoint {
     pub ssid: String,
     pub security: Security,
-    pub signal: u8, // 0–100
+    pub signal: u8, // 0–100，取组内最强的 BSSID
     pub bars: String,
     pub in_use: bool,
+    /// 组内所有射频，当前连接的置顶，其余按信号降序
+    pub bss: Vec<Bss>,
+    /// 不广播 SSID：扫描到的隐藏 AP 的 ssid 为空；已保存但未扫到的隐藏配置 bss 为空
+    #[serde(default)]
+    pub hidden: bool,
 }
 
-impl AccessPoint {
-    /// 用于 rofi 显示的单行文本
+/// 单个射频（BSSID）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bss {
+    pub bssid: String,
+    pub channel: u32,
+    pub frequency: u32, // MHz，0 表示未知
+    pub band: Band,
+    pub max_rate: u32, // Mbit/s，0 表示未知
+    pub mode: ApMode,
+    pub signal: u8,
+    pub bars: String,
+    pub in_use: bool,
+}
+
+impl Bss {
+    /// 按频率补全信道和频段
+    pub fn new(bssid: String, frequency: u32, signal: u8, in_use: bool) -> Self {
+        Self {
+            bssid,
+            channel: channel_from_frequency(frequency),
+            frequency,
+            band: Band::from_frequency(frequency),
+            max_rate: 0,
+            mode: ApMode::Infra,
+            signal,
+            bars: signal_bars(signal),
+            in_use,
+        }
+    }
+
+    /// 用于展开菜单的单行文本
     pub fn display_line(&self) -> String {
-        let lock = match self.security {
-            Security::Open => "   ",
-            Security::Wep => "🔓 ",
-            _ => "🔒 ",
-        };
         let active = if self.in_use { "● " } else { "  " };
+        let channel = if self.channel > 0 {
+            format!("ch {:<3}", self.channel)
+        } else {
+            "ch ?  ".into()
+        };
+        let rate = if self.max_rate > 0 {
+            format!("{} Mbit/s", self.max_rate)
+        } else {
+            String::new()
+        };
         format!(
-            "{}{}{:<20}  {}  {:>3}%",
-            active, lock, self.ssid, self.bars, self.signal
+            "{}{:<17}  {}  {:<7}  {}  {:>3}%  {}",
+            active, self.bssid, channel, self.band, self.bars, self.signal, rate
         )
     }
 }
 
-/// 加密类型
+/// 扫描得到的一条原始记录，交给 [`group_by_ssid`] 归组
+#[derive(Debug, Clone)]
+pub struct ScanEntry {
+    pub ssid: String,
+    pub security: Security,
+    pub bss: Bss,
+}
+
+/// 把逐 BSSID 的扫描结果按 SSID 归组；当前连接的网络置顶，其余按信号降序
+pub fn group_by_ssid(entries: Vec<ScanEntry>) -> Vec<AccessPoint> {
+    let mut aps: Vec<AccessPoint> = vec![];
+    for e in entries {
+        // 隐藏 AP 无从判断是否同一网络，各自单列
+        let group = if e.ssid.is_empty() {
+            None
+        } else {
+            aps.iter_mut().find(|ap| ap.ssid == e.ssid)
+        };
+        match group {
+            Some(ap) => ap.bss.push(e.bss),
+            None => aps.push(AccessPoint {
+                hidden: e.ssid.is_empty(),
+                ssid: e.ssid,
+                security: e.security,
+                signal: 0,
+                bars: String::new(),
+                in_use: false,
+                bss: vec![e.bss],
+            }),
+        }
+    }
+
+    for ap in &mut aps {
+        ap.bss
+            .sort_by(|a, b| b.in_use.cmp(&a.in_use).then(b.signal.cmp(&a.signal)));
+        ap.in_use = ap.bss.iter().any(|b| b.in_use);
+        ap.signal = ap.bss.iter().map(|b| b.signal).max().unwrap_or(0);
+        ap.bars = signal_bars(ap.signal);
+    }
+    aps.sort_by(|a, b| b.in_use.cmp(&a.in_use).then(b.signal.cmp(&a.signal)));
+    aps
+}
+
+/// 频段
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Band {
+    G2_4,
+    G5,
+    G6,
+    Unknown,
+}
+
+impl Band {
+    pub fn from_frequency(mhz: u32) -> Self {
+        match mhz {
+            2400..=2500 => Band::G2_4,
+            4900..=5925 => Band::G5,
+            5935..=7125 => Band::G6,
+            _ => Band::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for Band {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Band::G2_4 => "2.4 GHz",
+            Band::G5 => "5 GHz",
+            Band::G6 => "6 GHz",
+            Band::Unknown => "?",
+        };
+        f.pad(s)
+    }
+}
+
+/// 802.11 工作模式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ApMode {
+    Infra,
+    AdHoc,
+    Mesh,
+    Unknown,
+}
+
+impl std::fmt::Display for ApMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ApMode::Infra => "Infra",
+            ApMode::AdHoc => "Ad-Hoc",
+            ApMode::Mesh => "Mesh",
+            ApMode::Unknown => "?",
+        };
+        f.pad(s)
+    }
+}
+
+/// 中心频率（MHz）→ 信道号，未知频率返回 0
+pub fn channel_from_frequency(mhz: u32) -> u32 {
+    match mhz {
+        2484 => 14,
+        2412..=2472 => (mhz - 2407) / 5,
+        // 6 GHz 的 2 号信道不在 5950 起的等距序列上
+        5935 => 2,
+        5955..=7115 => (mhz - 5950) / 5,
+        5000..=5925 => (mhz - 5000) / 5,
+        _ => 0,
+    }
+}
+
+/// 新建连接的参数
+#[derive(Debug, Clone, Default)]
+pub struct ConnectRequest {
+    pub ssid: String,
+    pub password: Option<String>,
+    /// 指定要连接的 BSSID（None 由后端自选）
+    pub bssid: Option<String>,
+    /// 隐藏网络：后端需主动探测该 SSID
+    pub hidden: bool,
+    /// 802.1X 企业网络参数（此时忽略 password）
+    pub enterprise: Option<EnterpriseConfig>,
+}
+
+/// 802.1X 外层 EAP 方法
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EapMethod {
+    Peap,
+    Ttls,
+    Tls,
+}
+
+impl EapMethod {
+    pub const ALL: [EapMethod; 3] = [EapMethod::Peap, EapMethod::Ttls, EapMethod::Tls];
+
+    /// 该方法可选的内层认证；TLS 没有内层认证
+    pub fn phase2_choices(&self) -> &'static [Phase2Auth] {
+        match self {
+            EapMethod::Peap => &[Phase2Auth::Mschapv2, Phase2Auth::Gtc, Phase2Auth::Md5],
+            EapMethod::Ttls => &[
+                Phase2Auth::Pap,
+                Phase2Auth::Mschapv2,
+                Phase2Auth::Mschap,
+                Phase2Auth::Chap,
+            ],
+            EapMethod::Tls => &[],
+        }
+    }
+}
+
+impl std::fmt::Display for EapMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EapMethod::Peap => "PEAP",
+            EapMethod::Ttls => "TTLS",
+            EapMethod::Tls => "TLS",
+        };
+        f.write_str(s)
+    }
+}
+
+/// PEAP / TTLS 的内层（phase 2）认证
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase2Auth {
+    Mschapv2,
+    Mschap,
+    Pap,
+    Chap,
+    Gtc,
+    Md5,
+}
+
+impl std::fmt::Display for Phase2Auth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Phase2Auth::Mschapv2 => "MSCHAPV2",
+            Phase2Auth::Mschap => "MSCHAP",
+            Phase2Auth::Pap => "PAP",
+            Phase2Auth::Chap => "CHAP",
+            Phase2Auth::Gtc => "GTC",
+            Phase2Auth::Md5 => "MD5",
+        };
+        f.write_str(s)
+    }
+}
+
+/// 802.1X 连接参数，由菜单向导填写
+#[derive(Debug, Clone)]
+pub struct EnterpriseConfig {
+    pub eap: EapMethod,
+    pub phase2: Option<Phase2Auth>,
+    pub identity: String,
+    pub anonymous_identity: Option<String>,
+    /// CA 证书路径；None 表示不校验服务器证书
+    pub ca_cert: Option<String>,
+    /// 服务器证书域名后缀匹配
+    pub domain_match: Option<String>,
+    /// PEAP / TTLS 的密码
+    pub password: Option<String>,
+    /// TLS 的客户端证书、私钥及私钥密码
+    pub client_cert: Option<String>,
+    pub private_key: Option<String>,
+    pub private_key_password: Option<String>,
+}
+
+impl AccessPoint {
+    /// 已保存但本次没扫到的隐藏网络，安全类型未知
+    pub fn saved_hidden(name: String) -> Self {
+        Self {
+            ssid: name,
+            security: Security::unknown(),
+            signal: 0,
+            bars: signal_bars(0),
+            in_use: false,
+            bss: vec![],
+            hidden: true,
+        }
+    }
+
+    /// 标题等处使用的名称，扫描到的隐藏 AP 显示为 <hidden>
+    pub fn display_name(&self) -> &str {
+        if self.ssid.is_empty() {
+            "<hidden>"
+        } else {
+            &self.ssid
+        }
+    }
+
+    /// 按 row_format 模板生成菜单中的单行文本
+    pub fn display_line(&self, row_format: &str, glyphs: &Glyphs, saved: bool) -> String {
+        template::render(row_format, |name| self.field(name, glyphs, saved))
+            .trim_end()
+            .to_string()
+    }
+
+    /// 同 display_line，但输出 Pango 标记：信号按强弱着色，弱加密 / 开放标为风险色，已保存的 SSID 加粗
+    pub fn display_markup(
+        &self,
+        row_format: &str,
+        glyphs: &Glyphs,
+        saved: bool,
+        colors: &Colors,
+    ) -> String {
+        let risky = self.security.is_open() || self.security.is_weak();
+        let line = template::render_with(
+            row_format,
+            |name| self.field(name, glyphs, saved),
+            markup::escape,
+            |name, value| match name {
+                "ssid" if saved => markup::span(value, &colors.saved, true),
+                "bars" | "signal" => markup::span(value, colors.signal(self.signal), false),
+                "lock" | "security" if risky => markup::span(value, &colors.risky, false),
+                _ => markup::escape(value),
+            },
+        );
+        line.trim_end().to_string()
+    }
+
+    /// 行模板中占位符的值
+    fn field(&self, name: &str, glyphs: &Glyphs, saved: bool) -> Option<String> {
+        // 信号最强的射频决定频段和信道
+        let best = self.bss.iter().max_by_key(|b| b.signal);
+        Some(match name {
+            "ssid" => self.display_name().to_string(),
+            "signal" => self.signal.to_string(),
+            "bars" => glyphs.bars(self.signal),
+            "security" => self.security.to_string(),
+            "band" => best.map_or("?".into(), |b| b.band.to_string()),
+            "channel" => best
+                .filter(|b| b.channel > 0)
+                .map_or("?".into(), |b| b.channel.to_string()),
+            "saved" => glyphs.saved(saved).to_string(),
+            "in_use" => glyphs.in_use(self.in_use).to_string(),
+            "lock" => glyphs.lock(&self.security).to_string(),
+            "extra" => self.extra(),
+            _ => return None,
+        })
+    }
+
+    /// 附注：隐藏 AP 附上 BSSID 以示区分；多个 BSS 时提示可展开；没扫到的已保存隐藏网络
+    fn extra(&self) -> String {
+        if self.bss.is_empty() {
+            t!(HiddenSaved).to_string()
+        } else if self.ssid.is_empty() {
+            self.bss[0].bssid.clone()
+        } else if self.bss.len() > 1 {
+            format!("({} AP)", self.bss.len())
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// 按信号强度生成与 nmcli BARS 列一致的信号格
+pub fn signal_bars(signal: u8) -> String {
+    ["____", "▂___", "▂▄__", "▂▄▆_", "▂▄▆█"][signal_level(signal)].to_string()
+}
+
+/// 信号格数 0–4，分档与 nmcli 一致
+pub fn signal_level(signal: u8) -> usize {
+    match signal {
+        81.. => 4,
+        56..=80 => 3,
+        31..=55 => 2,
+        6..=30 => 1,
+        _ => 0,
+    }
+}
+
+/// dBm → 0–100 百分比（-100 dBm 记 0，-50 dBm 及以上记 100）
+pub fn dbm_to_percent(dbm: i32) -> u8 {
+    ((dbm + 100) * 2).clamp(0, 100) as u8
+}
+
+/// 加密描述：认证方式、WPA 代际与加密套件；全部为空即开放网络
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Security {
+    pub key_mgmt: Vec<KeyMgmt>,
+    /// 过渡模式会同时出现多代（如 WPA2 + WPA3）
+    pub wpa: Vec<WpaGen>,
+    pub ciphers: Vec<Cipher>,
+}
+
+/// 认证 / 密钥管理方式
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum Security {
-    Open,
-    Wep,
-    Wpa,
+pub enum KeyMgmt {
+    Psk,
+    Sae,
+    Eap,
+    Owe,
+    /// 无法识别的方式，保留原文
+    Other(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum WpaGen {
+    Wpa1,
     Wpa2,
     Wpa3,
-    Unknown(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Cipher {
+    Wep,
+    Tkip,
+    Ccmp,
+    Gcmp,
+}
+
+/// NM80211ApSecurityFlags，nmcli 与 D-Bus 后端共用
+pub mod nm_sec {
+    pub const PAIR_WEP40: u32 = 0x1;
+    pub const PAIR_WEP104: u32 = 0x2;
+    pub const PAIR_TKIP: u32 = 0x4;
+    pub const PAIR_CCMP: u32 = 0x8;
+    pub const GROUP_WEP40: u32 = 0x10;
+    pub const GROUP_WEP104: u32 = 0x20;
+    pub const GROUP_TKIP: u32 = 0x40;
+    pub const GROUP_CCMP: u32 = 0x80;
+    pub const KEY_MGMT_PSK: u32 = 0x100;
+    pub const KEY_MGMT_802_1X: u32 = 0x200;
+    pub const KEY_MGMT_SAE: u32 = 0x400;
+    pub const KEY_MGMT_OWE: u32 = 0x800;
+    pub const KEY_MGMT_OWE_TM: u32 = 0x1000;
+    pub const KEY_MGMT_EAP_SUITE_B_192: u32 = 0x2000;
 }
 
 impl Security {
+    pub fn wep() -> Self {
+        Self {
+            ciphers: vec![Cipher::Wep],
+            ..Default::default()
+        }
+    }
+
+    pub fn wpa2_psk() -> Self {
+        Self {
+            key_mgmt: vec![KeyMgmt::Psk],
+            wpa: vec![WpaGen::Wpa2],
+            ciphers: vec![Cipher::Ccmp],
+        }
+    }
+
+    pub fn wpa2_eap() -> Self {
+        Self {
+            key_mgmt: vec![KeyMgmt::Eap],
+            wpa: vec![WpaGen::Wpa2],
+            ciphers: vec![Cipher::Ccmp],
+        }
+    }
+
+    /// 安全类型未知（如没扫到的已保存网络）
+    pub fn unknown() -> Self {
+        Self {
+            key_mgmt: vec![KeyMgmt::Other(String::new())],
+            ..Default::default()
+        }
+    }
+
+    /// 由 NetworkManager 的 AP Flags 隐私位与 WpaFlags / RsnFlags 构造
+    pub fn from_nm_flags(privacy: bool, wpa: u32, rsn: u32) -> Self {
+        let all = wpa | rsn;
+        let mut sec = Security::default();
+        if all == 0 {
+            return if privacy { Security::wep() } else { sec };
+        }
+
+        for (bit, km) in [
+            (nm_sec::KEY_MGMT_PSK, KeyMgmt::Psk),
+            (nm_sec::KEY_MGMT_SAE, KeyMgmt::Sae),
+            (
+                nm_sec::KEY_MGMT_802_1X | nm_sec::KEY_MGMT_EAP_SUITE_B_192,
+                KeyMgmt::Eap,
+            ),
+            (nm_sec::KEY_MGMT_OWE | nm_sec::KEY_MGMT_OWE_TM, KeyMgmt::Owe),
+        ] {
+            if all & bit != 0 {
+                sec.key_mgmt.push(km);
+            }
+        }
+
+        if wpa != 0 {
+            sec.wpa.push(WpaGen::Wpa1);
+        }
+        if rsn & (nm_sec::KEY_MGMT_PSK | nm_sec::KEY_MGMT_802_1X) != 0 {
+            sec.wpa.push(WpaGen::Wpa2);
+        }
+        if rsn & (nm_sec::KEY_MGMT_SAE | nm_sec::KEY_MGMT_OWE | nm_sec::KEY_MGMT_EAP_SUITE_B_192)
+            != 0
+        {
+            sec.wpa.push(WpaGen::Wpa3);
+        }
+
+        for (bits, cipher) in [
+            (
+                nm_sec::PAIR_WEP40
+                    | nm_sec::PAIR_WEP104
+                    | nm_sec::GROUP_WEP40
+                    | nm_sec::GROUP_WEP104,
+                Cipher::Wep,
+            ),
+            (nm_sec::PAIR_TKIP | nm_sec::GROUP_TKIP, Cipher::Tkip),
+            (nm_sec::PAIR_CCMP | nm_sec::GROUP_CCMP, Cipher::Ccmp),
+        ] {
+            if all & bits != 0 {
+                sec.ciphers.push(cipher);
+            }
+        }
+        sec
+    }
+
+    /// 完全不加密（OWE 虽免密码但有加密，不算）
+    pub fn is_open(&self) -> bool {
+        self.key_mgmt.is_empty() && !self.is_wep()
+    }
+
+    pub fn is_wep(&self) -> bool {
+        self.key_mgmt.is_empty() && self.ciphers.contains(&Cipher::Wep)
+    }
+
+    pub fn is_enterprise(&self) -> bool {
+        self.key_mgmt.contains(&KeyMgmt::Eap)
+    }
+
+    /// 只支持 OWE（Enhanced Open）
+    pub fn is_owe(&self) -> bool {
+        self.key_mgmt == [KeyMgmt::Owe]
+    }
+
+    /// 只接受 SAE，不兼容 WPA2-PSK 客户端
+    pub fn is_sae_only(&self) -> bool {
+        self.key_mgmt == [KeyMgmt::Sae]
+    }
+
+    /// 只能用 TKIP（或 WEP）加密的弱网络
+    pub fn is_weak(&self) -> bool {
+        self.is_wep()
+            || (!self.ciphers.is_empty()
+                && !self.ciphers.contains(&Cipher::Ccmp)
+                && !self.ciphers.contains(&Cipher::Gcmp))
+    }
+
+    /// 连接时是否需要输入预共享密码
     pub fn needs_password(&self) -> bool {
-        !matches!(self, Security::Open)
+        self.is_wep()
+            || self
+                .key_mgmt
+                .iter()
+                .any(|k| matches!(k, KeyMgmt::Psk | KeyMgmt::Sae | KeyMgmt::Other(_)))
     }
 }
 
+/// 与 nmcli SECURITY 列相近的短文字，如 "WPA2/WPA3"、"WPA2 802.1X"、"WPA1 TKIP"
 impl std::fmt::Display for Security {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Security::Open => write!(f, "Open"),
-            Security::Wep => write!(f, "WEP"),
-            Security::Wpa => write!(f, "WPA"),
-            Security::Wpa2 => write!(f, "WPA2"),
-            Security::Wpa3 => write!(f, "WPA3"),
-            Security::Unknown(s) => write!(f, "{s}"),
-        }
-    }
-}
-
-impl From<&str> for Security {
-    fn from(s: &str) -> Self {
-        let up = s.to_uppercase();
-        if up.contains("WPA3") {
-            Security::Wpa3
-        } else if up.contains("WPA2") {
-            Security::Wpa2
-        } else if up.contains("WPA") {
-            Security::Wpa
-        } else if up.contains("WEP") {
-            Security::Wep
-        } else if up.is_empty() || up == "--" {
-            Security::Open
-        } else {
-            Security::Unknown(s.to_string())
+        if self.is_open() {
+            return f.write_str("Open");
+        }
+        if self.is_wep() {
+            return f.write_str("WEP");
+        }
+        if self.is_owe() {
+            return f.write_str("OWE");
+        }
+        let mut parts: Vec<String> = vec![];
+        if !self.wpa.is_empty() {
+            let gens: Vec<&str> = self
+                .wpa
+                .iter()
+                .map(|g| match g {
+                    WpaGen::Wpa1 => "WPA1",
+                    WpaGen::Wpa2 => "WPA2",
+                    WpaGen::Wpa3 => "WPA3",
+                })
+                .collect();
+            parts.push(gens.join("/"));
+        }
+        if self.is_enterprise() {
+            parts.push("802.1X".into());
+        }
+        if self.key_mgmt.contains(&KeyMgmt::Owe) {
+            parts.push("OWE".into());
+        }
+        if self.is_weak() {
+            parts.push("TKIP".into());
+        }
+        for k in &self.key_mgmt {
+            if let KeyMgmt::Other(s) = k {
+                parts.push(if s.is_empty() { "?".into() } else { s.clone() });
+            }
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+/// 当前连接详情
+#[derive(Debug)]
+pub struct ConnectionDetails {
+    pub ssid: String,
+    pub ip: String,
+    pub gateway: String,
+    pub dns: String,
+    pub security: String,
+    pub signal: String,
+    pub ping_ms: Option<f64>,
+}
+
+/// 一块 Wi-Fi 网卡
+#[derive(Debug, Clone)]
+pub struct WifiDevice {
+    pub name: String,
+    /// 后端报告的状态文字，如 connected / disconnected
+    pub state: String,
+    /// 当前活跃的连接名称
+    pub connection: Option<String>,
+}
+
+impl WifiDevice {
+    /// 用于网卡选择菜单的单行文本
+    pub fn display_line(&self, selected: bool) -> String {
+        let mark = if selected { "● " } else { "  " };
+        match &self.connection {
+            Some(c) => format!("{mark}{:<12}  {:<14}  {c}", self.name, self.state),
+            None => format!("{mark}{:<12}  {}", self.name, self.state),
         }
     }
 }
@@ -87,12 +647,37 @@ pub enum RadioState {
 /// 连接结果
 #[derive(Debug)]
 pub enum ConnectResult {
-    Success { ip: String },
+    Success {
+        ip: String,
+    },
     WrongPassword,
+    /// 802.1X 认证被拒绝或证书校验失败
+    EapFailed(String),
+    /// 附近没有该网络（隐藏网络探测不到也算）
+    NotFound,
     Timeout,
     Failed(String),
 }
 
+/// 主菜单快捷键作用于高亮网络的动作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetworkAction {
+    Details,
+    Forget,
+    QrCode,
+}
+
+impl NetworkAction {
+    /// 消息区快捷键提示中的名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            NetworkAction::Details => t!(KeyDetails),
+            NetworkAction::Forget => t!(KeyForget),
+            NetworkAction::QrCode => t!(KeyQrCode),
+        }
+    }
+}
+
 /// 菜单动作
 #[derive(Debug, Clone)]
 pub enum MenuAction {
@@ -103,6 +688,153 @@ pub enum MenuAction {
     Disconnect,
     Forget,
     Hotspot,
+    Interface,
     Details,
     QrCode,
+    /// 快捷键：对列表中的某个网络执行动作
+    OnNetwork(NetworkAction, AccessPoint),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ssid: &str, bssid: &str, signal: u8, in_use: bool) -> ScanEntry {
+        ScanEntry {
+            ssid: ssid.into(),
+            security: Security::wpa2_psk(),
+            bss: Bss::new(bssid.into(), 2437, signal, in_use),
+        }
+    }
+
+    #[test]
+    fn groups_bss_by_ssid() {
+        let aps = group_by_ssid(vec![
+            entry("Home", "AA:00:00:00:00:01", 40, false),
+            entry("Cafe", "AA:00:00:00:00:02", 60, false),
+            entry("Home", "AA:00:00:00:00:03", 70, false),
+            entry("", "AA:00:00:00:00:04", 30, false),
+            entry("", "AA:00:00:00:00:05", 20, false),
+        ]);
+        let names: Vec<&str> = aps.iter().map(|ap| ap.ssid.as_str()).collect();
+        assert_eq!(names, ["Home", "Cafe", "", ""]);
+        // 组内最强的射频在前，组的信号取最强值
+        let home = &aps[0];
+        assert_eq!(home.signal, 70);
+        assert_eq!(home.bars, signal_bars(70));
+        let bssids: Vec<&str> = home.bss.iter().map(|b| b.bssid.as_str()).collect();
+        assert_eq!(bssids, ["AA:00:00:00:00:03", "AA:00:00:00:00:01"]);
+        // 隐藏 AP 不归组
+        assert!(aps[2].hidden && aps[3].hidden);
+        assert_eq!(aps[2].bss.len(), 1);
+    }
+
+    #[test]
+    fn connected_network_is_pinned_first() {
+        let aps = group_by_ssid(vec![
+            entry("Strong", "AA:00:00:00:00:01", 90, false),
+            entry("Home", "AA:00:00:00:00:02", 80, false),
+            entry("Home", "AA:00:00:00:00:03", 20, true),
+        ]);
+        assert_eq!(aps[0].ssid, "Home");
+        assert!(aps[0].in_use && !aps[1].in_use);
+        // 组内也是当前关联的射频置顶，即使信号更弱
+        assert!(aps[0].bss[0].in_use);
+        assert_eq!(aps[0].bss[0].bssid, "AA:00:00:00:00:03");
+        assert_eq!(aps[0].signal, 80);
+    }
+
+    #[test]
+    fn security_from_nm_flags() {
+        use nm_sec::*;
+        const CCMP: u32 = PAIR_CCMP | GROUP_CCMP;
+        const TKIP: u32 = PAIR_TKIP | GROUP_TKIP;
+        // (privacy, wpa, rsn) → 显示文字, 弱加密, 需要密码, 企业网络
+        for (privacy, wpa, rsn, text, weak, password, enterprise) in [
+            (false, 0, 0, "Open", false, false, false),
+            (true, 0, 0, "WEP", true, true, false),
+            (true, KEY_MGMT_PSK | TKIP, 0, "WPA1 TKIP", true, true, false),
+            (
+                true,
+                KEY_MGMT_PSK | TKIP,
+                KEY_MGMT_PSK | PAIR_CCMP | GROUP_TKIP,
+                "WPA1/WPA2",
+                false,
+                true,
+                false,
+            ),
+            (true, 0, KEY_MGMT_PSK | CCMP, "WPA2", false, true, false),
+            (true, 0, KEY_MGMT_PSK | TKIP, "WPA2 TKIP", true, true, false),
+            (
+                true,
+                0,
+                KEY_MGMT_PSK | KEY_MGMT_SAE | CCMP,
+                "WPA2/WPA3",
+                false,
+                true,
+                false,
+            ),
+            (true, 0, KEY_MGMT_SAE | CCMP, "WPA3", false, true, false),
+            (true, 0, KEY_MGMT_OWE | CCMP, "OWE", false, false, false),
+            // OWE 过渡模式的开放 BSS
+            (false, 0, KEY_MGMT_OWE_TM, "OWE", false, false, false),
+            (
+                true,
+                0,
+                KEY_MGMT_802_1X | CCMP,
+                "WPA2 802.1X",
+                false,
+                false,
+                true,
+            ),
+            (
+                true,
+                0,
+                KEY_MGMT_EAP_SUITE_B_192,
+                "WPA3 802.1X",
+                false,
+                false,
+                true,
+            ),
+        ] {
+            let sec = Security::from_nm_flags(privacy, wpa, rsn);
+            assert_eq!(sec.to_string(), text, "{wpa:#x} {rsn:#x}");
+            assert_eq!(sec.is_weak(), weak, "{text}");
+            assert_eq!(sec.needs_password(), password, "{text}");
+            assert_eq!(sec.is_enterprise(), enterprise, "{text}");
+        }
+    }
+
+    #[test]
+    fn sae_only_and_owe_only() {
+        use nm_sec::*;
+        let transition = Security::from_nm_flags(true, 0, KEY_MGMT_PSK | KEY_MGMT_SAE | PAIR_CCMP);
+        assert!(!transition.is_sae_only());
+        assert!(Security::from_nm_flags(true, 0, KEY_MGMT_SAE | PAIR_CCMP).is_sae_only());
+        let owe = Security::from_nm_flags(true, 0, KEY_MGMT_OWE | PAIR_CCMP);
+        assert!(owe.is_owe() && !owe.is_open());
+        assert!(!Security::wep().is_open() && Security::default().is_open());
+    }
+
+    #[test]
+    fn channel_and_band_from_frequency() {
+        for (mhz, channel, band) in [
+            (2412, 1, Band::G2_4),
+            (2437, 6, Band::G2_4),
+            (2472, 13, Band::G2_4),
+            (2484, 14, Band::G2_4),
+            (5180, 36, Band::G5),
+            (5500, 100, Band::G5),
+            (5825, 165, Band::G5),
+            (5935, 2, Band::G6),
+            (5955, 1, Band::G6),
+            (6115, 33, Band::G6),
+            (7115, 233, Band::G6),
+            (0, 0, Band::Unknown),
+            (3000, 0, Band::Unknown),
+        ] {
+            assert_eq!(channel_from_frequency(mhz), channel, "{mhz} MHz");
+            assert_eq!(Band::from_frequency(mhz), band, "{mhz} MHz");
+        }
+    }
 }
@@ -0,0 +1,57 @@
+// src/wofi.rs — wofi 前端（Wayland）
+
+use crate::config::Config;
+use crate::menu::{self, Menu, SelectOpts, Selection};
+use async_trait::async_trait;
+
+pub struct Wofi {
+    cfg: Config,
+}
+
+impl Wofi {
+    pub fn new(cfg: Config) -> Self {
+        Self { cfg }
+    }
+
+    async fn dmenu(&self, items: &[String], prompt: &str, extra: Vec<String>) -> Option<String> {
+        let mut args: Vec<String> = vec![
+            "--dmenu".into(),
+            "--prompt".into(),
+            prompt.into(),
+            // 不用历史记录重排候选项，否则 AP 顺序会乱
+            "--cache-file=/dev/null".into(),
+            // wofi 的 --location 编号与 rofi 相同
+            "--location".into(),
+            self.cfg.position.to_string(),
+            "--xoffset".into(),
+            self.cfg.x_offset.to_string(),
+            "--yoffset".into(),
+            self.cfg.y_offset.to_string(),
+        ];
+        args.extend(extra);
+        menu::run("wofi", &args, items).await
+    }
+}
+
+#[async_trait]
+impl Menu for Wofi {
+    /// 字体由 wofi 的 CSS 决定；不支持高亮行和禁止自由输入
+    async fn select(
+        &self,
+        items: &[String],
+        prompt: &str,
+        opts: SelectOpts<'_>,
+    ) -> Option<Selection> {
+        let mut extra: Vec<String> = vec![];
+        if let Some(n) = opts.lines {
+            extra.extend(["--lines".into(), n.max(1).to_string()]);
+        }
+        let prompt = menu::prompt_with(prompt, opts.message);
+        let text = self.dmenu(items, &prompt, extra).await?;
+        Some(menu::match_row(items, text))
+    }
+
+    async fn password(&self, prompt: &str) -> Option<String> {
+        self.dmenu(&[], prompt, vec!["--password".into()]).await
+    }
+}
@@ -0,0 +1,1105 @@
+// src/wpa.rs — 通过 wpa_supplicant 控制接口（/var/run/wpa_supplicant/<iface>）操作 Wi-Fi
+
+use crate::backend::{Interfaces, NetworkBackend};
+use crate::i18n::t;
+use crate::netinfo;
+use crate::ping;
+use crate::types::{
+    dbm_to_percent, group_by_ssid, AccessPoint, ApMode, Bss, Cipher, ConnectRequest, ConnectResult,
+    ConnectionDetails, EnterpriseConfig, KeyMgmt, RadioState, ScanEntry, Security, WifiDevice,
+    WpaGen,
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::net::UnixDatagram;
+
+/// wpa_supplicant 默认的控制接口目录
+const CTRL_DIR: &str = "/var/run/wpa_supplicant";
+/// 单条命令等待回复的时间
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// 值为密钥的网络字段，出错时不回显
+const SECRET_FIELDS: &[&str] = &["psk", "wep_key0", "password", "private_key_passwd"];
+
+/// 通过控制接口与 wpa_supplicant 通信
+pub struct WpaSupplicant {
+    ctrl_dir: PathBuf,
+    ifaces: Interfaces,
+}
+
+/// 一个已连接到 wpa_supplicant 的控制套接字，drop 时删除本地套接字文件
+struct CtrlSocket {
+    sock: UnixDatagram,
+    local: PathBuf,
+}
+
+impl CtrlSocket {
+    async fn open(remote: &Path) -> Result<Self> {
+        // 同一进程可能同时开多个套接字（请求 + 事件监听），用计数器区分
+        static SEQ: AtomicU32 = AtomicU32::new(0);
+        let local = std::env::temp_dir().join(format!(
+            "rofi-rwifi-wpa-{}-{}",
+            std::process::id(),
+            SEQ.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_file(&local);
+        let sock = UnixDatagram::bind(&local)?;
+        let ctrl = Self { sock, local };
+        ctrl.sock
+            .connect(remote)
+            .map_err(|e| anyhow!(t!(CannotConnect, path = remote.display(), error = e)))?;
+        Ok(ctrl)
+    }
+
+    /// 发送命令并等待回复（跳过以 '<' 开头的异步事件）
+    async fn request(&self, cmd: &str) -> Result<String> {
+        self.sock.send(cmd.as_bytes()).await?;
+        let mut buf = vec![0u8; 8192];
+        loop {
+            let n = tokio::time::timeout(REPLY_TIMEOUT, self.sock.recv(&mut buf))
+                .await
+                .map_err(|_| anyhow!(t!(WpaNoResponse, cmd = redact(cmd))))??;
+            let reply = String::from_utf8_lossy(&buf[..n]).to_string();
+            if !reply.starts_with('<') {
+                return Ok(reply);
+            }
+        }
+    }
+
+    /// 期望回复 "OK" 的命令
+    async fn ok(&self, cmd: &str) -> Result<()> {
+        let reply = self.request(cmd).await?;
+        if reply.trim() == "OK" {
+            Ok(())
+        } else {
+            Err(anyhow!("{}: {}", redact(cmd), reply.trim()))
+        }
+    }
+
+    /// 等待下一条异步事件
+    async fn event(&self) -> Result<String> {
+        let mut buf = vec![0u8; 4096];
+        let n = self.sock.recv(&mut buf).await?;
+        Ok(String::from_utf8_lossy(&buf[..n]).to_string())
+    }
+}
+
+impl Drop for CtrlSocket {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.local);
+    }
+}
+
+/// 去掉 SET_NETWORK 命令里的密钥值，错误信息会进通知和终端输出
+fn redact(cmd: &str) -> String {
+    let mut parts = cmd.splitn(4, ' ');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("SET_NETWORK"), Some(id), Some(field)) if SECRET_FIELDS.contains(&field) => {
+            format!("SET_NETWORK {id} {field}")
+        }
+        _ => cmd.to_string(),
+    }
+}
+
+/// LIST_NETWORKS 中的一行
+struct ConfiguredNetwork {
+    id: String,
+    ssid: String,
+}
+
+impl WpaSupplicant {
+    pub fn new(ifaces: Interfaces) -> Self {
+        Self {
+            ctrl_dir: PathBuf::from(CTRL_DIR),
+            ifaces,
+        }
+    }
+
+    /// 控制目录下的无线接口（跳过 p2p-dev-*），按名称排序
+    fn ifnames(&self) -> Result<Vec<String>> {
+        let mut names: Vec<String> = std::fs::read_dir(&self.ctrl_dir)
+            .map_err(|e| anyhow!(t!(CannotRead, path = self.ctrl_dir.display(), error = e)))?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|n| !n.starts_with("p2p-dev-"))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// 指定的接口须有控制套接字；None 取第一个
+    fn resolve(&self, ifname: Option<String>) -> Result<String> {
+        let mut names = self.ifnames()?.into_iter();
+        match ifname {
+            Some(name) => names
+                .find(|n| *n == name)
+                .ok_or_else(|| anyhow!(t!(WpaNoInterface, name = name))),
+            None => names.next().ok_or_else(|| anyhow!(t!(WpaNoCtrl))),
+        }
+    }
+
+    /// 当前选中的接口
+    fn ifname(&self) -> Result<String> {
+        self.resolve(self.ifaces.station())
+    }
+
+    async fn ctrl(&self) -> Result<CtrlSocket> {
+        CtrlSocket::open(&self.ctrl_dir.join(self.ifname()?)).await
+    }
+
+    /// 热点接口的控制套接字
+    async fn hotspot_ctrl(&self) -> Result<CtrlSocket> {
+        let ifname = self.resolve(self.ifaces.hotspot())?;
+        CtrlSocket::open(&self.ctrl_dir.join(ifname)).await
+    }
+
+    async fn status(&self) -> Result<HashMap<String, String>> {
+        Ok(parse_key_values(
+            &self.ctrl().await?.request("STATUS").await?,
+        ))
+    }
+
+    async fn networks(&self, ctrl: &CtrlSocket) -> Result<Vec<ConfiguredNetwork>> {
+        Ok(parse_list_networks(&ctrl.request("LIST_NETWORKS").await?))
+    }
+
+    async fn network_id(&self, ctrl: &CtrlSocket, name: &str) -> Result<String> {
+        self.networks(ctrl)
+            .await?
+            .into_iter()
+            .find(|n| n.ssid == name)
+            .map(|n| n.id)
+            .ok_or_else(|| anyhow!(t!(SavedNotFound, name = name)))
+    }
+
+    /// AP 模式（mode=2）的网络 id 与 SSID
+    async fn ap_networks(&self, ctrl: &CtrlSocket) -> Result<Vec<ConfiguredNetwork>> {
+        let mut out = vec![];
+        for n in self.networks(ctrl).await? {
+            let mode = ctrl.request(&format!("GET_NETWORK {} mode", n.id)).await?;
+            if mode.trim() == "2" {
+                out.push(n);
+            }
+        }
+        Ok(out)
+    }
+
+    /// 扫描结果中该 SSID 的加密方式；隐藏网络等扫不到时为 None
+    async fn scanned_security(&self, ctrl: &CtrlSocket, ssid: &str) -> Option<Security> {
+        let results = ctrl.request("SCAN_RESULTS").await.ok()?;
+        group_by_ssid(parse_scan_results(&results, ""))
+            .into_iter()
+            .find(|ap| ap.ssid == ssid)
+            .map(|ap| ap.security)
+    }
+
+    /// 选中网络并监听事件，直到连上、密码被拒或超时
+    async fn select_and_wait(&self, ctrl: &CtrlSocket, id: &str, timeout: u64) -> ConnectResult {
+        let monitor = match self.ctrl().await {
+            Ok(m) => m,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        if let Err(e) = monitor.ok("ATTACH").await {
+            return ConnectResult::Failed(e.to_string());
+        }
+        if let Err(e) = ctrl.ok(&format!("SELECT_NETWORK {id}")).await {
+            return ConnectResult::Failed(e.to_string());
+        }
+
+        // Ok(None) 表示已连上，Ok(Some(..)) 为失败原因
+        let wait = async {
+            loop {
+                let ev = monitor.event().await?;
+                if ev.contains("CTRL-EVENT-CONNECTED") {
+                    return anyhow::Ok(None);
+                }
+                if ev.contains("CTRL-EVENT-SSID-TEMP-DISABLED") && ev.contains("WRONG_KEY") {
+                    return Ok(Some(ConnectResult::WrongPassword));
+                }
+                // 802.1X：服务器拒绝或证书校验失败
+                if ev.contains("CTRL-EVENT-EAP-FAILURE") || ev.contains("EAP-TLS-CERT-ERROR") {
+                    let msg = ev.split_once('>').map_or(ev.as_str(), |(_, m)| m);
+                    return Ok(Some(ConnectResult::EapFailed(msg.trim().to_string())));
+                }
+                if ev.contains("CTRL-EVENT-NETWORK-NOT-FOUND") {
+                    return Ok(Some(ConnectResult::NotFound));
+                }
+            }
+        };
+        let res = tokio::time::timeout(Duration::from_secs(timeout), wait).await;
+        let _ = monitor.ok("DETACH").await;
+
+        match res {
+            Ok(Ok(None)) => {
+                let ip = self.get_ip().await.unwrap_or_else(|| t!(Unknown).into());
+                ConnectResult::Success { ip }
+            }
+            Ok(Ok(Some(failure))) => failure,
+            Ok(Err(e)) => ConnectResult::Failed(e.to_string()),
+            Err(_) => ConnectResult::Timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl NetworkBackend for WpaSupplicant {
+    fn name(&self) -> &'static str {
+        "wpa_supplicant"
+    }
+
+    // ── 查询 ─────────────────────────────────────────────────
+
+    async fn rescan(&self) {
+        if let Ok(ctrl) = self.ctrl().await {
+            let _ = ctrl.request("SCAN").await;
+        }
+    }
+
+    async fn list_access_points(&self) -> Result<Vec<AccessPoint>> {
+        let ctrl = self.ctrl().await?;
+        let status = parse_key_values(&ctrl.request("STATUS").await?);
+        let current = status.get("bssid").cloned().unwrap_or_default();
+        let entries = parse_scan_results(&ctrl.request("SCAN_RESULTS").await?, &current);
+        Ok(group_by_ssid(entries))
+    }
+
+    async fn current_ssid(&self) -> Option<String> {
+        let status = self.status().await.ok()?;
+        if status.get("wpa_state").map(String::as_str) != Some("COMPLETED") {
+            return None;
+        }
+        status.get("ssid").map(|s| decode_ssid(s))
+    }
+
+    async fn current_link(&self) -> Option<(String, u8)> {
+        let ssid = self.current_ssid().await?;
+        let poll = self.ctrl().await.ok()?.request("SIGNAL_POLL").await.ok()?;
+        let signal = parse_key_values(&poll)
+            .get("RSSI")
+            .and_then(|r| r.parse::<i32>().ok())
+            .map_or(0, dbm_to_percent);
+        Some((ssid, signal))
+    }
+
+    async fn saved_connections(&self) -> Result<Vec<String>> {
+        let ctrl = self.ctrl().await?;
+        Ok(self
+            .networks(&ctrl)
+            .await?
+            .into_iter()
+            .map(|n| n.ssid)
+            .collect())
+    }
+
+    /// scan_ssid=1 的网络视为隐藏网络
+    async fn saved_hidden(&self) -> Result<Vec<String>> {
+        let ctrl = self.ctrl().await?;
+        let mut hidden = vec![];
+        for n in self.networks(&ctrl).await? {
+            let scan_ssid = ctrl
+                .request(&format!("GET_NETWORK {} scan_ssid", n.id))
+                .await?;
+            if scan_ssid.trim() == "1" {
+                hidden.push(n.ssid);
+            }
+        }
+        Ok(hidden)
+    }
+
+    /// wpa_supplicant 不会通过控制接口返回 psk，只能读明文配置
+    async fn saved_password(&self, name: &str) -> Option<String> {
+        let ctrl = self.ctrl().await.ok()?;
+        let id = self.network_id(&ctrl, name).await.ok()?;
+        let psk = ctrl.request(&format!("GET_NETWORK {id} psk")).await.ok()?;
+        let psk = psk.trim();
+        // 返回 "*" 或 FAIL 表示不可读
+        psk.strip_prefix('"')
+            .and_then(|p| p.strip_suffix('"'))
+            .map(str::to_string)
+    }
+
+    async fn get_ip(&self) -> Option<String> {
+        // 稍等一下让 DHCP 完成
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        netinfo::ipv4_address(&self.ifname().ok()?)
+    }
+
+    async fn get_details(&self, ssid: &str, ping_host: &str) -> Result<ConnectionDetails> {
+        let ifname = self.ifname()?;
+        let ctrl = self.ctrl().await?;
+        let (ping_ms, status, poll) =
+            tokio::join!(ping::ping_once(ping_host), ctrl.request("STATUS"), async {
+                // 请求和 SIGNAL_POLL 不能共用一个套接字并发收发
+                match self.ctrl().await {
+                    Ok(c) => c.request("SIGNAL_POLL").await,
+                    Err(e) => Err(e),
+                }
+            },);
+        let status = parse_key_values(&status?);
+        let signal = poll
+            .ok()
+            .map(|p| parse_key_values(&p))
+            .and_then(|p| p.get("RSSI").and_then(|r| r.parse::<i32>().ok()))
+            .map(|dbm| dbm_to_percent(dbm).to_string())
+            .unwrap_or_else(|| "--".into());
+
+        let dns = netinfo::dns_servers().join(", ");
+        Ok(ConnectionDetails {
+            ssid: ssid.to_string(),
+            ip: netinfo::ipv4_address(&ifname).unwrap_or_else(|| "N/A".into()),
+            gateway: netinfo::default_gateway(&ifname).unwrap_or_else(|| "N/A".into()),
+            dns: if dns.is_empty() { "N/A".into() } else { dns },
+            security: status
+                .get("key_mgmt")
+                .cloned()
+                .unwrap_or_else(|| "--".into()),
+            signal,
+            ping_ms,
+        })
+    }
+
+    fn interfaces(&self) -> &Interfaces {
+        &self.ifaces
+    }
+
+    async fn devices(&self) -> Result<Vec<WifiDevice>> {
+        let mut devices = Vec::new();
+        for name in self.ifnames()? {
+            let status = match CtrlSocket::open(&self.ctrl_dir.join(&name)).await {
+                Ok(c) => parse_key_values(&c.request("STATUS").await.unwrap_or_default()),
+                Err(_) => HashMap::new(),
+            };
+            let state = status
+                .get("wpa_state")
+                .map_or("unknown".into(), |s| s.to_lowercase());
+            let connection = (state == "completed")
+                .then(|| status.get("ssid").map(|s| decode_ssid(s)))
+                .flatten();
+            devices.push(WifiDevice {
+                name,
+                state,
+                connection,
+            });
+        }
+        Ok(devices)
+    }
+
+    // ── 连接管理 ─────────────────────────────────────────────
+
+    async fn connect_saved(&self, name: &str, bssid: Option<&str>, timeout: u64) -> ConnectResult {
+        let ctrl = match self.ctrl().await {
+            Ok(c) => c,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        let id = match self.network_id(&ctrl, name).await {
+            Ok(id) => id,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        // 锁定 BSSID；未指定时清除之前的锁定（"any"）
+        if let Err(e) = ctrl
+            .ok(&format!("BSSID {id} {}", bssid.unwrap_or("any")))
+            .await
+        {
+            return ConnectResult::Failed(e.to_string());
+        }
+        let result = self.select_and_wait(&ctrl, &id, timeout).await;
+        // SELECT_NETWORK 会禁用其它所有网络，无论成败都要恢复它们的自动连接
+        let _ = ctrl.ok("ENABLE_NETWORK all").await;
+        result
+    }
+
+    async fn connect_new(&self, req: &ConnectRequest, timeout: u64) -> ConnectResult {
+        let ctrl = match self.ctrl().await {
+            Ok(c) => c,
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+        let id = match ctrl.request("ADD_NETWORK").await {
+            Ok(id) if id.trim().parse::<u32>().is_ok() => id.trim().to_string(),
+            Ok(other) => return ConnectResult::Failed(format!("ADD_NETWORK: {}", other.trim())),
+            Err(e) => return ConnectResult::Failed(e.to_string()),
+        };
+
+        // SSID 用十六进制写入，免去引号转义
+        let mut cmds = vec![format!("SET_NETWORK {id} ssid {}", hex(&req.ssid))];
+        match (&req.enterprise, &req.password) {
+            (Some(eap), _) => cmds.extend(eap_commands(&id, eap)),
+            (None, password) => {
+                let security = self.scanned_security(&ctrl, &req.ssid).await;
+                cmds.extend(key_commands(&id, password.as_deref(), security.as_ref()));
+            }
+        }
+        if let Some(bssid) = &req.bssid {
+            cmds.push(format!("SET_NETWORK {id} bssid {bssid}"));
+        }
+        if req.hidden {
+            // 隐藏网络需要在探测请求中携带 SSID
+            cmds.push(format!("SET_NETWORK {id} scan_ssid 1"));
+        }
+        for cmd in &cmds {
+            if let Err(e) = ctrl.ok(cmd).await {
+                let _ = ctrl.ok(&format!("REMOVE_NETWORK {id}")).await;
+                return ConnectResult::Failed(e.to_string());
+            }
+        }
+
+        let result = self.select_and_wait(&ctrl, &id, timeout).await;
+        if matches!(result, ConnectResult::Success { .. }) {
+            // 先恢复被 SELECT_NETWORK 禁用的网络，否则 disabled=1 会被写进配置文件
+            let _ = ctrl.ok("ENABLE_NETWORK all").await;
+            let _ = ctrl.ok("SAVE_CONFIG").await;
+        } else {
+            // 清理残留配置，并恢复其他网络的自动连接
+            let _ = ctrl.ok(&format!("REMOVE_NETWORK {id}")).await;
+            let _ = ctrl.ok("ENABLE_NETWORK all").await;
+        }
+        result
+    }
+
+    async fn disconnect(&self, _name: &str) -> Result<()> {
+        self.ctrl()
+            .await?
+            .ok("DISCONNECT")
+            .await
+            .map_err(|_| anyhow!(t!(DisconnectFailed)))
+    }
+
+    async fn forget(&self, name: &str) -> Result<()> {
+        let ctrl = self.ctrl().await?;
+        let id = self.network_id(&ctrl, name).await?;
+        ctrl.ok(&format!("REMOVE_NETWORK {id}"))
+            .await
+            .map_err(|_| anyhow!(t!(ForgetFailed)))?;
+        let _ = ctrl.ok("SAVE_CONFIG").await;
+        Ok(())
+    }
+
+    // ── 无线电 & 热点 ─────────────────────────────────────────
+
+    /// wpa_supplicant 不管理 rfkill，接口存在即视为开启
+    async fn radio_state(&self) -> RadioState {
+        if self.ifname().is_ok() {
+            RadioState::Enabled
+        } else {
+            RadioState::Disabled
+        }
+    }
+
+    async fn set_radio(&self, _enable: bool) -> Result<()> {
+        Err(anyhow!(t!(WpaNoRadio)))
+    }
+
+    async fn hotspot_active(&self) -> Option<String> {
+        let ctrl = self.hotspot_ctrl().await.ok()?;
+        let status = parse_key_values(&ctrl.request("STATUS").await.ok()?);
+        if status.get("mode").map(String::as_str) == Some("AP") {
+            status.get("ssid").map(|s| decode_ssid(s))
+        } else {
+            None
+        }
+    }
+
+    async fn hotspot_profile(&self) -> Option<String> {
+        let ctrl = self.hotspot_ctrl().await.ok()?;
+        self.ap_networks(&ctrl)
+            .await
+            .ok()?
+            .into_iter()
+            .next()
+            .map(|n| n.ssid)
+    }
+
+    async fn hotspot_up(&self, name: &str) -> Result<()> {
+        let ctrl = self.hotspot_ctrl().await?;
+        let id = self.network_id(&ctrl, name).await?;
+        ctrl.ok(&format!("SELECT_NETWORK {id}")).await
+    }
+
+    async fn hotspot_down(&self, name: &str) -> Result<()> {
+        let ctrl = self.hotspot_ctrl().await?;
+        let id = self.network_id(&ctrl, name).await?;
+        // 恢复所有网络后单独禁用热点，让 station 重新关联
+        ctrl.ok("ENABLE_NETWORK all").await?;
+        ctrl.ok(&format!("DISABLE_NETWORK {id}")).await?;
+        ctrl.ok("REASSOCIATE").await
+    }
+
+    async fn create_hotspot(&self, ssid: &str, password: &str) -> Result<()> {
+        let ctrl = self.hotspot_ctrl().await?;
+        let id = ctrl.request("ADD_NETWORK").await?.trim().to_string();
+        let cmds = [
+            format!("SET_NETWORK {id} ssid {}", hex(ssid)),
+            format!("SET_NETWORK {id} mode 2"),
+            format!("SET_NETWORK {id} frequency 2437"),
+            format!("SET_NETWORK {id} key_mgmt WPA-PSK"),
+            format!("SET_NETWORK {id} proto RSN"),
+            format!("SET_NETWORK {id} pairwise CCMP"),
+            format!("SET_NETWORK {id} psk \"{password}\""),
+        ];
+        for cmd in &cmds {
+            if let Err(e) = ctrl.ok(cmd).await {
+                let _ = ctrl.ok(&format!("REMOVE_NETWORK {id}")).await;
+                return Err(anyhow!("{}: {e}", t!(HotspotCreateFailed)));
+            }
+        }
+        // 在 SELECT_NETWORK 禁用其它网络之前保存，配置文件里不会留下 disabled=1
+        let _ = ctrl.ok("SAVE_CONFIG").await;
+        ctrl.ok(&format!("SELECT_NETWORK {id}"))
+            .await
+            .map_err(|e| anyhow!("{}: {e}", t!(HotspotCreateFailed)))
+    }
+}
+
+// ── 回复解析 ─────────────────────────────────────────────────
+
+/// STATUS / SIGNAL_POLL 的 key=value 行
+fn parse_key_values(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// SCAN_RESULTS：bssid / frequency / signal level / flags / ssid（Tab 分隔，首行是表头）
+fn parse_scan_results(text: &str, current_bssid: &str) -> Vec<ScanEntry> {
+    text.lines()
+        .skip(1)
+        .filter_map(|l| {
+            let cols: Vec<&str> = l.splitn(5, '\t').collect();
+            if cols.len() < 4 {
+                return None;
+            }
+            // 隐藏网络的 SSID 为空或全是 \x00，统一记为空串
+            let mut ssid = decode_ssid(cols.get(4).copied().unwrap_or(""));
+            if ssid.chars().all(|c| c == '\0') {
+                ssid.clear();
+            }
+            let signal = dbm_to_percent(cols[2].parse().unwrap_or(-100));
+            let in_use = !current_bssid.is_empty() && cols[0].eq_ignore_ascii_case(current_bssid);
+            let mut bss = Bss::new(
+                cols[0].to_uppercase(),
+                cols[1].parse().unwrap_or(0),
+                signal,
+                in_use,
+            );
+            bss.mode = if cols[3].contains("[IBSS]") {
+                ApMode::AdHoc
+            } else if cols[3].contains("[MESH]") {
+                ApMode::Mesh
+            } else {
+                ApMode::Infra
+            };
+            Some(ScanEntry {
+                ssid,
+                security: parse_security(cols[3]),
+                bss,
+            })
+        })
+        .collect()
+}
+
+/// LIST_NETWORKS：network id / ssid / bssid / flags
+fn parse_list_networks(text: &str) -> Vec<ConfiguredNetwork> {
+    text.lines()
+        .skip(1)
+        .filter_map(|l| {
+            let cols: Vec<&str> = l.split('\t').collect();
+            if cols.len() < 2 {
+                return None;
+            }
+            Some(ConfiguredNetwork {
+                id: cols[0].to_string(),
+                ssid: decode_ssid(cols[1]),
+            })
+        })
+        .collect()
+}
+
+/// 个人网络的 key_mgmt 与密钥：按扫描到的加密方式选择，扫不到时有密码按 WPA-PSK、无密码按开放网络
+fn key_commands(id: &str, password: Option<&str>, security: Option<&Security>) -> Vec<String> {
+    let set = |key: &str, value: &str| format!("SET_NETWORK {id} {key} {value}");
+    let Some(p) = password else {
+        return if security.is_some_and(|s| s.is_owe()) {
+            vec![set("key_mgmt", "OWE"), set("ieee80211w", "2")]
+        } else {
+            vec![set("key_mgmt", "NONE")]
+        };
+    };
+    let quoted = format!("\"{p}\"");
+
+    if security.is_some_and(|s| s.is_wep()) {
+        // 10 / 26 位十六进制是原始密钥，其余（5 / 13 个字符）为 ASCII 密钥
+        let key = if is_hex(p) && matches!(p.len(), 10 | 26) {
+            p
+        } else {
+            &quoted
+        };
+        return vec![
+            set("key_mgmt", "NONE"),
+            set("wep_key0", key),
+            set("wep_tx_keyidx", "0"),
+        ];
+    }
+
+    let km = security.map_or(&[][..], |s| &s.key_mgmt[..]);
+    let (key_mgmt, pmf) = match (km.contains(&KeyMgmt::Psk), km.contains(&KeyMgmt::Sae)) {
+        // WPA3 个人版要求管理帧保护，过渡模式设为可选
+        (false, true) => ("SAE", Some("2")),
+        (true, true) => ("WPA-PSK SAE", Some("1")),
+        _ => ("WPA-PSK", None),
+    };
+    let mut cmds = vec![set("key_mgmt", key_mgmt)];
+    if let Some(pmf) = pmf {
+        cmds.push(set("ieee80211w", pmf));
+    }
+    // 64 位十六进制是原始 PSK，不加引号；SAE 只接受口令
+    let raw = is_hex(p) && p.len() == 64 && key_mgmt != "SAE";
+    cmds.push(set("psk", if raw { p } else { &quoted }));
+    cmds
+}
+
+/// 802.1X 网络的 SET_NETWORK 命令
+fn eap_commands(id: &str, eap: &EnterpriseConfig) -> Vec<String> {
+    let mut cmds = vec![
+        format!("SET_NETWORK {id} key_mgmt WPA-EAP"),
+        format!("SET_NETWORK {id} eap {}", eap.eap),
+        format!("SET_NETWORK {id} identity \"{}\"", eap.identity),
+    ];
+    if let Some(p) = eap.phase2 {
+        cmds.push(format!("SET_NETWORK {id} phase2 \"auth={p}\""));
+    }
+    let quoted = [
+        ("anonymous_identity", &eap.anonymous_identity),
+        ("ca_cert", &eap.ca_cert),
+        ("domain_suffix_match", &eap.domain_match),
+        ("password", &eap.password),
+        ("client_cert", &eap.client_cert),
+        ("private_key", &eap.private_key),
+        ("private_key_passwd", &eap.private_key_password),
+    ];
+    for (key, value) in quoted {
+        if let Some(v) = value {
+            cmds.push(format!("SET_NETWORK {id} {key} \"{v}\""));
+        }
+    }
+    cmds
+}
+
+/// 扫描结果的 flags（如 "[WPA-PSK-TKIP][WPA2-PSK+SAE-CCMP][ESS]"）解析为加密描述
+fn parse_security(flags: &str) -> Security {
+    fn add<T: PartialEq>(list: &mut Vec<T>, item: T) {
+        if !list.contains(&item) {
+            list.push(item);
+        }
+    }
+
+    let mut sec = Security::default();
+    for tag in flags.split(['[', ']']).filter(|t| !t.is_empty()) {
+        if tag == "WEP" {
+            return Security::wep();
+        }
+        // 只关心 WPA-* / WPA2-* / RSN-*，ESS、IBSS、P2P 等跳过
+        let Some((proto, rest)) = tag.split_once('-') else {
+            continue;
+        };
+        let gen = match proto {
+            "WPA" => WpaGen::Wpa1,
+            "WPA2" | "RSN" => WpaGen::Wpa2,
+            _ => continue,
+        };
+        for token in rest.split(['-', '+', '/']) {
+            match token {
+                "PSK" => {
+                    add(&mut sec.key_mgmt, KeyMgmt::Psk);
+                    add(&mut sec.wpa, gen);
+                }
+                "EAP" => {
+                    add(&mut sec.key_mgmt, KeyMgmt::Eap);
+                    add(&mut sec.wpa, gen);
+                }
+                "SAE" | "OWE" | "SUITE" => {
+                    let km = match token {
+                        "SAE" => KeyMgmt::Sae,
+                        "OWE" => KeyMgmt::Owe,
+                        _ => KeyMgmt::Eap,
+                    };
+                    add(&mut sec.key_mgmt, km);
+                    add(&mut sec.wpa, WpaGen::Wpa3);
+                }
+                "TKIP" => add(&mut sec.ciphers, Cipher::Tkip),
+                "CCMP" => add(&mut sec.ciphers, Cipher::Ccmp),
+                "GCMP" => add(&mut sec.ciphers, Cipher::Gcmp),
+                _ => {}
+            }
+        }
+    }
+    sec.wpa.sort();
+    sec
+}
+
+/// 还原 wpa_supplicant 的 printf_encode 转义（\\xNN、\\\\、\\"）
+fn decode_ssid(s: &str) -> String {
+    let mut out: Vec<u8> = Vec::with_capacity(s.len());
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 1 < bytes.len() {
+            match bytes[i + 1] {
+                b'x' if i + 3 < bytes.len() => {
+                    let digits = std::str::from_utf8(&bytes[i + 2..i + 4]).unwrap_or("");
+                    if let Ok(b) = u8::from_str_radix(digits, 16) {
+                        out.push(b);
+                        i += 4;
+                        continue;
+                    }
+                }
+                b'n' => {
+                    out.push(b'\n');
+                    i += 2;
+                    continue;
+                }
+                b'r' => {
+                    out.push(b'\r');
+                    i += 2;
+                    continue;
+                }
+                b't' => {
+                    out.push(b'\t');
+                    i += 2;
+                    continue;
+                }
+                b'e' => {
+                    out.push(0x1b);
+                    i += 2;
+                    continue;
+                }
+                c @ (b'\\' | b'"') => {
+                    out.push(c);
+                    i += 2;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn hex(s: &str) -> String {
+    s.bytes().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EapMethod;
+    use std::sync::{Arc, Mutex};
+
+    const STATUS: &str = "bssid=aa:bb:cc:dd:ee:01\nfreq=5180\nssid=Home\nid=0\nmode=station\n\
+                          key_mgmt=WPA2-PSK\nwpa_state=COMPLETED\n";
+    const SCAN_RESULTS: &str = "bssid / frequency / signal level / flags / ssid\n\
+        aa:bb:cc:dd:ee:02\t2437\t-70\t[WPA2-PSK-CCMP][ESS]\tHome\n\
+        aa:bb:cc:dd:ee:01\t5180\t-50\t[WPA2-PSK-CCMP][ESS]\tHome\n\
+        aa:bb:cc:dd:ee:03\t2412\t-60\t[WPA2-PSK+SAE-CCMP][ESS]\tCaf\\xc3\\xa9 \\\"A\\\"\n\
+        aa:bb:cc:dd:ee:04\t5500\t-80\t[RSN-SAE-CCMP][ESS]\tWPA3\n\
+        aa:bb:cc:dd:ee:05\t2462\t-75\t[WEP][ESS]\tOld\n\
+        aa:bb:cc:dd:ee:06\t2412\t-90\t[ESS]\tFree\n\
+        aa:bb:cc:dd:ee:07\t2412\t-65\t[WPA2-PSK-CCMP][ESS]\t\\x00\\x00\\x00\n\
+        aa:bb:cc:dd:ee:08\t5745\t-55\t[WPA2-EAP-CCMP][ESS]\tCorp\n\
+        aa:bb:cc:dd:ee:09\t2412\t-85\t[WPA2-PSK-CCMP][IBSS]\tAdhoc\n";
+    const LIST_NETWORKS: &str = "network id / ssid / bssid / flags\n\
+        0\tHome\tany\t[CURRENT]\n\
+        1\tWork\tany\t\n";
+    const CONNECTED: &str =
+        "<3>CTRL-EVENT-CONNECTED - Connection to aa:bb:cc:dd:ee:03 completed [id=2]";
+    const WRONG_KEY: &str =
+        "<3>CTRL-EVENT-SSID-TEMP-DISABLED id=2 ssid=\"WPA3\" auth_failures=1 duration=10 reason=WRONG_KEY";
+
+    /// 本地假的控制套接字：按命令回复固定内容，SELECT_NETWORK 后向 ATTACH 过的套接字推送 events
+    struct Fake {
+        dir: PathBuf,
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Fake {
+        fn start(name: &str, events: &'static [&'static str]) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("rofi-rwifi-wpa-test-{}-{name}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let server = UnixDatagram::bind(dir.join("wlan0")).unwrap();
+            let sent = Arc::new(Mutex::new(vec![]));
+            let log = sent.clone();
+            tokio::spawn(async move {
+                let mut monitor: Option<PathBuf> = None;
+                let mut buf = vec![0u8; 4096];
+                while let Ok((n, from)) = server.recv_from(&mut buf).await {
+                    let Some(from) = from.as_pathname().map(Path::to_path_buf) else {
+                        continue;
+                    };
+                    let cmd = String::from_utf8_lossy(&buf[..n]).to_string();
+                    log.lock().unwrap().push(cmd.clone());
+                    let reply = match cmd.split(' ').next().unwrap_or_default() {
+                        "STATUS" => STATUS,
+                        "SIGNAL_POLL" => "RSSI=-50\nLINKSPEED=866\nFREQUENCY=5180\n",
+                        "SCAN_RESULTS" => SCAN_RESULTS,
+                        "LIST_NETWORKS" => LIST_NETWORKS,
+                        "ADD_NETWORK" => "2\n",
+                        "GET_NETWORK" => "FAIL\n",
+                        // 值里带 rejected 的设置模拟被 wpa_supplicant 拒绝
+                        "SET_NETWORK" if cmd.contains("rejected") => "FAIL\n",
+                        "ATTACH" => {
+                            monitor = Some(from.clone());
+                            "OK\n"
+                        }
+                        _ => "OK\n",
+                    };
+                    let _ = server.send_to(reply.as_bytes(), &from).await;
+                    if let (true, Some(m)) = (cmd.starts_with("SELECT_NETWORK"), &monitor) {
+                        for ev in events {
+                            let _ = server.send_to(ev.as_bytes(), m).await;
+                        }
+                    }
+                }
+            });
+            Self { dir, sent }
+        }
+
+        fn backend(&self) -> WpaSupplicant {
+            WpaSupplicant {
+                ctrl_dir: self.dir.clone(),
+                ifaces: Interfaces::new(None, None),
+            }
+        }
+
+        fn sent(&self) -> Vec<String> {
+            self.sent.lock().unwrap().clone()
+        }
+
+        /// cmd 在 later 之前发出过
+        fn sent_before(&self, cmd: &str, later: &str) -> bool {
+            let sent = self.sent();
+            let pos = |c: &str| sent.iter().position(|s| s == c);
+            matches!((pos(cmd), pos(later)), (Some(a), Some(b)) if a < b)
+        }
+    }
+
+    impl Drop for Fake {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn request(ssid: &str, password: Option<&str>) -> ConnectRequest {
+        ConnectRequest {
+            ssid: ssid.into(),
+            password: password.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn scan_results_are_grouped_and_parsed() {
+        let fake = Fake::start("scan", &[]);
+        let aps = fake.backend().list_access_points().await.unwrap();
+        let find = |ssid: &str| aps.iter().find(|ap| ap.ssid == ssid).unwrap();
+
+        let home = &aps[0];
+        assert_eq!(home.ssid, "Home");
+        assert!(home.in_use);
+        assert_eq!(home.signal, 100);
+        assert_eq!(home.bss.len(), 2);
+        assert_eq!(home.bss[0].bssid, "AA:BB:CC:DD:EE:01");
+        assert!(home.bss[0].in_use && !home.bss[1].in_use);
+        assert_eq!(home.security, Security::wpa2_psk());
+
+        let cafe = find("Café \"A\"");
+        assert_eq!(cafe.security.key_mgmt, [KeyMgmt::Psk, KeyMgmt::Sae]);
+        assert_eq!(cafe.security.wpa, [WpaGen::Wpa2, WpaGen::Wpa3]);
+        assert!(find("WPA3").security.is_sae_only());
+        assert!(find("Old").security.is_wep());
+        assert!(find("Free").security.is_open());
+        assert!(find("Corp").security.is_enterprise());
+        assert_eq!(find("Adhoc").bss[0].mode, ApMode::AdHoc);
+
+        let hidden = find("");
+        assert!(hidden.hidden);
+        assert_eq!(hidden.signal, 70);
+    }
+
+    #[tokio::test]
+    async fn status_and_saved_networks() {
+        let fake = Fake::start("status", &[]);
+        let wpa = fake.backend();
+        assert_eq!(wpa.current_ssid().await.as_deref(), Some("Home"));
+        assert_eq!(
+            wpa.current_link().await,
+            Some(("Home".into(), dbm_to_percent(-50)))
+        );
+        assert_eq!(wpa.saved_connections().await.unwrap(), ["Home", "Work"]);
+        assert_eq!(wpa.saved_password("Work").await, None);
+    }
+
+    #[tokio::test]
+    async fn wrong_key_removes_network_and_restores_others() {
+        let fake = Fake::start("wrong-key", &[WRONG_KEY]);
+        let result = fake
+            .backend()
+            .connect_new(&request("WPA3", Some("hunter22")), 5)
+            .await;
+        assert!(matches!(result, ConnectResult::WrongPassword), "{result:?}");
+
+        let sent = fake.sent();
+        assert!(sent.contains(&"SET_NETWORK 2 key_mgmt SAE".into()));
+        assert!(sent.contains(&"SET_NETWORK 2 ieee80211w 2".into()));
+        assert!(sent.contains(&"SET_NETWORK 2 psk \"hunter22\"".into()));
+        assert!(fake.sent_before("REMOVE_NETWORK 2", "ENABLE_NETWORK all"));
+        assert!(!sent.contains(&"SAVE_CONFIG".into()));
+    }
+
+    #[tokio::test]
+    async fn rejected_password_is_not_echoed() {
+        let fake = Fake::start("rejected", &[]);
+        let result = fake
+            .backend()
+            .connect_new(&request("Home", Some("rejected-secret")), 5)
+            .await;
+        match result {
+            ConnectResult::Failed(msg) => {
+                assert_eq!(msg, "SET_NETWORK 2 psk: FAIL");
+            }
+            other => panic!("{other:?}"),
+        }
+        assert!(fake.sent().contains(&"REMOVE_NETWORK 2".into()));
+    }
+
+    #[tokio::test]
+    async fn rejected_eap_password_is_not_echoed() {
+        let fake = Fake::start("rejected-eap", &[]);
+        let req = ConnectRequest {
+            enterprise: Some(EnterpriseConfig {
+                eap: EapMethod::Peap,
+                phase2: None,
+                identity: "alice".into(),
+                anonymous_identity: None,
+                ca_cert: None,
+                domain_match: None,
+                password: Some("rejected-secret".into()),
+                client_cert: None,
+                private_key: None,
+                private_key_password: None,
+            }),
+            ..request("Corp", None)
+        };
+        let result = fake.backend().connect_new(&req, 5).await;
+        match result {
+            ConnectResult::Failed(msg) => assert_eq!(msg, "SET_NETWORK 2 password: FAIL"),
+            other => panic!("{other:?}"),
+        }
+        assert_eq!(
+            redact("SET_NETWORK 2 private_key_passwd \"x y\""),
+            "SET_NETWORK 2 private_key_passwd"
+        );
+        assert_eq!(
+            redact("SET_NETWORK 2 identity \"alice\""),
+            "SET_NETWORK 2 identity \"alice\""
+        );
+    }
+
+    #[tokio::test]
+    async fn no_event_times_out() {
+        let fake = Fake::start("timeout", &[]);
+        let result = fake
+            .backend()
+            .connect_new(&request("Home", Some("password")), 1)
+            .await;
+        assert!(matches!(result, ConnectResult::Timeout), "{result:?}");
+        assert!(fake.sent().contains(&"REMOVE_NETWORK 2".into()));
+    }
+
+    #[tokio::test]
+    async fn success_enables_others_before_saving() {
+        let fake = Fake::start("success", &[CONNECTED]);
+        let psk = "0123456789abcdef".repeat(4);
+        let result = fake
+            .backend()
+            .connect_new(&request("Café \"A\"", Some(&psk)), 5)
+            .await;
+        assert!(
+            matches!(result, ConnectResult::Success { .. }),
+            "{result:?}"
+        );
+
+        let sent = fake.sent();
+        assert!(sent.contains(&format!("SET_NETWORK 2 ssid {}", hex("Café \"A\""))));
+        assert!(sent.contains(&"SET_NETWORK 2 key_mgmt WPA-PSK SAE".into()));
+        assert!(sent.contains(&"SET_NETWORK 2 ieee80211w 1".into()));
+        assert!(sent.contains(&format!("SET_NETWORK 2 psk {psk}")));
+        assert!(fake.sent_before("ENABLE_NETWORK all", "SAVE_CONFIG"));
+    }
+
+    #[tokio::test]
+    async fn connect_saved_restores_other_networks() {
+        let fake = Fake::start("saved", &[CONNECTED]);
+        let result = fake.backend().connect_saved("Work", None, 5).await;
+        assert!(
+            matches!(result, ConnectResult::Success { .. }),
+            "{result:?}"
+        );
+        assert!(fake.sent_before("BSSID 1 any", "SELECT_NETWORK 1"));
+        assert!(fake.sent_before("SELECT_NETWORK 1", "ENABLE_NETWORK all"));
+    }
+
+    #[test]
+    fn key_commands_follow_security() {
+        let cmds = |p: Option<&str>, sec: Option<Security>| key_commands("0", p, sec.as_ref());
+        assert_eq!(cmds(None, None), ["SET_NETWORK 0 key_mgmt NONE"]);
+        assert_eq!(
+            cmds(Some("secret12"), None),
+            [
+                "SET_NETWORK 0 key_mgmt WPA-PSK",
+                "SET_NETWORK 0 psk \"secret12\""
+            ]
+        );
+        assert_eq!(
+            cmds(Some("abcdefghijklm"), Some(Security::wep())),
+            [
+                "SET_NETWORK 0 key_mgmt NONE",
+                "SET_NETWORK 0 wep_key0 \"abcdefghijklm\"",
+                "SET_NETWORK 0 wep_tx_keyidx 0"
+            ]
+        );
+        assert_eq!(
+            cmds(Some("0123456789"), Some(Security::wep()))[1],
+            "SET_NETWORK 0 wep_key0 0123456789"
+        );
+        // SAE 不接受原始 PSK，64 位十六进制也按口令写入
+        let hex64 = "a".repeat(64);
+        let sae = Security {
+            key_mgmt: vec![KeyMgmt::Sae],
+            ..Default::default()
+        };
+        assert_eq!(
+            cmds(Some(&hex64), Some(sae))[2],
+            format!("SET_NETWORK 0 psk \"{hex64}\"")
+        );
+    }
+
+    #[test]
+    fn decode_ssid_unescapes_printf_encoding() {
+        assert_eq!(decode_ssid("plain"), "plain");
+        assert_eq!(decode_ssid("\\xe4\\xb8\\xad"), "中");
+        assert_eq!(decode_ssid("a\\\\b\\\"c"), "a\\b\"c");
+        assert_eq!(decode_ssid("tab\\there"), "tab\there");
+        assert_eq!(decode_ssid("bad\\xzz"), "bad\\xzz");
+        assert_eq!(decode_ssid("end\\"), "end\\");
+    }
+}